@@ -0,0 +1,161 @@
+use std::{fmt, future::Future, io, pin::Pin, sync::Arc};
+
+use super::AuthToken;
+use crate::errors::{Neo4jError, Result};
+
+/// The future returned by [`AuthTokenManager::get_token`].
+pub type AuthTokenFuture<'a> = Pin<Box<dyn Future<Output = io::Result<AuthToken>> + Send + 'a>>;
+
+/// Provides the credentials for new connections, for tokens that expire and need to be rotated.
+///
+/// A manager is set with [`crate::ConfigBuilder::with_auth_token_manager`].
+/// The token is asked for whenever a connection is created or taken from the pool,
+/// and pooled connections that authenticated with a different token authenticate again
+/// with a LOGOFF and LOGON since Bolt 5.1, or are closed on older servers.
+/// Implementations should therefore cache the token until it expires.
+///
+/// # Example
+///
+/// ```no_run
+/// use neo4rs::{AuthToken, AuthTokenFuture, AuthTokenManager, Neo4jError};
+/// use std::sync::Mutex;
+///
+/// struct Sso {
+///     token: Mutex<Option<AuthToken>>,
+/// }
+///
+/// impl AuthTokenManager for Sso {
+///     fn get_token(&self) -> AuthTokenFuture<'_> {
+///         Box::pin(async move {
+///             if let Some(token) = self.token.lock().unwrap().clone() {
+///                 return Ok(token);
+///             }
+///             let token = AuthToken::bearer(std::env::var("SSO_TOKEN").unwrap_or_default());
+///             *self.token.lock().unwrap() = Some(token.clone());
+///             Ok(token)
+///         })
+///     }
+///
+///     fn handle_security_error(&self, token: &AuthToken, _error: &Neo4jError) -> bool {
+///         let mut current = self.token.lock().unwrap();
+///         if current.as_ref() == Some(token) {
+///             *current = None;
+///         }
+///         true
+///     }
+/// }
+/// ```
+pub trait AuthTokenManager: Send + Sync {
+    /// Returns the token to authenticate with.
+    fn get_token(&self) -> AuthTokenFuture<'_>;
+
+    /// Called when a query failed with a security error,
+    /// e.g. [`crate::Neo4jSecurityErrorKind::TokenExpired`],
+    /// on a connection that authenticated with `token`.
+    ///
+    /// Return `true` if [`AuthTokenManager::get_token`] will return a fresh token.
+    /// Queries that failed because the token expired are then retried with a new connection.
+    fn handle_security_error(&self, token: &AuthToken, error: &Neo4jError) -> bool;
+}
+
+/// The credentials in the [`crate::Config`], either a fixed token or a manager.
+#[derive(Clone)]
+pub(crate) enum Auth {
+    Token(AuthToken),
+    Manager(Arc<dyn AuthTokenManager>),
+}
+
+impl Auth {
+    pub(crate) async fn token(&self) -> Result<AuthToken> {
+        match self {
+            Auth::Token(token) => Ok(token.clone()),
+            Auth::Manager(manager) => Ok(manager.get_token().await?),
+        }
+    }
+
+    /// Whether the query that failed with `error` can be retried with a fresh token.
+    pub(crate) fn handle_security_error(&self, token: &AuthToken, error: &Neo4jError) -> bool {
+        match self {
+            Auth::Token(_) => false,
+            Auth::Manager(manager) => manager.handle_security_error(token, error),
+        }
+    }
+}
+
+impl From<AuthToken> for Auth {
+    fn from(token: AuthToken) -> Self {
+        Auth::Token(token)
+    }
+}
+
+impl PartialEq for Auth {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Auth::Token(a), Auth::Token(b)) => a == b,
+            (Auth::Manager(a), Auth::Manager(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Debug for Auth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Auth::Token(token) => token.fmt(f),
+            Auth::Manager(_) => f.write_str("AuthTokenManager(..)"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    struct Rotating {
+        tokens: Mutex<Vec<AuthToken>>,
+    }
+
+    impl AuthTokenManager for Rotating {
+        fn get_token(&self) -> AuthTokenFuture<'_> {
+            Box::pin(async move { Ok(self.tokens.lock().unwrap()[0].clone()) })
+        }
+
+        fn handle_security_error(&self, token: &AuthToken, _error: &Neo4jError) -> bool {
+            let mut tokens = self.tokens.lock().unwrap();
+            if tokens[0] == *token && tokens.len() > 1 {
+                tokens.remove(0);
+            }
+            true
+        }
+    }
+
+    fn expired() -> Neo4jError {
+        Neo4jError::new(
+            "Neo.ClientError.Security.TokenExpired".into(),
+            "Token expired".into(),
+        )
+    }
+
+    #[tokio::test]
+    async fn should_rotate_managed_tokens() {
+        let auth = Auth::Manager(Arc::new(Rotating {
+            tokens: Mutex::new(vec![AuthToken::bearer("one"), AuthToken::bearer("two")]),
+        }));
+
+        let token = auth.token().await.unwrap();
+        assert_eq!(token, AuthToken::bearer("one"));
+
+        assert!(auth.handle_security_error(&token, &expired()));
+        assert_eq!(auth.token().await.unwrap(), AuthToken::bearer("two"));
+    }
+
+    #[tokio::test]
+    async fn should_not_retry_fixed_tokens() {
+        let auth = Auth::from(AuthToken::bearer("one"));
+
+        let token = auth.token().await.unwrap();
+        assert_eq!(token, AuthToken::bearer("one"));
+        assert!(!auth.handle_security_error(&token, &expired()));
+    }
+}
@@ -0,0 +1,254 @@
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
+use crate::types::{BoltMap, BoltType};
+
+/// The credentials used to authenticate with the server, see [`crate::ConfigBuilder::auth`].
+///
+/// # Example
+///
+/// ```
+/// use neo4rs::{AuthToken, ConfigBuilder};
+///
+/// let config = ConfigBuilder::default()
+///     .uri("neo4j://localhost:7687")
+///     .auth(AuthToken::bearer("eyJhbGciOiJIUzI1NiJ9..."))
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Clone, PartialEq)]
+pub struct AuthToken {
+    scheme: Arc<str>,
+    principal: Option<Arc<str>>,
+    credentials: Option<Arc<str>>,
+    realm: Option<Arc<str>>,
+    parameters: Option<BoltMap>,
+}
+
+impl AuthToken {
+    /// Authenticate with a username and password.
+    pub fn basic(user: impl Into<String>, password: impl Into<String>) -> Self {
+        Self::custom("basic", user, password)
+    }
+
+    /// Authenticate with a token from a single sign-on provider, e.g. a JWT.
+    pub fn bearer(token: impl Into<String>) -> Self {
+        Self {
+            credentials: Some(token.into().into()),
+            ..Self::new("bearer")
+        }
+    }
+
+    /// Authenticate with a base64 encoded Kerberos ticket.
+    pub fn kerberos(base64_ticket: impl Into<String>) -> Self {
+        Self::custom("kerberos", "", base64_ticket)
+    }
+
+    /// Do not authenticate, for servers that have authentication disabled.
+    pub fn none() -> Self {
+        Self::new("none")
+    }
+
+    /// Authenticate with a scheme that is implemented by a server plugin.
+    ///
+    /// Use [`AuthToken::with_realm`] and [`AuthToken::with_parameters`]
+    /// for any further fields that the plugin expects.
+    pub fn custom(
+        scheme: impl Into<String>,
+        principal: impl Into<String>,
+        credentials: impl Into<String>,
+    ) -> Self {
+        Self {
+            principal: Some(principal.into().into()),
+            credentials: Some(credentials.into().into()),
+            ..Self::new(scheme)
+        }
+    }
+
+    /// The realm of the principal, for the `basic` and custom schemes.
+    pub fn with_realm(self, realm: impl Into<String>) -> Self {
+        Self {
+            realm: Some(realm.into().into()),
+            ..self
+        }
+    }
+
+    /// Additional parameters for a custom scheme.
+    pub fn with_parameters(self, parameters: BoltMap) -> Self {
+        Self {
+            parameters: Some(parameters),
+            ..self
+        }
+    }
+
+    fn new(scheme: impl Into<String>) -> Self {
+        Self {
+            scheme: scheme.into().into(),
+            principal: None,
+            credentials: None,
+            realm: None,
+            parameters: None,
+        }
+    }
+
+    /// Adds the fields of the token to the extra map of a HELLO or LOGON.
+    #[cfg_attr(feature = "unstable-bolt-protocol-impl-v2", allow(dead_code))]
+    pub(crate) fn write_into(&self, extra: &mut BoltMap) {
+        extra.put("scheme".into(), (*self.scheme).into());
+        let fields = [
+            ("principal", &self.principal),
+            ("credentials", &self.credentials),
+            ("realm", &self.realm),
+        ];
+        for (key, value) in fields {
+            if let Some(value) = value {
+                extra.put(key.into(), (**value).into());
+            }
+        }
+        if let Some(parameters) = &self.parameters {
+            extra.put("parameters".into(), BoltType::Map(parameters.clone()));
+        }
+    }
+}
+
+impl fmt::Debug for AuthToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuthToken")
+            .field("scheme", &self.scheme)
+            .field("principal", &self.principal)
+            .field("realm", &self.realm)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Eq for AuthToken {}
+
+/// The parameters are left out, since their values cannot be hashed.
+impl Hash for AuthToken {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.scheme.hash(state);
+        self.principal.hash(state);
+        self.credentials.hash(state);
+        self.realm.hash(state);
+    }
+}
+
+#[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+mod extra {
+    use bytes::BytesMut;
+    use serde::{ser::SerializeMap as _, Serialize};
+
+    use super::AuthToken;
+    use crate::{packstream::RawBytes, types::BoltWireFormat as _, Version};
+
+    /// The fields of an [`AuthToken`] in the extra map of a HELLO or LOGON.
+    #[derive(Debug, Clone, PartialEq)]
+    pub(crate) struct AuthExtra<'a> {
+        token: &'a AuthToken,
+        version: Version,
+    }
+
+    impl AuthToken {
+        /// The parameters can contain any value, which are encoded depending on the version.
+        pub(crate) fn for_version(&self, version: Version) -> AuthExtra<'_> {
+            AuthExtra {
+                token: self,
+                version,
+            }
+        }
+    }
+
+    impl Serialize for AuthExtra<'_> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            let token = self.token;
+            let mut map = serializer.serialize_map(None)?;
+            map.serialize_entry("scheme", &*token.scheme)?;
+            let fields = [
+                ("principal", &token.principal),
+                ("credentials", &token.credentials),
+                ("realm", &token.realm),
+            ];
+            for (key, value) in fields {
+                if let Some(value) = value {
+                    map.serialize_entry(key, &**value)?;
+                }
+            }
+            if let Some(parameters) = &token.parameters {
+                let mut bytes = BytesMut::new();
+                parameters
+                    .write_into(self.version.into(), &mut bytes)
+                    .map_err(serde::ser::Error::custom)?;
+                map.serialize_entry("parameters", &RawBytes(bytes.freeze()))?;
+            }
+            map.end()
+        }
+    }
+}
+
+#[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+pub(crate) use extra::AuthExtra;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extra(token: &AuthToken) -> BoltMap {
+        let mut extra = BoltMap::default();
+        token.write_into(&mut extra);
+        extra
+    }
+
+    #[test]
+    fn should_write_basic_with_realm() {
+        let token = AuthToken::basic("neo4j", "secret").with_realm("native");
+
+        let expected: BoltMap = [
+            ("scheme".into(), "basic".into()),
+            ("principal".into(), "neo4j".into()),
+            ("credentials".into(), "secret".into()),
+            ("realm".into(), "native".into()),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(extra(&token), expected);
+    }
+
+    #[test]
+    fn should_write_bearer_and_none() {
+        let expected: BoltMap = [
+            ("scheme".into(), "bearer".into()),
+            ("credentials".into(), "token".into()),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(extra(&AuthToken::bearer("token")), expected);
+
+        let expected: BoltMap = [("scheme".into(), "none".into())].into_iter().collect();
+        assert_eq!(extra(&AuthToken::none()), expected);
+    }
+
+    #[test]
+    fn should_write_custom_parameters() {
+        let parameters: BoltMap = [("tenant".into(), "acme".into())].into_iter().collect();
+        let token = AuthToken::custom("plugin", "neo4j", "secret").with_parameters(parameters);
+
+        assert_eq!(
+            extra(&token).get::<BoltMap>("parameters").unwrap(),
+            [("tenant".into(), "acme".into())].into_iter().collect()
+        );
+    }
+
+    #[test]
+    fn should_hide_credentials_in_debug() {
+        let debug = format!("{:?}", AuthToken::basic("neo4j", "secret"));
+
+        assert!(debug.contains("neo4j"));
+        assert!(!debug.contains("secret"));
+    }
+}
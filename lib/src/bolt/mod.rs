@@ -14,7 +14,8 @@ mod structs;
 mod summary;
 
 pub use request::{
-    Commit, Discard, Goodbye, Hello, HelloBuilder, Pull, Reset, Rollback, WrapExtra,
+    Begin, Commit, Discard, DiscardAll, Goodbye, Hello, HelloBuilder, Logoff, Logon, Pull, PullAll,
+    Reset, Rollback, Run, RunResponse, Telemetry, TxExtra, WrapExtra,
 };
 pub use structs::{
     Bolt, BoltRef, Date, DateDuration, DateTime, DateTimeZoneId, DateTimeZoneIdRef, Duration,
@@ -0,0 +1,95 @@
+use serde::Serialize;
+
+use crate::{
+    bolt::{ExpectedResponse, Summary},
+    errors::Result,
+    version::Encoding,
+    Version,
+};
+
+use super::tx_extra::{Meta, TxExtra};
+
+#[derive(Debug, Clone)]
+pub struct Begin<'a> {
+    extra: Meta<'a>,
+}
+
+impl<'a> Begin<'a> {
+    pub fn new(extra: TxExtra<'a>, version: Version) -> Result<Self> {
+        Self::with_encoding(extra, version.into())
+    }
+
+    /// Like [`Begin::new`], but also applying the patches that the server accepted.
+    pub(crate) fn with_encoding(extra: TxExtra<'a>, encoding: Encoding) -> Result<Self> {
+        let extra = extra.for_version(encoding)?;
+        Ok(Self { extra })
+    }
+}
+
+impl Serialize for Begin<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_newtype_variant("Request", 0x11, "BEGIN", &self.extra)
+    }
+}
+
+impl ExpectedResponse for Begin<'_> {
+    type Response = Summary<()>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        bolt::{Message as _, MessageResponse as _},
+        config::{NotificationFilter, NotificationMinimumSeverity},
+        packstream::bolt,
+        Operation,
+    };
+
+    #[test]
+    fn serialize() {
+        let begin = Begin::new(TxExtra::new(), Version::V4_4).unwrap();
+        let bytes = begin.to_bytes().unwrap();
+
+        let expected = bolt().structure(1, 0x11).tiny_map(0).build();
+
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn serialize_with_extra() {
+        let notifications =
+            NotificationFilter::new().with_minimum_severity(NotificationMinimumSeverity::Warning);
+        let extra = TxExtra::new()
+            .with_db("neo4j")
+            .with_mode(Operation::Read)
+            .with_notification_filter(&notifications);
+        let begin = Begin::new(extra, Version::V5_2).unwrap();
+        let bytes = begin.to_bytes().unwrap();
+
+        let expected = bolt()
+            .structure(1, 0x11)
+            .tiny_map(3)
+            .tiny_string("mode")
+            .tiny_string("r")
+            .tiny_string("db")
+            .tiny_string("neo4j")
+            .string8("notifications_minimum_severity")
+            .tiny_string("WARNING")
+            .build();
+
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn parse() {
+        let data = bolt().structure(1, 0x70).tiny_map(0).build();
+
+        let response = <Begin as ExpectedResponse>::Response::parse(data).unwrap();
+
+        assert!(matches!(response, Summary::Success(_)));
+    }
+}
@@ -36,6 +36,23 @@ impl ExpectedResponse for Discard {
     type Response = Summary<Streaming>;
 }
 
+/// DISCARD_ALL from Bolt 3, which has no query ids and always applies to all records.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct DiscardAll;
+
+impl Serialize for DiscardAll {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_unit_variant("Request", 0x2F, "DISCARD_ALL")
+    }
+}
+
+impl ExpectedResponse for DiscardAll {
+    type Response = Summary<Streaming>;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -76,6 +93,15 @@ mod tests {
         assert_eq!(bytes, expected);
     }
 
+    #[test]
+    fn serialize_discard_all() {
+        let bytes = DiscardAll.to_bytes().unwrap();
+
+        let expected = bolt().structure(0, 0x2F).build();
+
+        assert_eq!(bytes, expected);
+    }
+
     #[test]
     fn parse() {
         let data = bolt()
@@ -1,32 +1,34 @@
 use std::{borrow::Borrow, collections::HashMap};
 
 use crate::{
+    auth::{AuthExtra, AuthToken},
     bolt::{ExpectedResponse, Summary},
+    config::{NotificationExtra, NotificationFilter},
+    connection::Hints,
+    version::BOLT_AGENT,
     Version,
 };
 use serde::{ser::SerializeMap, Deserialize, Serialize};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Hello<'a> {
     metadata: Meta<'a>,
 }
 
 pub struct HelloBuilder<'a> {
-    scheme: &'a str,
-    principal: &'a str,
-    credentials: &'a str,
+    auth: &'a AuthToken,
     user_agent: &'a str,
     routing: ServerRouting<'a>,
+    notifications: Option<&'a NotificationFilter>,
 }
 
 impl<'a> HelloBuilder<'a> {
-    pub fn new(principal: &'a str, credentials: &'a str) -> Self {
+    pub fn new(auth: &'a AuthToken) -> Self {
         Self {
-            scheme: "basic",
-            principal,
-            credentials,
+            auth,
             user_agent: "neo4rs",
             routing: ServerRouting::No,
+            notifications: None,
         }
     }
 
@@ -43,44 +45,81 @@ impl<'a> HelloBuilder<'a> {
         HelloBuilder { routing, ..self }
     }
 
+    pub fn with_notification_filter(self, notifications: &'a NotificationFilter) -> Self {
+        Self {
+            notifications: Some(notifications),
+            ..self
+        }
+    }
+
     pub fn build(self, version: Version) -> Hello<'a> {
         let Self {
-            scheme,
-            principal,
-            credentials,
+            auth,
             user_agent,
             mut routing,
+            notifications,
         } = self;
 
         if version < Version::V4_1 {
             routing = ServerRouting::No;
         }
 
+        // Starting with 5.1, the credentials are sent with a separate LOGON message
+        let auth = (version < Version::V5_1).then(|| auth.for_version(version));
+
+        // Bolt 4.4 can send date times in UTC, like 5.0 does, if the server accepts the patch
+        let patch_bolt: &[&str] = if version == Version::V4_4 {
+            &["utc"]
+        } else {
+            &[]
+        };
+
+        // Notification filtering was introduced with 5.2
+        let notifications = notifications
+            .filter(|_| version >= Version::V5_2)
+            .map(|n| n.for_version(version));
+
+        // Starting with 5.3, the driver identifies itself with a bolt agent
+        let bolt_agent = (version >= Version::V5_3).then_some(BoltAgent {
+            product: BOLT_AGENT,
+        });
+
         let metadata = Meta {
+            auth,
             user_agent,
-            scheme,
-            principal,
-            credentials,
             routing,
+            patch_bolt,
+            notifications,
+            bolt_agent,
         };
         Hello { metadata }
     }
 }
 
 impl<'a> Hello<'a> {
-    pub fn builder(principal: &'a str, credentials: &'a str) -> HelloBuilder<'a> {
-        HelloBuilder::new(principal, credentials)
+    pub fn builder(auth: &'a AuthToken) -> HelloBuilder<'a> {
+        HelloBuilder::new(auth)
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 struct Meta<'a> {
-    scheme: &'a str,
-    principal: &'a str,
-    credentials: &'a str,
+    #[serde(flatten)]
+    auth: Option<AuthExtra<'a>>,
     user_agent: &'a str,
     #[serde(skip_serializing_if = "ServerRouting::is_none")]
     routing: ServerRouting<'a>,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    patch_bolt: &'static [&'static str],
+    #[serde(flatten)]
+    notifications: Option<NotificationExtra<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bolt_agent: Option<BoltAgent>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct BoltAgent {
+    product: &'static str,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -119,6 +158,10 @@ impl Serialize for ServerRouting<'_> {
 pub struct Response {
     pub(crate) server: String,
     pub(crate) connection_id: String,
+    #[serde(default)]
+    pub(crate) patch_bolt: Vec<String>,
+    #[serde(default)]
+    pub(crate) hints: Hints,
 }
 
 impl ExpectedResponse for Hello<'_> {
@@ -139,12 +182,14 @@ mod tests {
     use super::*;
     use crate::{
         bolt::{Message as _, MessageResponse as _},
+        config::{NotificationCategory, NotificationMinimumSeverity},
         packstream::bolt,
     };
 
     #[test]
     fn serialize() {
-        let hello = Hello::builder("user", "pass").build(Version::V4_1);
+        let auth = AuthToken::basic("user", "pass");
+        let hello = Hello::builder(&auth).build(Version::V4_1);
         let bytes = hello.to_bytes().unwrap();
 
         let expected = bolt()
@@ -163,11 +208,61 @@ mod tests {
         assert_eq!(bytes, expected);
     }
 
+    #[test]
+    fn serialize_bearer_token() {
+        let auth = AuthToken::bearer("token");
+        let hello = Hello::builder(&auth).build(Version::V4_1);
+        let bytes = hello.to_bytes().unwrap();
+
+        let expected = bolt()
+            .structure(1, 0x01)
+            .tiny_map(3)
+            .tiny_string("scheme")
+            .tiny_string("bearer")
+            .tiny_string("credentials")
+            .tiny_string("token")
+            .tiny_string("user_agent")
+            .tiny_string("neo4rs")
+            .build();
+
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn serialize_custom_token() {
+        let parameters = [("tenant".into(), "acme".into())].into_iter().collect();
+        let auth = AuthToken::custom("plugin", "user", "pass")
+            .with_realm("ldap")
+            .with_parameters(parameters);
+        let hello = Hello::builder(&auth).build(Version::V4_1);
+        let bytes = hello.to_bytes().unwrap();
+
+        let expected = bolt()
+            .structure(1, 0x01)
+            .tiny_map(6)
+            .tiny_string("scheme")
+            .tiny_string("plugin")
+            .tiny_string("principal")
+            .tiny_string("user")
+            .tiny_string("credentials")
+            .tiny_string("pass")
+            .tiny_string("realm")
+            .tiny_string("ldap")
+            .tiny_string("parameters")
+            .tiny_map(1)
+            .tiny_string("tenant")
+            .tiny_string("acme")
+            .tiny_string("user_agent")
+            .tiny_string("neo4rs")
+            .build();
+
+        assert_eq!(bytes, expected);
+    }
+
     #[test]
     fn serialize_with_server_side_routing() {
-        let hello = Hello::builder("user", "pass")
-            .with_routing([])
-            .build(Version::V4_1);
+        let auth = AuthToken::basic("user", "pass");
+        let hello = Hello::builder(&auth).with_routing([]).build(Version::V4_1);
         let bytes = hello.to_bytes().unwrap();
 
         let expected = bolt()
@@ -190,7 +285,8 @@ mod tests {
 
     #[test]
     fn serialize_with_routing_context() {
-        let hello = Hello::builder("user", "pass")
+        let auth = AuthToken::basic("user", "pass");
+        let hello = Hello::builder(&auth)
             .with_routing([("region", "eu-west-1"), ("zone", "a")])
             .build(Version::V4_1);
         let bytes = hello.to_bytes().unwrap();
@@ -219,9 +315,8 @@ mod tests {
 
     #[test]
     fn serialize_routing_based_on_version() {
-        let hello = Hello::builder("user", "pass")
-            .with_routing([])
-            .build(Version::V4);
+        let auth = AuthToken::basic("user", "pass");
+        let hello = Hello::builder(&auth).with_routing([]).build(Version::V4);
         let bytes = hello.to_bytes().unwrap();
 
         let expected = bolt()
@@ -240,6 +335,127 @@ mod tests {
         assert_eq!(bytes, expected);
     }
 
+    #[test]
+    fn serialize_without_credentials_on_5_1() {
+        let auth = AuthToken::basic("user", "pass");
+        let hello = Hello::builder(&auth).with_routing([]).build(Version::V5_1);
+        let bytes = hello.to_bytes().unwrap();
+
+        let expected = bolt()
+            .structure(1, 0x01)
+            .tiny_map(2)
+            .tiny_string("user_agent")
+            .tiny_string("neo4rs")
+            .tiny_string("routing")
+            .tiny_map(0)
+            .build();
+
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn serialize_utc_patch_on_4_4() {
+        let auth = AuthToken::basic("user", "pass");
+        let hello = Hello::builder(&auth).build(Version::V4_4);
+        let bytes = hello.to_bytes().unwrap();
+
+        let expected = bolt()
+            .structure(1, 0x01)
+            .tiny_map(5)
+            .tiny_string("scheme")
+            .tiny_string("basic")
+            .tiny_string("principal")
+            .tiny_string("user")
+            .tiny_string("credentials")
+            .tiny_string("pass")
+            .tiny_string("user_agent")
+            .tiny_string("neo4rs")
+            .tiny_string("patch_bolt")
+            .tiny_list(1)
+            .tiny_string("utc")
+            .build();
+
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn serialize_notification_filter_on_5_2() {
+        let notifications = NotificationFilter::new()
+            .with_minimum_severity(NotificationMinimumSeverity::Warning)
+            .with_disabled_categories([NotificationCategory::Hint]);
+        let auth = AuthToken::basic("user", "pass");
+        let hello = Hello::builder(&auth)
+            .with_notification_filter(&notifications)
+            .build(Version::V5_2);
+        let bytes = hello.to_bytes().unwrap();
+
+        let expected = bolt()
+            .structure(1, 0x01)
+            .tiny_map(3)
+            .tiny_string("user_agent")
+            .tiny_string("neo4rs")
+            .string8("notifications_minimum_severity")
+            .tiny_string("WARNING")
+            .string8("notifications_disabled_categories")
+            .tiny_list(1)
+            .tiny_string("HINT")
+            .build();
+
+        assert_eq!(bytes, expected);
+
+        let hello = Hello::builder(&auth)
+            .with_notification_filter(&notifications)
+            .build(Version::V5_1);
+        let bytes = hello.to_bytes().unwrap();
+
+        let expected = bolt()
+            .structure(1, 0x01)
+            .tiny_map(1)
+            .tiny_string("user_agent")
+            .tiny_string("neo4rs")
+            .build();
+
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn serialize_bolt_agent_on_5_3() {
+        let auth = AuthToken::basic("user", "pass");
+        let hello = Hello::builder(&auth).build(Version::V5_3);
+        let bytes = hello.to_bytes().unwrap();
+
+        let expected = bolt()
+            .structure(1, 0x01)
+            .tiny_map(2)
+            .tiny_string("user_agent")
+            .tiny_string("neo4rs")
+            .tiny_string("bolt_agent")
+            .tiny_map(1)
+            .tiny_string("product")
+            .string8(BOLT_AGENT)
+            .build();
+
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn parse_accepted_patches() {
+        let data = bolt()
+            .tiny_map(3)
+            .tiny_string("server")
+            .tiny_string("Neo4j/4.4.30")
+            .tiny_string("connection_id")
+            .tiny_string("bolt-31")
+            .tiny_string("patch_bolt")
+            .tiny_list(1)
+            .tiny_string("utc")
+            .build();
+
+        let response = Response::parse(data).unwrap();
+
+        assert_eq!(response.patch_bolt, ["utc"]);
+    }
+
     #[test]
     fn parse() {
         let data = bolt()
@@ -254,5 +470,29 @@ mod tests {
 
         assert_eq!(response.server, "Neo4j/4.1.4");
         assert_eq!(response.connection_id, "bolt-31");
+        assert!(response.patch_bolt.is_empty());
+        assert!(!response.hints.telemetry_enabled);
+    }
+
+    #[test]
+    fn parse_hints() {
+        let data = bolt()
+            .tiny_map(3)
+            .tiny_string("server")
+            .tiny_string("Neo4j/5.20.0")
+            .tiny_string("connection_id")
+            .tiny_string("bolt-31")
+            .tiny_string("hints")
+            .tiny_map(2)
+            .string8("connection.recv_timeout_seconds")
+            .tiny_int(120)
+            .string8("telemetry.enabled")
+            .bool(true)
+            .build();
+
+        let response = Response::parse(data).unwrap();
+
+        assert!(response.hints.telemetry_enabled);
+        assert_eq!(response.hints.recv_timeout_seconds, Some(120));
     }
 }
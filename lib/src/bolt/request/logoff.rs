@@ -0,0 +1,35 @@
+use crate::bolt::{ExpectedResponse, Summary};
+use serde::Serialize;
+
+/// Removes the authentication from a connection on Bolt 5.1 or newer,
+/// so that it can be authenticated again with a [`super::Logon`].
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct Logoff;
+
+impl ExpectedResponse for Logoff {
+    type Response = Summary<()>;
+}
+
+impl Serialize for Logoff {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_unit_variant("Request", 0x6B, "LOGOFF")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bolt::Message as _, packstream::bolt};
+
+    #[test]
+    fn serialize() {
+        let bytes = Logoff.to_bytes().unwrap();
+
+        let expected = bolt().structure(0, 0x6B).build();
+
+        assert_eq!(bytes, expected);
+    }
+}
@@ -0,0 +1,76 @@
+use crate::{
+    auth::AuthToken,
+    bolt::{ExpectedResponse, Summary},
+    Version,
+};
+use serde::Serialize;
+
+/// Authenticates a connection on Bolt 5.1 or newer,
+/// where HELLO no longer carries any credentials.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Logon<'a> {
+    auth: &'a AuthToken,
+}
+
+impl<'a> Logon<'a> {
+    pub fn new(auth: &'a AuthToken) -> Self {
+        Self { auth }
+    }
+}
+
+impl ExpectedResponse for Logon<'_> {
+    type Response = Summary<()>;
+}
+
+impl Serialize for Logon<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        // LOGON was introduced with 5.1, and the encoding has not changed since
+        let auth = self.auth.for_version(Version::V5_1);
+        serializer.serialize_newtype_variant("Request", 0x6A, "LOGON", &auth)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bolt::Message as _, packstream::bolt};
+
+    #[test]
+    fn serialize() {
+        let auth = AuthToken::basic("user", "pass");
+        let logon = Logon::new(&auth);
+        let bytes = logon.to_bytes().unwrap();
+
+        let expected = bolt()
+            .structure(1, 0x6A)
+            .tiny_map(3)
+            .tiny_string("scheme")
+            .tiny_string("basic")
+            .tiny_string("principal")
+            .tiny_string("user")
+            .tiny_string("credentials")
+            .tiny_string("pass")
+            .build();
+
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn serialize_none() {
+        let auth = AuthToken::none();
+        let logon = Logon::new(&auth);
+        let bytes = logon.to_bytes().unwrap();
+
+        let expected = bolt()
+            .structure(1, 0x6A)
+            .tiny_map(1)
+            .tiny_string("scheme")
+            .tiny_string("none")
+            .build();
+
+        assert_eq!(bytes, expected);
+    }
+}
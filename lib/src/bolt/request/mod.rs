@@ -1,18 +1,30 @@
+mod begin;
 mod commit;
 mod discard;
 mod extra;
 mod goodbye;
 mod hello;
+mod logoff;
+mod logon;
 mod pull;
 mod reset;
 mod rollback;
 mod route;
+mod run;
+mod telemetry;
+mod tx_extra;
 
+pub use begin::Begin;
 pub use commit::Commit;
-pub use discard::Discard;
+pub use discard::{Discard, DiscardAll};
 pub use extra::WrapExtra;
 pub use goodbye::Goodbye;
 pub use hello::{Hello, HelloBuilder};
-pub use pull::Pull;
+pub use logoff::Logoff;
+pub use logon::Logon;
+pub use pull::{Pull, PullAll};
 pub use reset::Reset;
 pub use rollback::Rollback;
+pub use run::{Response as RunResponse, Run};
+pub use telemetry::Telemetry;
+pub use tx_extra::TxExtra;
@@ -36,6 +36,23 @@ impl ExpectedResponse for Pull {
     type Response = Summary<Streaming>;
 }
 
+/// PULL_ALL from Bolt 3, which has no query ids and always applies to all records.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct PullAll;
+
+impl Serialize for PullAll {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_unit_variant("Request", 0x3F, "PULL_ALL")
+    }
+}
+
+impl ExpectedResponse for PullAll {
+    type Response = Summary<Streaming>;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -76,6 +93,15 @@ mod tests {
         assert_eq!(bytes, expected);
     }
 
+    #[test]
+    fn serialize_pull_all() {
+        let bytes = PullAll.to_bytes().unwrap();
+
+        let expected = bolt().structure(0, 0x3F).build();
+
+        assert_eq!(bytes, expected);
+    }
+
     #[test]
     fn parse() {
         let data = bolt()
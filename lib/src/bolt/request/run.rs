@@ -0,0 +1,165 @@
+use bytes::BytesMut;
+use serde::{ser::SerializeStructVariant as _, Deserialize, Serialize};
+
+use crate::{
+    bolt::{ExpectedResponse, Summary},
+    errors::Result,
+    packstream::RawBytes,
+    types::{BoltMap, BoltWireFormat as _},
+    version::Encoding,
+    Version,
+};
+
+use super::tx_extra::{Meta, TxExtra};
+
+#[derive(Debug, Clone)]
+pub struct Run<'a> {
+    query: &'a str,
+    parameters: RawBytes,
+    extra: Meta<'a>,
+}
+
+impl<'a> Run<'a> {
+    /// The extra fields only apply to auto-commit transactions,
+    /// a RUN inside of an explicit transaction should use [`TxExtra::new`].
+    pub fn new(
+        query: &'a str,
+        parameters: &BoltMap,
+        extra: TxExtra<'a>,
+        version: Version,
+    ) -> Result<Self> {
+        Self::with_encoding(query, parameters, extra, version.into())
+    }
+
+    /// Like [`Run::new`], but also applying the patches that the server accepted.
+    pub(crate) fn with_encoding(
+        query: &'a str,
+        parameters: &BoltMap,
+        extra: TxExtra<'a>,
+        encoding: Encoding,
+    ) -> Result<Self> {
+        // Parameters can contain any value, which are encoded depending on the version
+        let mut bytes = BytesMut::new();
+        parameters.write_into(encoding, &mut bytes)?;
+        let parameters = RawBytes(bytes.freeze());
+        let extra = extra.for_version(encoding)?;
+        Ok(Self {
+            query,
+            parameters,
+            extra,
+        })
+    }
+}
+
+impl Serialize for Run<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut structure = serializer.serialize_struct_variant("Request", 0x10, "RUN", 3)?;
+        structure.serialize_field("query", self.query)?;
+        structure.serialize_field("parameters", &self.parameters)?;
+        structure.serialize_field("extra", &self.extra)?;
+        structure.end()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Response {
+    pub(crate) fields: Vec<String>,
+    #[serde(default)]
+    pub(crate) t_first: Option<i64>,
+    #[serde(default)]
+    pub(crate) qid: Option<i64>,
+}
+
+impl ExpectedResponse for Run<'_> {
+    type Response = Summary<Response>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        bolt::{Message as _, MessageResponse as _},
+        packstream::bolt,
+        types::BoltType,
+    };
+
+    #[test]
+    fn serialize() {
+        let parameters = [("name".into(), "neo4rs".into())].into_iter().collect();
+        let extra = TxExtra::new().with_db("neo4j");
+        let run = Run::new("RETURN $name", &parameters, extra, Version::V5_0).unwrap();
+        let bytes = run.to_bytes().unwrap();
+
+        let expected = bolt()
+            .structure(3, 0x10)
+            .tiny_string("RETURN $name")
+            .tiny_map(1)
+            .tiny_string("name")
+            .tiny_string("neo4rs")
+            .tiny_map(1)
+            .tiny_string("db")
+            .tiny_string("neo4j")
+            .build();
+
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn serialize_parameters_for_version() {
+        let date_time = chrono::DateTime::parse_from_rfc3339("2024-01-01T00:00:00+01:00").unwrap();
+        let parameters = [("at".into(), BoltType::DateTime(date_time.into()))]
+            .into_iter()
+            .collect::<BoltMap>();
+
+        let encodings = [
+            (Encoding::from(Version::V4_4), 0x46),
+            (Encoding::new(Version::V4_4, true), 0x49),
+            (Encoding::from(Version::V5_0), 0x49),
+        ];
+        for (encoding, tag) in encodings {
+            let run =
+                Run::with_encoding("RETURN $at", &parameters, TxExtra::new(), encoding).unwrap();
+            let bytes = run.to_bytes().unwrap();
+
+            let prefix = bolt()
+                .structure(3, 0x10)
+                .tiny_string("RETURN $at")
+                .tiny_map(1)
+                .tiny_string("at")
+                .structure(3, tag)
+                .build();
+            assert!(bytes.starts_with(&prefix));
+        }
+    }
+
+    #[test]
+    fn parse() {
+        let data = bolt()
+            .structure(1, 0x70)
+            .tiny_map(3)
+            .tiny_string("fields")
+            .tiny_list(1)
+            .tiny_string("n")
+            .tiny_string("t_first")
+            .tiny_int(2)
+            .tiny_string("qid")
+            .tiny_int(1)
+            .build();
+
+        let response = <Run as ExpectedResponse>::Response::parse(data).unwrap();
+
+        assert_eq!(
+            response,
+            Summary::Success(crate::bolt::Success {
+                metadata: Response {
+                    fields: vec!["n".into()],
+                    t_first: Some(2),
+                    qid: Some(1),
+                }
+            })
+        );
+    }
+}
@@ -0,0 +1,47 @@
+use crate::{
+    bolt::{ExpectedResponse, Summary},
+    messages::TelemetryApi,
+};
+use serde::Serialize;
+
+/// Tells a server on Bolt 5.4 or newer which driver API the next query was sent from.
+/// Only sent when the server enabled it with the `telemetry.enabled` hint.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Telemetry {
+    api: TelemetryApi,
+}
+
+impl Telemetry {
+    pub(crate) fn new(api: TelemetryApi) -> Self {
+        Self { api }
+    }
+}
+
+impl ExpectedResponse for Telemetry {
+    type Response = Summary<()>;
+}
+
+impl Serialize for Telemetry {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_newtype_variant("Request", 0x54, "TELEMETRY", &(self.api as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bolt::Message as _, packstream::bolt};
+
+    #[test]
+    fn serialize() {
+        let telemetry = Telemetry::new(TelemetryApi::UnmanagedTransaction);
+        let bytes = telemetry.to_bytes().unwrap();
+
+        let expected = bolt().structure(1, 0x54).tiny_int(1).build();
+
+        assert_eq!(bytes, expected);
+    }
+}
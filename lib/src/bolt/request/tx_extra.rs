@@ -0,0 +1,315 @@
+use std::time::Duration;
+
+use bytes::BytesMut;
+use serde::Serialize;
+
+use crate::{
+    config::{NotificationExtra, NotificationFilter},
+    errors::{Error, Result},
+    packstream::RawBytes,
+    types::{BoltMap, BoltType, BoltWireFormat as _},
+    version::Encoding,
+    Operation, Version,
+};
+
+/// The extra fields of a BEGIN, or of a RUN in an auto-commit transaction.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TxExtra<'a> {
+    bookmarks: Vec<&'a str>,
+    tx_timeout: Option<Duration>,
+    tx_metadata: Option<&'a BoltMap>,
+    mode: Option<Operation>,
+    db: Option<&'a str>,
+    imp_user: Option<&'a str>,
+    notifications: Option<&'a NotificationFilter>,
+}
+
+impl<'a> TxExtra<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_bookmarks(self, bookmarks: impl IntoIterator<Item = &'a str>) -> Self {
+        Self {
+            bookmarks: bookmarks.into_iter().collect(),
+            ..self
+        }
+    }
+
+    pub fn with_tx_timeout(self, tx_timeout: Duration) -> Self {
+        Self {
+            tx_timeout: Some(tx_timeout),
+            ..self
+        }
+    }
+
+    pub fn with_tx_metadata(self, tx_metadata: &'a BoltMap) -> Self {
+        Self {
+            tx_metadata: Some(tx_metadata),
+            ..self
+        }
+    }
+
+    pub fn with_mode(self, mode: Operation) -> Self {
+        Self {
+            mode: Some(mode),
+            ..self
+        }
+    }
+
+    pub fn with_db(self, db: &'a str) -> Self {
+        Self {
+            db: Some(db),
+            ..self
+        }
+    }
+
+    pub fn with_imp_user(self, imp_user: &'a str) -> Self {
+        Self {
+            imp_user: Some(imp_user),
+            ..self
+        }
+    }
+
+    pub fn with_notification_filter(self, notifications: &'a NotificationFilter) -> Self {
+        Self {
+            notifications: Some(notifications),
+            ..self
+        }
+    }
+
+    /// Reads the extra fields from the untyped map of a [`crate::Query`].
+    pub(crate) fn from_map(extra: &'a BoltMap) -> Result<Self> {
+        fn string<'a>(key: &str, value: &'a BoltType) -> Result<&'a str> {
+            match value {
+                BoltType::String(s) => Ok(&s.value),
+                otherwise => Err(invalid(key, otherwise)),
+            }
+        }
+
+        fn invalid(key: &str, value: &BoltType) -> Error {
+            Error::UnexpectedMessage(format!("invalid value for the extra `{key}`: {value:?}"))
+        }
+
+        let mut this = Self::new();
+        for (key, value) in &extra.value {
+            let key = key.value.as_str();
+            this = match (key, value) {
+                ("bookmarks", BoltType::List(bookmarks)) => {
+                    let bookmarks = bookmarks
+                        .value
+                        .iter()
+                        .map(|b| string(key, b))
+                        .collect::<Result<_>>()?;
+                    Self { bookmarks, ..this }
+                }
+                ("tx_timeout", BoltType::Integer(ms)) => {
+                    let ms = u64::try_from(ms.value).map_err(|_| invalid(key, value))?;
+                    this.with_tx_timeout(Duration::from_millis(ms))
+                }
+                ("tx_metadata", BoltType::Map(metadata)) => this.with_tx_metadata(metadata),
+                ("mode", value) => match string(key, value)? {
+                    "r" => this.with_mode(Operation::Read),
+                    "w" => this.with_mode(Operation::Write),
+                    _ => return Err(invalid(key, value)),
+                },
+                ("db", value) => this.with_db(string(key, value)?),
+                ("imp_user", value) => this.with_imp_user(string(key, value)?),
+                ("bookmarks" | "tx_timeout" | "tx_metadata", value) => {
+                    return Err(invalid(key, value))
+                }
+                (key, _) => {
+                    return Err(Error::UnexpectedMessage(format!(
+                        "unsupported transaction extra `{key}`"
+                    )))
+                }
+            };
+        }
+        Ok(this)
+    }
+
+    pub(crate) fn for_version(self, encoding: impl Into<Encoding>) -> Result<Meta<'a>> {
+        let encoding = encoding.into();
+        let version = encoding.version;
+        let Self {
+            bookmarks,
+            tx_timeout,
+            tx_metadata,
+            mode,
+            db,
+            imp_user,
+            notifications,
+        } = self;
+
+        // Databases were introduced with 4.0, impersonation with 4.4
+        if version < Version::V4 && db.is_some() {
+            return Err(Error::UnsupportedFeature("Selecting a database", version));
+        }
+        if version < Version::V4_4 && imp_user.is_some() {
+            return Err(Error::UnsupportedFeature("Impersonation", version));
+        }
+
+        let tx_timeout = tx_timeout
+            .map(|t| i64::try_from(t.as_millis()))
+            .transpose()
+            .map_err(|e| Error::IntegerOverflow("tx_timeout", e))?;
+
+        // The metadata can contain any value, which are encoded depending on the version
+        let tx_metadata = tx_metadata
+            .filter(|m| !m.value.is_empty())
+            .map(|m| {
+                let mut bytes = BytesMut::new();
+                m.write_into(encoding, &mut bytes)?;
+                Ok::<_, Error>(RawBytes(bytes.freeze()))
+            })
+            .transpose()?;
+
+        // The server defaults to write transactions
+        let mode = matches!(mode, Some(Operation::Read)).then_some("r");
+
+        // Notification filtering was introduced with 5.2
+        let notifications = notifications
+            .filter(|_| version >= Version::V5_2)
+            .map(|n| n.for_version(version));
+
+        Ok(Meta {
+            bookmarks,
+            tx_timeout,
+            tx_metadata,
+            mode,
+            db,
+            imp_user,
+            notifications,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct Meta<'a> {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    bookmarks: Vec<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tx_timeout: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tx_metadata: Option<RawBytes>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mode: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    db: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    imp_user: Option<&'a str>,
+    #[serde(flatten)]
+    notifications: Option<NotificationExtra<'a>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bolt::Message as _, packstream::bolt};
+
+    #[test]
+    fn serialize_empty() {
+        let extra = TxExtra::new().for_version(Version::V5_0).unwrap();
+        let bytes = extra.to_bytes().unwrap();
+
+        assert_eq!(bytes, bolt().tiny_map(0).build());
+    }
+
+    #[test]
+    fn serialize_all_fields() {
+        let metadata = [("app".into(), "neo4rs".into())].into_iter().collect();
+        let extra = TxExtra::new()
+            .with_bookmarks(["bm"])
+            .with_tx_timeout(Duration::from_secs(2))
+            .with_tx_metadata(&metadata)
+            .with_mode(Operation::Read)
+            .with_db("neo4j")
+            .with_imp_user("alice")
+            .for_version(Version::V5_0)
+            .unwrap();
+        let bytes = extra.to_bytes().unwrap();
+
+        let expected = bolt()
+            .tiny_map(6)
+            .tiny_string("bookmarks")
+            .tiny_list(1)
+            .tiny_string("bm")
+            .tiny_string("tx_timeout")
+            .int16(2000)
+            .tiny_string("tx_metadata")
+            .tiny_map(1)
+            .tiny_string("app")
+            .tiny_string("neo4rs")
+            .tiny_string("mode")
+            .tiny_string("r")
+            .tiny_string("db")
+            .tiny_string("neo4j")
+            .tiny_string("imp_user")
+            .tiny_string("alice")
+            .build();
+
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn omit_write_mode() {
+        let extra = TxExtra::new()
+            .with_mode(Operation::Write)
+            .for_version(Version::V5_0)
+            .unwrap();
+        let bytes = extra.to_bytes().unwrap();
+
+        assert_eq!(bytes, bolt().tiny_map(0).build());
+    }
+
+    #[test]
+    fn reject_unsupported_fields() {
+        let error = TxExtra::new()
+            .with_db("neo4j")
+            .for_version(Version::V3)
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            Error::UnsupportedFeature("Selecting a database", Version::V3)
+        ));
+
+        let error = TxExtra::new()
+            .with_imp_user("alice")
+            .for_version(Version::V4_3)
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            Error::UnsupportedFeature("Impersonation", Version::V4_3)
+        ));
+    }
+
+    #[test]
+    fn read_from_map() {
+        let map: BoltMap = [
+            ("db".into(), "neo4j".into()),
+            ("mode".into(), "r".into()),
+            ("tx_timeout".into(), 1500.into()),
+        ]
+        .into_iter()
+        .collect();
+
+        let extra = TxExtra::from_map(&map).unwrap();
+
+        assert_eq!(
+            extra,
+            TxExtra::new()
+                .with_db("neo4j")
+                .with_mode(Operation::Read)
+                .with_tx_timeout(Duration::from_millis(1500))
+        );
+    }
+
+    #[test]
+    fn reject_unknown_keys_from_map() {
+        let map: BoltMap = [("foo".into(), "bar".into())].into_iter().collect();
+
+        let error = TxExtra::from_map(&map).unwrap_err();
+
+        assert!(matches!(error, Error::UnexpectedMessage(_)));
+    }
+}
@@ -8,6 +8,8 @@ use serde::{
     Deserialize, Deserializer,
 };
 
+use crate::types::serde::BoltVectorVisitor;
+
 use super::{
     Bolt, BoltRef, Date, DateTime, DateTimeZoneId, DateTimeZoneIdRef, Duration, LegacyDateTime,
     LegacyDateTimeZoneId, LegacyDateTimeZoneIdRef, LocalDateTime, LocalTime, Node, NodeRef, Path,
@@ -147,6 +149,9 @@ impl<'de> Deserialize<'de> for BoltRef<'de> {
                     0x66 => data
                         .struct_variant(&[], LegacyDateTimeZoneIdRef::visitor())
                         .map(BoltRef::LegacyDateTimeZoneId),
+                    0x56 => data
+                        .struct_variant(&[], BoltVectorVisitor)
+                        .map(BoltRef::Vector),
                     0x72 => Err(Error::invalid_type(
                         serde::de::Unexpected::Other("unbounded relationship outside of a path"),
                         &"a valid Bolt struct",
@@ -306,6 +311,9 @@ impl<'de> Deserialize<'de> for Bolt {
                     0x66 => data
                         .struct_variant(&[], LegacyDateTimeZoneId::visitor())
                         .map(Bolt::LegacyDateTimeZoneId),
+                    0x56 => data
+                        .struct_variant(&[], BoltVectorVisitor)
+                        .map(Bolt::Vector),
                     0x72 => Err(Error::invalid_type(
                         serde::de::Unexpected::Other("unbounded relationship outside of a path"),
                         &"a valid Bolt struct",
@@ -639,4 +647,25 @@ mod tests {
         assert_eq!(email, Some("foo@bar.com".to_owned()));
         assert_eq!(missing, None);
     }
+
+    #[test]
+    fn vector() {
+        use crate::packstream::{bolt, from_bytes, from_bytes_ref, Data};
+
+        let data = bolt()
+            .structure(2, 0x56)
+            .bytes8(1, &[0xC6])
+            .bytes8(8, &[0x3F, 0x80, 0x00, 0x00, 0xC0, 0x20, 0x00, 0x00])
+            .build();
+
+        let bolt: Bolt = from_bytes(data.clone()).unwrap();
+        assert_eq!(bolt, Bolt::Vector(crate::BoltVector::F32(vec![1.0, -2.5])));
+
+        let mut data = Data::new(data);
+        let bolt: BoltRef = from_bytes_ref(&mut data).unwrap();
+        assert_eq!(
+            bolt,
+            BoltRef::Vector(crate::BoltVector::F32(vec![1.0, -2.5]))
+        );
+    }
 }
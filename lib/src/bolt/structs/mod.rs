@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use crate::types::BoltVector;
+
 pub use self::date::{Date, DateDuration};
 pub use self::datetime::{
     DateTime, DateTimeZoneId, DateTimeZoneIdRef, LegacyDateTime, LegacyDateTimeZoneId,
@@ -48,6 +50,7 @@ pub enum BoltRef<'de> {
     Point3D(Point3D),
     LegacyDateTime(LegacyDateTime),
     LegacyDateTimeZoneId(LegacyDateTimeZoneIdRef<'de>),
+    Vector(BoltVector),
 }
 
 impl From<()> for BoltRef<'_> {
@@ -92,6 +95,7 @@ impl_from_ref!(LegacyDateTimeZoneId(LegacyDateTimeZoneIdRef<'de>));
 impl_from_ref!(Duration(Duration));
 impl_from_ref!(Point2D(Point2D));
 impl_from_ref!(Point3D(Point3D));
+impl_from_ref!(Vector(BoltVector));
 
 macro_rules! impl_try_from_int_ref {
     ($($t:ty),*) => {
@@ -155,6 +159,7 @@ pub enum Bolt {
     Point3D(Point3D),
     LegacyDateTime(LegacyDateTime),
     LegacyDateTimeZoneId(LegacyDateTimeZoneId),
+    Vector(BoltVector),
 }
 
 impl From<()> for Bolt {
@@ -199,6 +204,7 @@ impl_from!(LegacyDateTimeZoneId(LegacyDateTimeZoneId));
 impl_from!(Duration(Duration));
 impl_from!(Point2D(Point2D));
 impl_from!(Point3D(Point3D));
+impl_from!(Vector(BoltVector));
 
 macro_rules! impl_try_from_int {
     ($($t:ty),*) => {
@@ -279,6 +285,7 @@ impl From<BoltRef<'_>> for Bolt {
             BoltRef::Point3D(v) => Self::Point3D(v),
             BoltRef::LegacyDateTime(v) => Self::LegacyDateTime(v),
             BoltRef::LegacyDateTimeZoneId(v) => Self::LegacyDateTimeZoneId(v.to_owned()),
+            BoltRef::Vector(v) => Self::Vector(v),
         }
     }
 }
@@ -295,12 +302,16 @@ impl From<Bolt> for crate::BoltType {
         fn conv_unrel(rel: urel::UnboundRelationship) -> BoltUnboundedRelation {
             let id = BoltInteger::new(rel.id().try_into().unwrap());
             let typ = BoltString::from(rel.typ());
+            let element_id = rel.element_id().map(BoltString::from);
             let properties = rel.into::<Bolt>().unwrap();
             let properties = BoltType::from(properties);
             let BoltType::Map(properties) = properties else {
                 panic!("properties should be a map");
             };
-            BoltUnboundedRelation::new(id, typ, properties)
+            BoltUnboundedRelation {
+                element_id,
+                ..BoltUnboundedRelation::new(id, typ, properties)
+            }
         }
 
         match value {
@@ -326,22 +337,29 @@ impl From<Bolt> for crate::BoltType {
                     .cloned()
                     .map(BoltType::from)
                     .collect::<Vec<_>>();
+                let element_id = v.element_id().map(BoltString::from);
                 let properties = v.into::<Bolt>().unwrap();
                 let properties = BoltType::from(properties);
                 let BoltType::Map(properties) = properties else {
                     panic!("properties should be a map");
                 };
-                Self::Node(BoltNode::new(
-                    BoltInteger::new(id.try_into().unwrap()),
-                    BoltList::from(labels),
-                    properties,
-                ))
+                Self::Node(BoltNode {
+                    element_id,
+                    ..BoltNode::new(
+                        BoltInteger::new(id.try_into().unwrap()),
+                        BoltList::from(labels),
+                        properties,
+                    )
+                })
             }
             Bolt::Relationship(v) => {
                 let id = v.id();
                 let start_node_id = v.start_node_id();
                 let end_node_id = v.end_node_id();
                 let typ = BoltString::from(v.typ());
+                let element_id = v.element_id().map(BoltString::from);
+                let start_node_element_id = v.start_node_element_id().map(BoltString::from);
+                let end_node_element_id = v.end_node_element_id().map(BoltString::from);
                 let properties = v.into::<Bolt>().unwrap();
                 let properties = BoltType::from(properties);
                 let BoltType::Map(properties) = properties else {
@@ -353,6 +371,9 @@ impl From<Bolt> for crate::BoltType {
                     end_node_id: BoltInteger::new(end_node_id.try_into().unwrap()),
                     typ,
                     properties,
+                    element_id,
+                    start_node_element_id,
+                    end_node_element_id,
                 })
             }
             Bolt::Path(v) => {
@@ -395,14 +416,19 @@ impl From<Bolt> for crate::BoltType {
                 nanoseconds: BoltInteger::new(v.nanoseconds_since_midnight().try_into().unwrap()),
             }),
             Bolt::DateTime(v) => Self::DateTime(BoltDateTime {
-                seconds: v.seconds_since_epoch().into(),
+                seconds: (v.seconds_since_epoch() + i64::from(v.timezone_offset_seconds())).into(),
                 nanoseconds: BoltInteger::new(v.nanoseconds().into()),
                 tz_offset_seconds: v.timezone_offset_seconds().into(),
             }),
-            Bolt::DateTimeZoneId(v) => Self::DateTimeZoneId(BoltDateTimeZoneId {
-                seconds: v.seconds_since_epoch().into(),
-                nanoseconds: BoltInteger::new(v.nanoseconds().into()),
-                tz_id: v.timezone_identifier().into(),
+            Bolt::DateTimeZoneId(v) => Self::DateTimeZoneId(match v.as_chrono_datetime() {
+                Some(datetime) => datetime.into(),
+                // the seconds are kept as sent if the zone is unknown
+                None => BoltDateTimeZoneId {
+                    seconds: v.seconds_since_epoch().into(),
+                    nanoseconds: BoltInteger::new(v.nanoseconds().into()),
+                    tz_id: v.timezone_identifier().into(),
+                    offset_seconds: None,
+                },
             }),
             Bolt::LocalDateTime(v) => Self::LocalDateTime(BoltLocalDateTime {
                 seconds: v.seconds_since_epoch().into(),
@@ -430,11 +456,12 @@ impl From<Bolt> for crate::BoltType {
                 nanoseconds: BoltInteger::new(v.nanoseconds().into()),
                 tz_offset_seconds: v.timezone_offset_seconds().into(),
             }),
-            Bolt::LegacyDateTimeZoneId(v) => Self::DateTimeZoneId(BoltDateTimeZoneId {
-                seconds: v.seconds_since_epoch().into(),
-                nanoseconds: BoltInteger::new(v.nanoseconds().into()),
-                tz_id: v.timezone_identifier().into(),
-            }),
+            Bolt::LegacyDateTimeZoneId(v) => Self::DateTimeZoneId(BoltDateTimeZoneId::from_local(
+                v.seconds_since_epoch().into(),
+                BoltInteger::new(v.nanoseconds().into()),
+                v.timezone_identifier().into(),
+            )),
+            Bolt::Vector(v) => Self::Vector(v),
         }
     }
 }
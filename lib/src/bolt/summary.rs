@@ -5,6 +5,8 @@ use serde::{
     Deserialize,
 };
 
+use crate::errors::{GqlDiagnosticRecord, GqlError, Neo4jError};
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Summary<R> {
     Success(Success<R>),
@@ -31,15 +33,59 @@ pub struct Success<R> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(from = "FailureMetadata")]
 pub struct Failure {
     pub(crate) code: String,
     pub(crate) message: String,
+    /// The GQL data, sent by servers since Bolt 5.7, boxed to keep errors small.
+    pub(crate) gql: Option<Box<GqlError>>,
+}
+
+#[derive(Deserialize)]
+struct FailureMetadata {
+    #[serde(alias = "neo4j_code")]
+    code: String,
+    message: String,
+    #[serde(default)]
+    gql_status: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    diagnostic_record: Option<GqlDiagnosticRecord>,
+    #[serde(default)]
+    cause: Option<Box<GqlError>>,
+}
+
+impl From<FailureMetadata> for Failure {
+    fn from(metadata: FailureMetadata) -> Self {
+        let FailureMetadata {
+            code,
+            message,
+            gql_status,
+            description,
+            diagnostic_record,
+            cause,
+        } = metadata;
+        let gql = gql_status.map(|gql_status| {
+            Box::new(GqlError::new(
+                gql_status,
+                message.clone(),
+                description.unwrap_or_default(),
+                diagnostic_record,
+                cause,
+            ))
+        });
+        Self { code, message, gql }
+    }
 }
 
 impl Failure {
     pub fn into_error(self) -> crate::errors::Error {
-        let Self { code, message } = self;
-        crate::errors::Error::Neo4j(crate::errors::Neo4jError::new(code, message))
+        let error = match self.gql {
+            Some(gql) => Neo4jError::with_gql(self.code, *gql),
+            None => Neo4jError::new(self.code, self.message),
+        };
+        crate::errors::Error::Neo4j(error)
     }
 }
 
@@ -134,4 +180,45 @@ mod tests {
             "The client is unauthorized due to authentication failure."
         );
     }
+
+    #[test]
+    fn parse_gql_failure() {
+        let data = bolt()
+            .structure(1, 0x7F)
+            .tiny_map(5)
+            .string8("neo4j_code")
+            .string8("Neo.TransientError.General.DatabaseUnavailable")
+            .tiny_string("message")
+            .string8("The database is unavailable.")
+            .string8("gql_status")
+            .tiny_string("08N00")
+            .string8("description")
+            .string8("error: connection exception")
+            .string8("diagnostic_record")
+            .tiny_map(1)
+            .string8("_classification")
+            .string8("TRANSIENT_ERROR")
+            .build();
+
+        let failure = Summary::<()>::parse(data).unwrap();
+
+        let failure = match failure {
+            Summary::Failure(failure) => failure,
+            _ => panic!("Expected failure"),
+        };
+
+        let error = match failure.into_error() {
+            crate::errors::Error::Neo4j(error) => error,
+            _ => panic!("Expected a Neo4j error"),
+        };
+
+        assert_eq!(
+            error.code(),
+            "Neo.TransientError.General.DatabaseUnavailable"
+        );
+        assert_eq!(error.kind(), crate::errors::Neo4jErrorKind::Transient);
+        assert_eq!(error.gql_status(), Some("08N00"));
+        assert_eq!(error.description(), Some("error: connection exception"));
+        assert!(error.cause().is_none());
+    }
 }
@@ -1,9 +1,16 @@
-use crate::auth::{ClientCertificate, ConnectionTLSConfig};
+use crate::auth::{
+    Auth, AuthToken, AuthTokenManager, ClientCertificate, ClientIdentity, ConnectionTLSConfig,
+    TlsValidation,
+};
+use crate::connection::Connector;
 use crate::errors::{Error, Result};
+use crate::resolver::{Resolver, ServerAddressResolver};
+use crate::types::BoltMap;
+use crate::version::Version;
 #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
-use serde::{Deserialize, Deserializer, Serialize};
+use serde::{ser::SerializeMap as _, Deserialize, Deserializer, Serialize};
 use std::path::Path;
-use std::{ops::Deref, sync::Arc};
+use std::{ops::Deref, sync::Arc, time::Duration};
 
 const DEFAULT_FETCH_SIZE: usize = 200;
 const DEFAULT_MAX_CONNECTIONS: usize = 16;
@@ -60,9 +67,251 @@ impl Deref for Database {
     }
 }
 
+/// The minimum severity of the notifications that the server sends, see [`NotificationFilter`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum NotificationMinimumSeverity {
+    Information,
+    Warning,
+    /// Disables all notifications.
+    Off,
+}
+
+impl NotificationMinimumSeverity {
+    fn as_str(self) -> &'static str {
+        match self {
+            NotificationMinimumSeverity::Information => "INFORMATION",
+            NotificationMinimumSeverity::Warning => "WARNING",
+            NotificationMinimumSeverity::Off => "OFF",
+        }
+    }
+}
+
+/// The category of notifications that can be disabled, see [`NotificationFilter`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum NotificationCategory {
+    Hint,
+    Unrecognized,
+    Unsupported,
+    Performance,
+    Deprecation,
+    Security,
+    Topology,
+    Generic,
+    Schema,
+}
+
+impl NotificationCategory {
+    fn as_str(self) -> &'static str {
+        match self {
+            NotificationCategory::Hint => "HINT",
+            NotificationCategory::Unrecognized => "UNRECOGNIZED",
+            NotificationCategory::Unsupported => "UNSUPPORTED",
+            NotificationCategory::Performance => "PERFORMANCE",
+            NotificationCategory::Deprecation => "DEPRECATION",
+            NotificationCategory::Security => "SECURITY",
+            NotificationCategory::Topology => "TOPOLOGY",
+            NotificationCategory::Generic => "GENERIC",
+            NotificationCategory::Schema => "SCHEMA",
+        }
+    }
+}
+
+/// Controls which notifications the server sends with the result summary.
+///
+/// The filter can be set for the driver in [`ConfigBuilder`] and overridden
+/// for a transaction with [`crate::TxnOptions::with_notification_filter`]
+/// or for a query with [`crate::Query::with_notification_filter`].
+/// Settings that are not set use the value of the enclosing level,
+/// ending with the server configuration.
+///
+/// Filtering requires Bolt 5.2 (Neo4j 5.7) or later, older servers ignore the filter.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NotificationFilter {
+    minimum_severity: Option<NotificationMinimumSeverity>,
+    disabled_categories: Option<Arc<[NotificationCategory]>>,
+}
+
+impl NotificationFilter {
+    /// Creates a filter that uses the settings of the enclosing level.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a filter that turns off all notifications.
+    pub fn disable_all() -> Self {
+        Self::new().with_minimum_severity(NotificationMinimumSeverity::Off)
+    }
+
+    /// Only send notifications with at least the given severity.
+    pub fn with_minimum_severity(mut self, severity: NotificationMinimumSeverity) -> Self {
+        self.minimum_severity = Some(severity);
+        self
+    }
+
+    /// Do not send notifications of the given categories.
+    ///
+    /// An empty list enables all categories, even if they are disabled on the enclosing level.
+    pub fn with_disabled_categories(
+        mut self,
+        categories: impl IntoIterator<Item = NotificationCategory>,
+    ) -> Self {
+        self.disabled_categories = Some(categories.into_iter().collect());
+        self
+    }
+
+    /// Adds the filter to the extra map of HELLO, BEGIN, or RUN.
+    pub(crate) fn write_into(&self, version: Version, extra: &mut BoltMap) {
+        if let Some(severity) = self.minimum_severity {
+            extra.put(
+                "notifications_minimum_severity".into(),
+                severity.as_str().into(),
+            );
+        }
+        if let Some(categories) = self.disabled_categories.as_deref() {
+            let categories = categories.iter().map(|c| c.as_str()).collect::<Vec<_>>();
+            extra.put(disabled_categories_key(version).into(), categories.into());
+        }
+    }
+
+    #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+    pub(crate) fn for_version(&self, version: Version) -> NotificationExtra<'_> {
+        NotificationExtra {
+            filter: self,
+            version,
+        }
+    }
+}
+
+/// Starting with 5.5, categories are called classifications, following GQL.
+fn disabled_categories_key(version: Version) -> &'static str {
+    if version >= Version::V5_5 {
+        "notifications_disabled_classifications"
+    } else {
+        "notifications_disabled_categories"
+    }
+}
+
+/// Serializes a [`NotificationFilter`] for the negotiated Bolt version.
+#[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct NotificationExtra<'a> {
+    filter: &'a NotificationFilter,
+    version: Version,
+}
+
+#[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+impl Serialize for NotificationExtra<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+        if let Some(severity) = self.filter.minimum_severity {
+            map.serialize_entry("notifications_minimum_severity", severity.as_str())?;
+        }
+        if let Some(categories) = self.filter.disabled_categories.as_deref() {
+            let categories = categories.iter().map(|c| c.as_str()).collect::<Vec<_>>();
+            map.serialize_entry(disabled_categories_key(self.version), &categories)?;
+        }
+        map.end()
+    }
+}
+
+/// The kind of proxy, see [`Proxy`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum ProxyKind {
+    Socks5,
+    HttpConnect,
+}
+
+/// A proxy through which all connections to the server are tunneled.
+///
+/// The proxy is used for the initial connection to the router as well as
+/// for the connections to the servers of the routing table.
+/// TLS is negotiated with the server inside of the tunnel.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Proxy {
+    pub(crate) kind: ProxyKind,
+    pub(crate) address: Arc<str>,
+    pub(crate) auth: Option<(Arc<str>, Arc<str>)>,
+}
+
+impl Proxy {
+    /// A SOCKS5 proxy at the given address, e.g. "proxy.example.com:1080".
+    ///
+    /// The host name of the server is resolved by the proxy.
+    pub fn socks5(address: impl Into<String>) -> Self {
+        Self::new(ProxyKind::Socks5, address.into())
+    }
+
+    /// An HTTP proxy at the given address, e.g. "proxy.example.com:3128",
+    /// that supports the `CONNECT` method.
+    pub fn http_connect(address: impl Into<String>) -> Self {
+        Self::new(ProxyKind::HttpConnect, address.into())
+    }
+
+    fn new(kind: ProxyKind, address: String) -> Self {
+        Self {
+            kind,
+            address: address.into(),
+            auth: None,
+        }
+    }
+
+    /// Authenticate with the proxy using a username and password.
+    ///
+    /// SOCKS5 proxies use the username/password method,
+    /// HTTP proxies use the `Basic` scheme in the `Proxy-Authorization` header.
+    pub fn with_auth(mut self, user: impl Into<String>, password: impl Into<String>) -> Self {
+        self.auth = Some((user.into().into(), password.into().into()));
+        self
+    }
+}
+
+impl std::fmt::Debug for Proxy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Proxy")
+            .field("kind", &self.kind)
+            .field("address", &self.address)
+            .field("user", &self.auth.as_ref().map(|(user, _)| user))
+            .finish_non_exhaustive()
+    }
+}
+
+/// The options for the sockets of new connections, see [`ConfigBuilder::connect_timeout`] and following.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct SocketOptions {
+    pub(crate) connect_timeout: Option<Duration>,
+    pub(crate) address_timeout: Option<Duration>,
+    pub(crate) tls_handshake_timeout: Option<Duration>,
+    pub(crate) keepalive: Option<Duration>,
+    pub(crate) nodelay: bool,
+}
+
+/// How new connections reach the server and which protocol versions they offer.
+#[derive(Clone, Default)]
+pub(crate) struct TransportConfig {
+    pub(crate) proxy: Option<Proxy>,
+    pub(crate) socket: SocketOptions,
+    pub(crate) connector: Option<Arc<dyn Connector>>,
+    pub(crate) min_version: Option<Version>,
+}
+
+impl std::fmt::Debug for TransportConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TransportConfig")
+            .field("proxy", &self.proxy)
+            .field("socket", &self.socket)
+            .field("connector", &self.connector.as_ref().map(|_| "custom"))
+            .field("min_version", &self.min_version)
+            .finish()
+    }
+}
+
 /// The configuration that is used once a connection is alive.
 #[derive(Debug, Clone)]
 pub struct LiveConfig {
+    pub(crate) auth: Auth,
     pub(crate) db: Option<Database>,
     pub(crate) fetch_size: usize,
 }
@@ -71,17 +320,21 @@ pub struct LiveConfig {
 #[derive(Debug, Clone)]
 pub struct Config {
     pub(crate) uri: String,
-    pub(crate) user: String,
-    pub(crate) password: String,
+    pub(crate) auth: Auth,
     pub(crate) max_connections: usize,
     pub(crate) db: Option<Database>,
     pub(crate) fetch_size: usize,
     pub(crate) tls_config: ConnectionTLSConfig,
+    pub(crate) notifications: NotificationFilter,
+    pub(crate) transport: TransportConfig,
+    #[cfg_attr(not(feature = "unstable-bolt-protocol-impl-v2"), allow(dead_code))]
+    pub(crate) resolver: Option<Resolver>,
 }
 
 impl Config {
     pub(crate) fn into_live_config(self) -> LiveConfig {
         LiveConfig {
+            auth: self.auth,
             db: self.db,
             fetch_size: self.fetch_size,
         }
@@ -93,10 +346,14 @@ pub struct ConfigBuilder {
     uri: Option<String>,
     user: Option<String>,
     password: Option<String>,
+    auth: Option<Auth>,
     db: Option<Database>,
     fetch_size: usize,
     max_connections: usize,
     tls_config: ConnectionTLSConfig,
+    notifications: NotificationFilter,
+    transport: TransportConfig,
+    resolver: Option<Resolver>,
 }
 
 impl ConfigBuilder {
@@ -106,6 +363,9 @@ impl ConfigBuilder {
     }
 
     /// The uri of the Neo4j server, e.g. "127.0.0.1:7687".
+    ///
+    /// On Unix, the server can also be reached through a Unix domain socket,
+    /// e.g. "bolt+unix:///var/run/neo4j/bolt.sock".
     pub fn uri(mut self, uri: impl Into<String>) -> Self {
         self.uri = Some(uri.into());
         self
@@ -123,6 +383,25 @@ impl ConfigBuilder {
         self
     }
 
+    /// The credentials for authenticating with the Neo4j server,
+    /// for schemes other than a username and password.
+    ///
+    /// Takes precedence over [`ConfigBuilder::user`] and [`ConfigBuilder::password`].
+    pub fn auth(mut self, auth: AuthToken) -> Self {
+        self.auth = Some(Auth::Token(auth));
+        self
+    }
+
+    /// Get the credentials from a manager, for tokens that expire and need to be rotated.
+    ///
+    /// Takes the place of [`ConfigBuilder::auth`], [`ConfigBuilder::user`] and [`ConfigBuilder::password`].
+    /// Queries that fail because the token expired are retried
+    /// if the manager returns a fresh token, see [`AuthTokenManager::handle_security_error`].
+    pub fn with_auth_token_manager(mut self, manager: impl AuthTokenManager + 'static) -> Self {
+        self.auth = Some(Auth::Manager(Arc::new(manager)));
+        self
+    }
+
     /// The name of the database to connect to.
     ///
     /// Defaults to the server configured default database if not set.
@@ -150,36 +429,167 @@ impl ConfigBuilder {
         self
     }
 
+    /// Only receive notifications with at least the given severity.
+    /// Use [`NotificationMinimumSeverity::Off`] to disable all notifications.
+    ///
+    /// Defaults to the server configuration if not set.
+    /// Requires Bolt 5.2 (Neo4j 5.7) or later, older servers ignore the setting.
+    pub fn notifications_minimum_severity(mut self, severity: NotificationMinimumSeverity) -> Self {
+        self.notifications = self.notifications.with_minimum_severity(severity);
+        self
+    }
+
+    /// Do not receive notifications of the given categories.
+    ///
+    /// Defaults to the server configuration if not set.
+    /// Requires Bolt 5.2 (Neo4j 5.7) or later, older servers ignore the setting.
+    pub fn notifications_disabled_categories(
+        mut self,
+        categories: impl IntoIterator<Item = NotificationCategory>,
+    ) -> Self {
+        self.notifications = self.notifications.with_disabled_categories(categories);
+        self
+    }
+
     /// A CA certificate to use to validate the server's certificate.
     ///
     /// This is required if the server's certificate is not signed by a known CA.
     pub fn with_client_certificate(mut self, client_cert: impl AsRef<Path>) -> Self {
-        self.tls_config =
-            ConnectionTLSConfig::ClientCACertificate(ClientCertificate::new(client_cert));
+        self.tls_config.validation =
+            TlsValidation::ClientCACertificate(ClientCertificate::new(client_cert));
+        self
+    }
+
+    /// A certificate chain and private key in PEM files that identify the client,
+    /// for servers that require mutual TLS.
+    ///
+    /// The key can be PKCS#8, PKCS#1 (RSA) or SEC1 (EC) encoded.
+    /// It is only used with the schemes that encrypt the connection, e.g. `neo4j+s`.
+    pub fn with_client_identity(
+        mut self,
+        cert_file: impl AsRef<Path>,
+        key_file: impl AsRef<Path>,
+    ) -> Self {
+        self.tls_config.client_identity = Some(ClientIdentity::new(cert_file, key_file));
         self
     }
 
     /// Skip SSL validation. This is not recommended for production use.
     /// This is true by default when connecting to the server using `neo4j+ssc` or 'bolt+ssc' schemes.
     pub fn skip_ssl_validation(mut self) -> Self {
-        self.tls_config = ConnectionTLSConfig::NoSSLValidation;
+        self.tls_config.validation = TlsValidation::NoSSLValidation;
+        self
+    }
+
+    /// Connect to the server through a SOCKS5 or HTTP `CONNECT` proxy.
+    ///
+    /// Defaults to connecting to the server directly if not set.
+    /// Connections through a Unix domain socket do not use the proxy.
+    pub fn with_proxy(mut self, proxy: Proxy) -> Self {
+        self.transport.proxy = Some(proxy);
+        self
+    }
+
+    /// The maximum time to establish the TCP connection to the server,
+    /// including the handshake with the proxy, if one is configured.
+    /// The Bolt handshake that follows is limited to the same time.
+    ///
+    /// Defaults to waiting as long as the operating system does if not set.
+    /// A connection attempt that takes longer fails with [`Error::ConnectTimeout`].
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.transport.socket.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// The maximum time to connect to a single address of the server.
+    ///
+    /// When the host name resolves to multiple addresses, IPv6 and IPv4 addresses are tried
+    /// in turns, starting a new attempt every 250ms until one of them succeeds.
+    /// If no address succeeds, the error of every address is reported in [`Error::ConnectFailed`].
+    ///
+    /// Defaults to waiting as long as the operating system does if not set.
+    pub fn connect_timeout_per_address(mut self, timeout: Duration) -> Self {
+        self.transport.socket.address_timeout = Some(timeout);
+        self
+    }
+
+    /// The maximum time for the TLS handshake with the server.
+    ///
+    /// Defaults to no timeout if not set.
+    /// A handshake that takes longer fails with [`Error::ConnectTimeout`].
+    pub fn tls_handshake_timeout(mut self, timeout: Duration) -> Self {
+        self.transport.socket.tls_handshake_timeout = Some(timeout);
+        self
+    }
+
+    /// Enables TCP keepalive, sending probes on idle connections with the given interval.
+    ///
+    /// Defaults to the operating system configuration if not set, which usually disables keepalive.
+    pub fn tcp_keepalive(mut self, interval: Duration) -> Self {
+        self.transport.socket.keepalive = Some(interval);
+        self
+    }
+
+    /// Whether to set `TCP_NODELAY`, which disables Nagle's algorithm.
+    ///
+    /// Defaults to `false` if not set.
+    pub fn tcp_nodelay(mut self, nodelay: bool) -> Self {
+        self.transport.socket.nodelay = nodelay;
+        self
+    }
+
+    /// The oldest Bolt version to offer when connecting.
+    ///
+    /// Defaults to [`Version::V4`] if not set.
+    /// Use [`Version::V3`] to connect to Neo4j 3.5, Bolt 3 then takes the place of the manifest handshake.
+    pub fn min_bolt_version(mut self, version: Version) -> Self {
+        self.transport.min_version = Some(version);
+        self
+    }
+
+    /// Open the streams to the server with a custom [`Connector`]
+    /// instead of the built-in TCP and TLS connections.
+    ///
+    /// The connector is used for the initial connection to the router as well as
+    /// for the connections to the servers of the routing table.
+    /// The connect timeout still applies, the proxy and the TCP options do not.
+    ///
+    /// TLS is still negotiated on top of the stream for the `+s` and `+ssc` schemes.
+    /// Use the `bolt` or `neo4j` scheme when the connector encrypts the stream itself.
+    pub fn with_connector(mut self, connector: impl Connector + 'static) -> Self {
+        self.transport.connector = Some(Arc::new(connector));
+        self
+    }
+
+    /// Resolve the address of a `neo4j://` URI to the routers that are asked for the routing table.
+    ///
+    /// Defaults to only using the address of the URI if not set.
+    /// The resolver is only used with client-side routing.
+    pub fn with_resolver(mut self, resolver: impl ServerAddressResolver + 'static) -> Self {
+        self.resolver = Some(Resolver(Arc::new(resolver)));
         self
     }
 
     pub fn build(self) -> Result<Config> {
-        if let (Some(uri), Some(user), Some(password)) = (self.uri, self.user, self.password) {
-            Ok(Config {
-                uri,
-                user,
-                password,
-                fetch_size: self.fetch_size,
-                max_connections: self.max_connections,
-                db: self.db,
-                tls_config: self.tls_config,
-            })
-        } else {
-            Err(Error::InvalidConfig)
-        }
+        let auth = match (self.auth, self.user, self.password) {
+            (Some(auth), _, _) => auth,
+            (None, Some(user), Some(password)) => AuthToken::basic(user, password).into(),
+            _ => return Err(Error::InvalidConfig),
+        };
+        let Some(uri) = self.uri else {
+            return Err(Error::InvalidConfig);
+        };
+        Ok(Config {
+            uri,
+            auth,
+            fetch_size: self.fetch_size,
+            max_connections: self.max_connections,
+            db: self.db,
+            tls_config: self.tls_config,
+            notifications: self.notifications,
+            transport: self.transport,
+            resolver: self.resolver,
+        })
     }
 }
 
@@ -189,10 +599,14 @@ impl Default for ConfigBuilder {
             uri: None,
             user: None,
             password: None,
+            auth: None,
             db: None,
             max_connections: DEFAULT_MAX_CONNECTIONS,
             fetch_size: DEFAULT_FETCH_SIZE,
-            tls_config: ConnectionTLSConfig::None,
+            tls_config: ConnectionTLSConfig::default(),
+            notifications: NotificationFilter::default(),
+            transport: TransportConfig::default(),
+            resolver: None,
         }
     }
 }
@@ -213,12 +627,14 @@ mod tests {
             .build()
             .unwrap();
         assert_eq!(config.uri, "127.0.0.1:7687");
-        assert_eq!(config.user, "some_user");
-        assert_eq!(config.password, "some_password");
+        assert_eq!(
+            config.auth,
+            AuthToken::basic("some_user", "some_password").into()
+        );
         assert_eq!(config.db.as_deref(), Some("some_db"));
         assert_eq!(config.fetch_size, 10);
         assert_eq!(config.max_connections, 5);
-        assert_eq!(config.tls_config, ConnectionTLSConfig::None);
+        assert_eq!(config.tls_config, ConnectionTLSConfig::default());
     }
 
     #[test]
@@ -230,12 +646,56 @@ mod tests {
             .build()
             .unwrap();
         assert_eq!(config.uri, "127.0.0.1:7687");
-        assert_eq!(config.user, "some_user");
-        assert_eq!(config.password, "some_password");
+        assert_eq!(
+            config.auth,
+            AuthToken::basic("some_user", "some_password").into()
+        );
         assert_eq!(config.db, None);
         assert_eq!(config.fetch_size, 200);
         assert_eq!(config.max_connections, 16);
-        assert_eq!(config.tls_config, ConnectionTLSConfig::None);
+        assert_eq!(config.tls_config, ConnectionTLSConfig::default());
+        assert_eq!(config.notifications, NotificationFilter::default());
+        assert_eq!(config.transport.socket.connect_timeout, None);
+        assert!(!config.transport.socket.nodelay);
+    }
+
+    #[test]
+    fn should_build_with_notification_filter() {
+        let config = ConfigBuilder::default()
+            .uri("127.0.0.1:7687")
+            .user("some_user")
+            .password("some_password")
+            .notifications_minimum_severity(NotificationMinimumSeverity::Warning)
+            .notifications_disabled_categories([
+                NotificationCategory::Hint,
+                NotificationCategory::Deprecation,
+            ])
+            .build()
+            .unwrap();
+
+        let mut extra = BoltMap::default();
+        config.notifications.write_into(Version::V5_2, &mut extra);
+        assert_eq!(
+            extra
+                .get::<String>("notifications_minimum_severity")
+                .unwrap(),
+            "WARNING"
+        );
+        assert_eq!(
+            extra
+                .get::<Vec<String>>("notifications_disabled_categories")
+                .unwrap(),
+            ["HINT", "DEPRECATION"]
+        );
+
+        let mut extra = BoltMap::default();
+        config.notifications.write_into(Version::V5_5, &mut extra);
+        assert_eq!(
+            extra
+                .get::<Vec<String>>("notifications_disabled_classifications")
+                .unwrap(),
+            ["HINT", "DEPRECATION"]
+        );
     }
 
     #[test]
@@ -248,12 +708,93 @@ mod tests {
             .build()
             .unwrap();
         assert_eq!(config.uri, "127.0.0.1:7687");
-        assert_eq!(config.user, "some_user");
-        assert_eq!(config.password, "some_password");
+        assert_eq!(
+            config.auth,
+            AuthToken::basic("some_user", "some_password").into()
+        );
         assert_eq!(config.db, None);
         assert_eq!(config.fetch_size, 200);
         assert_eq!(config.max_connections, 16);
-        assert_eq!(config.tls_config, ConnectionTLSConfig::NoSSLValidation);
+        assert_eq!(config.tls_config.validation, TlsValidation::NoSSLValidation);
+    }
+
+    #[test]
+    fn should_build_with_client_identity() {
+        let config = ConfigBuilder::default()
+            .uri("neo4j+s://localhost:7687")
+            .user("some_user")
+            .password("some_password")
+            .with_client_certificate("ca.pem")
+            .with_client_identity("client.pem", "client.key")
+            .build()
+            .unwrap();
+        assert_eq!(
+            config.tls_config,
+            ConnectionTLSConfig {
+                validation: TlsValidation::ClientCACertificate(ClientCertificate::new("ca.pem")),
+                client_identity: Some(ClientIdentity::new("client.pem", "client.key")),
+            }
+        );
+    }
+
+    #[test]
+    fn should_build_with_socket_options() {
+        let config = ConfigBuilder::default()
+            .uri("127.0.0.1:7687")
+            .user("some_user")
+            .password("some_password")
+            .connect_timeout(Duration::from_secs(5))
+            .connect_timeout_per_address(Duration::from_secs(2))
+            .tls_handshake_timeout(Duration::from_secs(10))
+            .tcp_keepalive(Duration::from_secs(60))
+            .tcp_nodelay(true)
+            .build()
+            .unwrap();
+        assert_eq!(
+            config.transport.socket,
+            SocketOptions {
+                connect_timeout: Some(Duration::from_secs(5)),
+                address_timeout: Some(Duration::from_secs(2)),
+                tls_handshake_timeout: Some(Duration::from_secs(10)),
+                keepalive: Some(Duration::from_secs(60)),
+                nodelay: true,
+            }
+        );
+    }
+
+    #[test]
+    fn should_build_with_proxy() {
+        let config = ConfigBuilder::default()
+            .uri("neo4j+s://example.databases.neo4j.io")
+            .user("some_user")
+            .password("some_password")
+            .with_proxy(Proxy::socks5("proxy:1080").with_auth("proxy_user", "proxy_password"))
+            .build()
+            .unwrap();
+
+        let proxy = config.transport.proxy.unwrap();
+        assert_eq!(proxy.kind, ProxyKind::Socks5);
+        assert_eq!(&*proxy.address, "proxy:1080");
+        assert!(!format!("{proxy:?}").contains("proxy_password"));
+    }
+
+    #[test]
+    fn should_build_with_auth_token() {
+        let config = ConfigBuilder::default()
+            .uri("127.0.0.1:7687")
+            .user("some_user")
+            .password("some_password")
+            .auth(AuthToken::bearer("some_token"))
+            .build()
+            .unwrap();
+        assert_eq!(config.auth, AuthToken::bearer("some_token").into());
+
+        let config = ConfigBuilder::default()
+            .uri("127.0.0.1:7687")
+            .auth(AuthToken::none())
+            .build()
+            .unwrap();
+        assert_eq!(config.auth, AuthToken::none().into());
     }
 
     #[test]
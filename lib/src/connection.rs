@@ -1,10 +1,12 @@
-use crate::auth::ConnectionTLSConfig;
+use crate::auth::{Auth, AuthToken, ConnectionTLSConfig, TlsValidation};
+use crate::config::{NotificationFilter, SocketOptions, TransportConfig};
 #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
 use crate::messages::HelloBuilder;
 #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
 use {
     crate::bolt::{
-        ExpectedResponse, Hello, HelloBuilder, Message, MessageResponse, Reset, Summary,
+        ExpectedResponse, Goodbye, Hello, HelloBuilder, Logoff, Logon, Message, MessageResponse,
+        Reset, Summary, Telemetry,
     },
     log::debug,
 };
@@ -13,10 +15,10 @@ use {
 use crate::routing::{Route, RoutingTable};
 use crate::{
     connection::stream::ConnectionStream,
-    errors::{Error, Result},
-    messages::{BoltRequest, BoltResponse},
-    version::Version,
-    BoltMap, BoltString, BoltType,
+    errors::{ConnectStep, Error, Result},
+    messages::{BoltRequest, BoltResponse, TelemetryApi},
+    version::{Encoding, Version, VersionRange, CAPABILITIES, DEFAULT_MIN_VERSION, MANIFEST_V1},
+    BoltMap, BoltString, BoltType, ServerAddress,
 };
 use bytes::{BufMut, Bytes, BytesMut};
 use log::{info, warn};
@@ -24,13 +26,17 @@ use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, Server
 use rustls::crypto::CryptoProvider;
 use rustls::pki_types::{CertificateDer, UnixTime};
 use rustls::{DigitallySignedStruct, SignatureScheme};
+use serde::Deserialize;
 use std::fmt::{Debug, Display, Formatter};
-use std::{fs::File, io::BufReader, mem, sync::Arc};
+#[cfg(unix)]
+use std::path::{Path, PathBuf};
+use std::{fs::File, future::Future, io::BufReader, mem, sync::Arc, time::Duration};
 use tokio::{
     io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufStream},
     net::TcpStream,
 };
 use tokio_rustls::{
+    client::TlsStream,
     rustls::{
         pki_types::{IpAddr, Ipv4Addr, Ipv6Addr, ServerName},
         ClientConfig, RootCertStore,
@@ -41,17 +47,93 @@ use url::{Host, Url};
 
 const MAX_CHUNK_SIZE: usize = 65_535 - mem::size_of::<u16>();
 
+/// RECORD is the only response that is not a summary of a request.
+const RECORD_SIGNATURE: u8 = 0x71;
+
+/// Reads an unsigned LEB128 encoded integer, as used by the manifest handshake.
+async fn read_varint<A: AsyncRead + Unpin>(stream: &mut A) -> Result<u64> {
+    let mut value = 0_u64;
+    for shift in (0..64).step_by(7) {
+        let byte = stream.read_u8().await?;
+        value |= u64::from(byte & 0x7F) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(Error::UnexpectedMessage(
+        "Invalid variable length integer in the handshake manifest".into(),
+    ))
+}
+
+/// Fails with [`Error::RecvTimeout`] if the server does not send anything within the `timeout`.
+async fn with_recv_timeout<T>(
+    timeout: Option<Duration>,
+    read: impl Future<Output = std::io::Result<T>>,
+) -> Result<T> {
+    let Some(timeout) = timeout else {
+        return Ok(read.await?);
+    };
+    match tokio::time::timeout(timeout, read).await {
+        Ok(result) => Ok(result?),
+        Err(_) => Err(Error::RecvTimeout(timeout)),
+    }
+}
+
+fn put_varint(bytes: &mut BytesMut, mut value: u64) {
+    while value >= 0x80 {
+        bytes.put_u8((value as u8 & 0x7F) | 0x80);
+        value >>= 7;
+    }
+    bytes.put_u8(value as u8);
+}
+
+/// The connection hints sent by the server in the HELLO success metadata.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Deserialize)]
+pub(crate) struct Hints {
+    #[serde(rename = "telemetry.enabled", default)]
+    pub(crate) telemetry_enabled: bool,
+    #[serde(rename = "connection.recv_timeout_seconds", default)]
+    pub(crate) recv_timeout_seconds: Option<i64>,
+}
+
+impl Hints {
+    fn recv_timeout(self) -> Option<Duration> {
+        self.recv_timeout_seconds
+            .filter(|seconds| *seconds > 0)
+            .map(|seconds| Duration::from_secs(seconds as u64))
+    }
+}
+
 #[derive(Debug)]
 pub struct Connection {
     version: Version,
+    /// Whether the server accepted the `utc` patch in HELLO, see [`Encoding`].
+    utc_patch: bool,
     stream: BufStream<ConnectionStream>,
+    telemetry: bool,
+    /// The APIs that were already reported with TELEMETRY, one bit each.
+    telemetry_sent: u8,
+    /// Whether the response to a TELEMETRY is the next one to read.
+    telemetry_unread: bool,
+    recv_timeout: Option<Duration>,
+    broken: bool,
+    /// Requests that were sent, but whose summary has not been read yet.
+    pending: usize,
+    /// The token that the connection authenticated with.
+    auth: Option<AuthToken>,
 }
 
 impl Connection {
     pub(crate) async fn new(info: &ConnectionInfo) -> Result<Self> {
         let mut connection = Self::prepare(info).await?;
-        let hello = info.to_hello(connection.version);
+        let token = info.auth.token().await?;
+        let hello = info.to_hello(connection.version, &token);
         connection.hello(hello).await?;
+        if connection.version >= Version::V5_1 {
+            let logon = info.to_logon(&token);
+            connection.logon(logon).await?;
+        }
+        connection.auth = Some(token);
         Ok(connection)
     }
 
@@ -59,55 +141,230 @@ impl Connection {
         self.version
     }
 
+    pub(crate) fn encoding(&self) -> Encoding {
+        Encoding::new(self.version, self.utc_patch)
+    }
+
+    pub(crate) fn auth_token(&self) -> Option<&AuthToken> {
+        self.auth.as_ref()
+    }
+
     pub(crate) async fn prepare(info: &ConnectionInfo) -> Result<Self> {
-        let mut stream = match &info.host {
-            Host::Domain(domain) => TcpStream::connect((&**domain, info.port)).await?,
-            Host::Ipv4(ip) => TcpStream::connect((*ip, info.port)).await?,
-            Host::Ipv6(ip) => TcpStream::connect((*ip, info.port)).await?,
+        let (host, port) = match &info.address {
+            Address::Tcp { host, port } => (host, *port),
+            #[cfg(unix)]
+            Address::Unix(path) => {
+                let address = path.display().to_string();
+                let timeout = info.transport.socket.connect_timeout;
+                let connect = tokio::net::UnixStream::connect(path);
+                let mut stream =
+                    Self::connect_step(ConnectStep::Connect, &address, timeout, connect).await?;
+                let version = Self::handshake(info, &address, &mut stream).await?;
+                return Ok(Self::create(stream, version));
+            }
         };
-
-        Ok(match &info.encryption {
-            Some((connector, domain)) => {
-                let mut stream = connector.connect(domain.clone(), stream).await?;
-                let version = Self::init(&mut stream).await?;
-                Self::create(stream, version)
+        let TransportConfig {
+            proxy,
+            socket,
+            connector,
+            ..
+        } = &info.transport;
+
+        let address = ServerAddress::new(host.to_string(), port).to_string();
+
+        let mut stream = match connector {
+            Some(connector) => {
+                let host = match host {
+                    Host::Ipv6(ip) => ip.to_string(),
+                    host => host.to_string(),
+                };
+                let connect = connector.connect(&host, port);
+                let stream = Self::connect_step(
+                    ConnectStep::Connect,
+                    &address,
+                    socket.connect_timeout,
+                    connect,
+                )
+                .await?;
+                Self::encrypt(info, &address, stream).await?
             }
             None => {
-                let version = Self::init(&mut stream).await?;
-                Self::create(stream, version)
+                let connect = async {
+                    match (proxy, host) {
+                        (Some(proxy), host) => proxy::connect(proxy, host, port).await,
+                        (None, host) => {
+                            let addresses = match host {
+                                Host::Domain(domain) => {
+                                    tokio::net::lookup_host((&**domain, port)).await?.collect()
+                                }
+                                Host::Ipv4(ip) => vec![(*ip, port).into()],
+                                Host::Ipv6(ip) => vec![(*ip, port).into()],
+                            };
+                            happy_eyeballs::connect(addresses, socket.address_timeout).await
+                        }
+                    }
+                };
+                let step = match proxy {
+                    Some(_) => ConnectStep::Proxy,
+                    None => ConnectStep::Connect,
+                };
+                let stream =
+                    Self::connect_step(step, &address, socket.connect_timeout, connect).await?;
+                socket.apply(&stream)?;
+                Self::encrypt(info, &address, stream).await?
             }
-        })
+        };
+
+        #[cfg(feature = "websocket")]
+        if info.websocket {
+            let scheme = if info.encryption.is_some() {
+                "wss"
+            } else {
+                "ws"
+            };
+            let uri = format!("{scheme}://{host}:{port}");
+            stream = websocket::WebSocket::connect(stream, uri).await?.into();
+        }
+
+        let version = Self::handshake(info, &address, &mut stream).await?;
+        Ok(Self::create(stream, version))
+    }
+
+    /// Agrees on the protocol version, within the [`crate::ConfigBuilder::connect_timeout`].
+    async fn handshake<A: AsyncWrite + AsyncRead + Unpin>(
+        info: &ConnectionInfo,
+        address: &str,
+        stream: &mut A,
+    ) -> Result<Version> {
+        let timeout = info.transport.socket.connect_timeout;
+        let init = Self::init(stream, info.min_version());
+        Self::connect_step(ConnectStep::BoltHandshake, address, timeout, init).await
+    }
+
+    /// Runs the TLS handshake over `stream` if the connection is encrypted.
+    async fn encrypt<S>(info: &ConnectionInfo, address: &str, stream: S) -> Result<ConnectionStream>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+        ConnectionStream: From<S> + From<TlsStream<S>>,
+    {
+        match &info.encryption {
+            Some((tls, domain)) => {
+                let handshake = tls.connect(domain.clone(), stream);
+                let timeout = info.transport.socket.tls_handshake_timeout;
+                Ok(
+                    Self::connect_step(ConnectStep::TlsHandshake, address, timeout, handshake)
+                        .await?
+                        .into(),
+                )
+            }
+            None => Ok(stream.into()),
+        }
+    }
+
+    /// Runs one step of establishing the connection to `address`,
+    /// failing with [`Error::ConnectTimeout`] after `timeout`, if one is set.
+    async fn connect_step<T, E>(
+        step: ConnectStep,
+        address: &str,
+        timeout: Option<Duration>,
+        future: impl Future<Output = Result<T, E>>,
+    ) -> Result<T>
+    where
+        Error: From<E>,
+    {
+        let Some(timeout) = timeout else {
+            return Ok(future.await?);
+        };
+        match tokio::time::timeout(timeout, future).await {
+            Ok(result) => Ok(result?),
+            Err(_) => Err(Error::ConnectTimeout(step, address.to_owned(), timeout)),
+        }
     }
 
-    async fn init<A: AsyncWrite + AsyncRead + Unpin>(stream: &mut A) -> Result<Version> {
-        stream.write_all_buf(&mut Self::init_msg()).await?;
+    async fn init<A: AsyncWrite + AsyncRead + Unpin>(
+        stream: &mut A,
+        min_version: Version,
+    ) -> Result<Version> {
+        stream
+            .write_all_buf(&mut Self::init_msg(min_version))
+            .await?;
         stream.flush().await?;
 
         let mut response = [0, 0, 0, 0];
         stream.read_exact(&mut response).await?;
-        let version = Version::parse(response)?;
+        let version = if u32::from_be_bytes(response) == MANIFEST_V1 {
+            Self::negotiate(stream, min_version).await?
+        } else {
+            Version::parse(response)?
+        };
         info!("Connected to Neo4j with version {}", version);
         Ok(version)
     }
 
+    /// Second half of the manifest handshake: the server lists all the version ranges
+    /// and capabilities it supports and the client replies with the ones it picked.
+    async fn negotiate<A: AsyncWrite + AsyncRead + Unpin>(
+        stream: &mut A,
+        min_version: Version,
+    ) -> Result<Version> {
+        let count = read_varint(stream).await?;
+        let mut ranges = Vec::with_capacity(count.min(16) as usize);
+        for _ in 0..count {
+            let mut range = [0, 0, 0, 0];
+            stream.read_exact(&mut range).await?;
+            ranges.push(VersionRange::from_bytes(range));
+        }
+        let capabilities = read_varint(stream).await?;
+
+        let version = Version::negotiate(&ranges, min_version);
+        let mut reply = BytesMut::with_capacity(14);
+        match version {
+            Ok(version) => {
+                reply.put_slice(&version.to_bytes());
+                put_varint(&mut reply, capabilities & CAPABILITIES);
+            }
+            Err(_) => {
+                reply.put_u32(0);
+                put_varint(&mut reply, 0);
+            }
+        }
+        stream.write_all_buf(&mut reply).await?;
+        stream.flush().await?;
+
+        version
+    }
+
     fn create(stream: impl Into<ConnectionStream>, version: Version) -> Connection {
         Connection {
             version,
+            utc_patch: false,
             stream: BufStream::new(stream.into()),
+            telemetry: false,
+            telemetry_sent: 0,
+            telemetry_unread: false,
+            recv_timeout: None,
+            broken: false,
+            pending: 0,
+            auth: None,
         }
     }
 
-    fn init_msg() -> Bytes {
+    fn init_msg(min_version: Version) -> Bytes {
         let mut init = BytesMut::with_capacity(20);
         init.put_slice(&[0x60, 0x60, 0xB0, 0x17]);
-        Version::add_supported_versions(&mut init);
+        Version::add_supported_versions(&mut init, min_version);
         init.freeze()
     }
 
     #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
     async fn hello(&mut self, req: BoltRequest) -> Result<()> {
         match self.send_recv(req).await? {
-            BoltResponse::Success(_msg) => Ok(()),
+            BoltResponse::Success(msg) => {
+                let patches = msg.get::<Vec<String>>("patch_bolt").unwrap_or_default();
+                self.utc_patch = patches.iter().any(|p| p == "utc");
+                self.apply_hints(msg.get::<Hints>("hints").unwrap_or_default());
+                Ok(())
+            }
             BoltResponse::Failure(msg) => {
                 Err(Error::AuthenticationError(msg.get("message").unwrap()))
             }
@@ -120,12 +377,75 @@ impl Connection {
         let hello = self.send_recv_as(hello).await?;
 
         match hello {
+            Summary::Success(msg) => {
+                self.utc_patch = msg.metadata.patch_bolt.iter().any(|p| p == "utc");
+                self.apply_hints(msg.metadata.hints);
+                Ok(())
+            }
+            Summary::Ignored => Err(Error::RequestIgnoredError),
+            Summary::Failure(msg) => Err(Error::AuthenticationError(msg.message)),
+        }
+    }
+
+    #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
+    async fn logon(&mut self, req: BoltRequest) -> Result<()> {
+        match self.send_recv(req).await? {
+            BoltResponse::Success(_msg) => Ok(()),
+            BoltResponse::Failure(msg) => {
+                Err(Error::AuthenticationError(msg.get("message").unwrap()))
+            }
+            msg => Err(msg.into_error("LOGON")),
+        }
+    }
+
+    #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+    async fn logon(&mut self, logon: Logon<'_>) -> Result<()> {
+        let logon = self.send_recv_as(logon).await?;
+
+        match logon {
             Summary::Success(_msg) => Ok(()),
             Summary::Ignored => Err(Error::RequestIgnoredError),
             Summary::Failure(msg) => Err(Error::AuthenticationError(msg.message)),
         }
     }
 
+    /// Whether the connection can switch to another token with LOGOFF and LOGON,
+    /// which were introduced with Bolt 5.1.
+    pub(crate) fn supports_reauth(&self) -> bool {
+        self.version >= Version::V5_1
+    }
+
+    /// Authenticates the connection again with `token`, without opening a new socket.
+    pub(crate) async fn reauth(&mut self, token: &AuthToken) -> Result<()> {
+        if !self.supports_reauth() {
+            return Err(Error::UnsupportedFeature("Re-authentication", self.version));
+        }
+        // the connection is unauthenticated after the LOGOFF, and must not be reused if LOGON fails
+        self.auth = None;
+
+        #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
+        {
+            match self.send_recv(BoltRequest::logoff()).await? {
+                BoltResponse::Success(_) => {}
+                msg => return Err(msg.into_error("LOGOFF")),
+            }
+            self.logon(BoltRequest::logon(token)).await?;
+        }
+
+        #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+        {
+            match self.send_recv_as(Logoff).await? {
+                Summary::Success(_) => {}
+                Summary::Ignored => return Err(Error::RequestIgnoredError),
+                Summary::Failure(err) => return Err(err.into_error()),
+            }
+            self.logon(Logon::new(token)).await?;
+        }
+
+        self.auth = Some(token.clone());
+        Ok(())
+    }
+
     #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
     pub async fn route(&mut self, route: Route<'_>) -> Result<RoutingTable> {
         debug!("Routing request: {}", route);
@@ -138,10 +458,64 @@ impl Connection {
         }
     }
 
+    fn apply_hints(&mut self, hints: Hints) {
+        self.telemetry = self.version >= Version::V5_4 && hints.telemetry_enabled;
+        self.recv_timeout = hints.recv_timeout();
+    }
+
+    /// Whether the connection is in an unknown state and must not be reused,
+    /// e.g. because the server did not respond in time.
+    pub(crate) fn is_broken(&self) -> bool {
+        self.broken
+    }
+
+    /// Reports the driver API that is used for the next query,
+    /// if the server asked for telemetry in the HELLO hints.
+    ///
+    /// Every API is only reported once per connection.
+    /// The message is sent along with the next request and its response is read before that one's.
+    pub(crate) async fn write_telemetry(&mut self, api: TelemetryApi) -> Result<()> {
+        let bit = 1 << api as u8;
+        if !self.telemetry || self.telemetry_sent & bit != 0 {
+            return Ok(());
+        }
+
+        #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
+        self.write(BoltRequest::telemetry(api)).await?;
+
+        #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+        self.write_as(Telemetry::new(api)).await?;
+
+        self.telemetry_sent |= bit;
+        self.telemetry_unread = true;
+        Ok(())
+    }
+
+    fn check_telemetry(&self, response: Bytes) -> Result<()> {
+        #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
+        {
+            match BoltResponse::parse(self.version, response)? {
+                BoltResponse::Success(_) => Ok(()),
+                msg => Err(msg.into_error("TELEMETRY")),
+            }
+        }
+
+        #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+        {
+            match <Telemetry as ExpectedResponse>::Response::parse(response)? {
+                Summary::Success(_) => Ok(()),
+                Summary::Ignored => Err(Error::RequestIgnoredError),
+                Summary::Failure(err) => Err(err.into_error()),
+            }
+        }
+    }
+
     pub async fn reset(&mut self) -> Result<()> {
         #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
         {
-            match self.send_recv(BoltRequest::reset()).await? {
+            self.send(BoltRequest::reset()).await?;
+            let response = self.recv_last_summary().await?;
+            match BoltResponse::parse(self.version, response)? {
                 BoltResponse::Success(_) => Ok(()),
                 BoltResponse::Failure(f) => Err(Error::Neo4j(f.into_error())),
                 msg => Err(msg.into_error("RESET")),
@@ -150,7 +524,9 @@ impl Connection {
 
         #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
         {
-            match self.send_recv_as(Reset).await? {
+            self.send_as(Reset).await?;
+            let response = self.recv_last_summary().await?;
+            match <Reset as ExpectedResponse>::Response::parse(response)? {
                 Summary::Success(_) => Ok(()),
                 Summary::Failure(err) => Err(Error::ConnectionClosed(err)),
                 msg => Err(Error::UnexpectedMessage(format!(
@@ -161,6 +537,39 @@ impl Connection {
         }
     }
 
+    /// Tells the server that the connection is about to be closed.
+    /// The server does not respond to GOODBYE, so nothing is read afterwards.
+    pub(crate) async fn goodbye(&mut self) -> Result<()> {
+        #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
+        let bytes = BoltRequest::goodbye().into_bytes(self.encoding())?;
+        #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+        let bytes = Goodbye.to_bytes()?;
+        self.write_bytes(bytes).await?;
+        self.flush().await?;
+        self.broken = true;
+        Ok(())
+    }
+
+    /// Skips the responses of requests that were pipelined before,
+    /// e.g. the PULL of a stream that was dropped, and returns the summary of the last request.
+    async fn recv_last_summary(&mut self) -> Result<Bytes> {
+        loop {
+            let response = self.recv_bytes().await?;
+            if self.pending == 0 {
+                return Ok(response);
+            }
+        }
+    }
+
+    /// Reads the responses to all requests that were sent before,
+    /// e.g. the ones that were pipelined after a request that failed.
+    pub(crate) async fn skip_pending(&mut self) -> Result<()> {
+        while self.pending > 0 {
+            self.recv_bytes().await?;
+        }
+        Ok(())
+    }
+
     pub async fn send_recv(&mut self, message: BoltRequest) -> Result<BoltResponse> {
         self.send(message).await?;
         self.recv().await
@@ -177,15 +586,37 @@ impl Connection {
     }
 
     pub async fn send(&mut self, message: BoltRequest) -> Result<()> {
-        let bytes: Bytes = message.into_bytes(self.version)?;
-        self.send_bytes(bytes).await
+        self.write(message).await?;
+        self.flush().await
     }
 
     #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
     #[allow(unused)]
     pub(crate) async fn send_as<T: Message>(&mut self, message: T) -> Result<()> {
+        self.write_as(message).await?;
+        self.flush().await
+    }
+
+    /// Buffers the message without flushing it,
+    /// so that it is sent together with the messages that follow.
+    pub(crate) async fn write(&mut self, message: BoltRequest) -> Result<()> {
+        let bytes: Bytes = message.into_bytes(self.encoding())?;
+        self.write_bytes(bytes).await?;
+        self.pending += 1;
+        Ok(())
+    }
+
+    #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+    pub(crate) async fn write_as<T: Message>(&mut self, message: T) -> Result<()> {
         let bytes = message.to_bytes()?;
-        self.send_bytes(bytes).await
+        self.write_bytes(bytes).await?;
+        self.pending += 1;
+        Ok(())
+    }
+
+    pub(crate) async fn flush(&mut self) -> Result<()> {
+        self.stream.flush().await?;
+        Ok(())
     }
 
     pub async fn recv(&mut self) -> Result<BoltResponse> {
@@ -200,7 +631,7 @@ impl Connection {
         Ok(T::parse(bytes)?)
     }
 
-    async fn send_bytes(&mut self, bytes: Bytes) -> Result<()> {
+    async fn write_bytes(&mut self, bytes: Bytes) -> Result<()> {
         Self::dbg("send", &bytes);
         let end_marker: [u8; 2] = [0, 0];
         for c in bytes.chunks(MAX_CHUNK_SIZE) {
@@ -208,13 +639,31 @@ impl Connection {
             self.stream.write_all(c).await?;
         }
         self.stream.write_all(&end_marker).await?;
-        self.stream.flush().await?;
         Ok(())
     }
 
     async fn recv_bytes(&mut self) -> Result<Bytes> {
+        if mem::take(&mut self.telemetry_unread) {
+            let response = self.read_response().await?;
+            self.check_telemetry(response)?;
+        }
+        self.read_response().await
+    }
+
+    async fn read_response(&mut self) -> Result<Bytes> {
+        let result = self.read_message().await;
+        if result.is_err() {
+            // the rest of a message that was read in part, or a late response,
+            // would be read as the answer to the next request
+            self.broken = true;
+        }
+        result
+    }
+
+    async fn read_message(&mut self) -> Result<Bytes> {
         let mut bytes = BytesMut::new();
         let mut chunk_size = 0;
+        // every NOOP chunk, sent by the server to keep the connection alive, restarts the timeout
         while chunk_size == 0 {
             chunk_size = self.read_chunk_size().await?;
         }
@@ -226,11 +675,15 @@ impl Connection {
 
         let bytes = bytes.freeze();
         Self::dbg("recv", &bytes);
+        if bytes.get(1) != Some(&RECORD_SIGNATURE) {
+            self.pending = self.pending.saturating_sub(1);
+        }
         Ok(bytes)
     }
 
     async fn read_chunk_size(&mut self) -> Result<usize> {
-        Ok(usize::from(self.stream.read_u16().await?))
+        let size = with_recv_timeout(self.recv_timeout, self.stream.read_u16()).await?;
+        Ok(usize::from(size))
     }
 
     async fn read_chunk(&mut self, chunk_size: usize, buf: &mut BytesMut) -> Result<()> {
@@ -238,14 +691,15 @@ impl Connection {
         if buf.capacity() < (buf.len() + chunk_size) {
             buf.reserve(chunk_size);
         }
-        let mut remaining = chunk_size;
-        while remaining > 0 {
-            remaining -= (&mut self.stream)
-                .take(remaining as u64)
-                .read_buf(buf)
-                .await?;
-        }
-        Ok(())
+        let stream = &mut self.stream;
+        let read = async move {
+            let mut remaining = chunk_size;
+            while remaining > 0 {
+                remaining -= stream.take(remaining as u64).read_buf(buf).await?;
+            }
+            Ok(())
+        };
+        with_recv_timeout(self.recv_timeout, read).await
     }
 
     #[cfg(not(all(feature = "unstable-serde-packstream-format", test, debug_assertions)))]
@@ -293,24 +747,63 @@ impl Display for Routing {
     }
 }
 
+impl SocketOptions {
+    fn apply(&self, stream: &TcpStream) -> Result<()> {
+        stream.set_nodelay(self.nodelay)?;
+        if let Some(interval) = self.keepalive {
+            let keepalive = socket2::TcpKeepalive::new().with_time(interval);
+            #[cfg(any(
+                target_os = "android",
+                target_os = "freebsd",
+                target_os = "ios",
+                target_os = "linux",
+                target_os = "macos",
+                target_os = "netbsd",
+                target_os = "windows",
+            ))]
+            let keepalive = keepalive.with_interval(interval);
+            socket2::SockRef::from(stream).set_tcp_keepalive(&keepalive)?;
+        }
+        Ok(())
+    }
+}
+
+/// The URI scheme to connect through a Unix domain socket, e.g. `bolt+unix:///var/run/neo4j.sock`.
+const UNIX_SCHEME: &str = "bolt+unix";
+
+/// Where the server is reached.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Address {
+    Tcp {
+        host: Host<Arc<str>>,
+        port: u16,
+    },
+    #[cfg(unix)]
+    Unix(Arc<Path>),
+}
+
+#[derive(Clone)]
 pub(crate) struct ConnectionInfo {
-    pub user: Arc<str>,
-    pub password: Arc<str>,
-    pub host: Host<Arc<str>>,
-    pub port: u16,
+    pub auth: Auth,
+    pub address: Address,
+    /// Whether Bolt is carried over WebSocket, with the `bolt+ws` and similar schemes.
+    #[cfg(feature = "websocket")]
+    pub websocket: bool,
     pub routing: Routing,
     pub encryption: Option<(TlsConnector, ServerName<'static>)>,
+    pub transport: TransportConfig,
+    pub notifications: NotificationFilter,
 }
 
 impl Debug for ConnectionInfo {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ConnectionInfo")
-            .field("user", &self.user)
-            .field("password", &"***")
-            .field("host", &self.host)
-            .field("port", &self.port)
+            .field("auth", &self.auth)
+            .field("address", &self.address)
             .field("routing", &self.routing)
             .field("encryption", &self.encryption.is_some())
+            .field("transport", &self.transport)
+            .field("notifications", &self.notifications)
             .finish_non_exhaustive()
     }
 }
@@ -318,9 +811,10 @@ impl Debug for ConnectionInfo {
 impl ConnectionInfo {
     pub(crate) fn new(
         uri: &str,
-        user: &str,
-        password: &str,
+        auth: &Auth,
         tls_config: &ConnectionTLSConfig,
+        notifications: &NotificationFilter,
+        transport: &TransportConfig,
     ) -> Result<Self> {
         let mut url = NeoUrl::parse(uri)?;
 
@@ -331,14 +825,29 @@ impl ConnectionInfo {
             "neo4j" => (true, false, false),
             "neo4j+s" => (true, true, true),
             "neo4j+ssc" => (true, true, false),
+            #[cfg(unix)]
+            UNIX_SCHEME => (false, false, false),
+            #[cfg(feature = "websocket")]
+            "bolt+ws" => (false, false, false),
+            #[cfg(feature = "websocket")]
+            "bolt+wss" => (false, true, true),
+            #[cfg(feature = "websocket")]
+            "neo4j+ws" => (true, false, false),
+            #[cfg(feature = "websocket")]
+            "neo4j+wss" => (true, true, true),
             otherwise => return Err(Error::UnsupportedScheme(otherwise.to_owned())),
         };
+        #[cfg(feature = "websocket")]
+        let websocket = url.scheme().ends_with("+ws") || url.scheme().ends_with("+wss");
 
         let encryption = encryption
             .then(|| {
                 // do not apply validation if using a self-signed certificate,as the documentation suggests
                 let config = if !validation {
-                    &ConnectionTLSConfig::NoSSLValidation
+                    &ConnectionTLSConfig {
+                        validation: TlsValidation::NoSSLValidation,
+                        ..tls_config.clone()
+                    }
                 } else {
                     tls_config
                 };
@@ -356,21 +865,32 @@ impl ConnectionInfo {
             Routing::No
         };
 
-        url.warn_on_unexpected_components();
-
-        let host = match url.host() {
-            Host::Domain(s) => Host::Domain(Arc::<str>::from(s)),
-            Host::Ipv4(d) => Host::Ipv4(d),
-            Host::Ipv6(d) => Host::Ipv6(d),
+        let address = match url.unix_socket() {
+            #[cfg(unix)]
+            Some(path) => Address::Unix(path?.into()),
+            _ => {
+                url.warn_on_unexpected_components();
+                let host = match url.host() {
+                    Host::Domain(s) => Host::Domain(Arc::<str>::from(s)),
+                    Host::Ipv4(d) => Host::Ipv4(d),
+                    Host::Ipv6(d) => Host::Ipv6(d),
+                };
+                Address::Tcp {
+                    host,
+                    port: url.port(),
+                }
+            }
         };
 
         Ok(Self {
-            user: user.into(),
-            password: password.into(),
-            host,
-            port: url.port(),
+            auth: auth.clone(),
+            address,
+            #[cfg(feature = "websocket")]
+            websocket,
             encryption,
+            transport: transport.clone(),
             routing,
+            notifications: notifications.clone(),
         })
     }
 
@@ -389,26 +909,34 @@ impl ConnectionInfo {
         }
 
         let builder = ClientConfig::builder();
-        let config = match tls_config {
-            ConnectionTLSConfig::None => {
-                warn!("TLS config set to None but required from the URI. Using default config.");
-                builder
-                    .with_root_certificates(root_cert_store)
-                    .with_no_client_auth()
+        let builder = match &tls_config.validation {
+            TlsValidation::None => {
+                if tls_config.client_identity.is_none() {
+                    warn!(
+                        "TLS config set to None but required from the URI. Using default config."
+                    );
+                }
+                builder.with_root_certificates(root_cert_store)
             }
-            ConnectionTLSConfig::ClientCACertificate(certificate) => {
+            TlsValidation::ClientCACertificate(certificate) => {
                 let cert_file = File::open(&certificate.cert_file)?;
                 let mut reader = BufReader::new(cert_file);
                 let certs = rustls_pemfile::certs(&mut reader).flatten();
                 root_cert_store.add_parsable_certificates(certs);
-                builder
-                    .with_root_certificates(root_cert_store)
-                    .with_no_client_auth()
+                builder.with_root_certificates(root_cert_store)
             }
-            ConnectionTLSConfig::NoSSLValidation => builder
+            TlsValidation::NoSSLValidation => builder
                 .dangerous()
-                .with_custom_certificate_verifier(Arc::new(NoCertificateVerification))
-                .with_no_client_auth(),
+                .with_custom_certificate_verifier(Arc::new(NoCertificateVerification)),
+        };
+        let config = match &tls_config.client_identity {
+            Some(identity) => {
+                let (certs, key) = identity.load()?;
+                builder
+                    .with_client_auth_cert(certs, key)
+                    .map_err(|e| Error::InvalidClientIdentity(e.to_string()))?
+            }
+            None => builder.with_no_client_auth(),
         };
 
         let config = Arc::new(config);
@@ -424,18 +952,33 @@ impl ConnectionInfo {
         Ok((connector, domain))
     }
 
+    /// The oldest version to offer in the handshake, see [`crate::ConfigBuilder::min_bolt_version`].
+    fn min_version(&self) -> Version {
+        self.transport.min_version.unwrap_or(DEFAULT_MIN_VERSION)
+    }
+
+    /// The same connection details, but authenticating with `auth`.
+    pub(crate) fn with_auth(&self, auth: Auth) -> Self {
+        Self {
+            auth,
+            ..self.clone()
+        }
+    }
+
     #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
-    pub(crate) fn to_hello(&self, version: Version) -> BoltRequest {
-        HelloBuilder::new(&*self.user, &*self.password)
+    pub(crate) fn to_hello(&self, version: Version, auth: &AuthToken) -> BoltRequest {
+        HelloBuilder::new(auth.clone())
             .with_routing(self.routing.clone())
+            .with_notification_filter(self.notifications.clone())
             .build(version)
     }
 
     #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
-    pub(crate) fn to_hello(&self, version: Version) -> Hello {
+    pub(crate) fn to_hello<'a>(&'a self, version: Version, auth: &'a AuthToken) -> Hello<'a> {
+        let hello = HelloBuilder::new(auth).with_notification_filter(&self.notifications);
         match self.routing {
-            Routing::No => HelloBuilder::new(&self.user, &self.password).build(version),
-            Routing::Yes(ref routing) => HelloBuilder::new(&self.user, &self.password)
+            Routing::No => hello.build(version),
+            Routing::Yes(ref routing) => hello
                 .with_routing(
                     routing
                         .iter()
@@ -444,6 +987,16 @@ impl ConnectionInfo {
                 .build(version),
         }
     }
+
+    #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
+    pub(crate) fn to_logon(&self, auth: &AuthToken) -> BoltRequest {
+        BoltRequest::logon(auth)
+    }
+
+    #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+    pub(crate) fn to_logon<'a>(&self, auth: &'a AuthToken) -> Logon<'a> {
+        Logon::new(auth)
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -452,7 +1005,7 @@ pub struct NeoUrl(Url);
 impl NeoUrl {
     pub(crate) fn parse(uri: &str) -> Result<Self> {
         let url = match Url::parse(uri) {
-            Ok(url) if url.has_host() => url,
+            Ok(url) if url.has_host() || url.scheme() == UNIX_SCHEME => url,
             // missing scheme
             Ok(_) | Err(url::ParseError::RelativeUrlWithoutBase) => {
                 Url::parse(&format!("bolt://{}", uri))?
@@ -475,7 +1028,37 @@ impl NeoUrl {
         self.0.port().unwrap_or(7687)
     }
 
-    fn routing_context(&mut self) -> Vec<(BoltString, BoltString)> {
+    /// The same URI, but for the server at `address`.
+    #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+    pub(crate) fn with_address(&self, address: &ServerAddress) -> Result<Self> {
+        let mut url = self.0.clone();
+        let host = match address.host() {
+            ipv6 if ipv6.contains(':') => format!("[{ipv6}]"),
+            host => host.to_owned(),
+        };
+        url.set_host(Some(&host))?;
+        url.set_port(Some(address.port()))
+            .map_err(|()| Error::UnsupportedScheme(url.scheme().to_owned()))?;
+        Ok(Self(url))
+    }
+
+    /// The path of the socket for `bolt+unix` URIs, which must not have a host other than `localhost`.
+    #[cfg(unix)]
+    fn unix_socket(&self) -> Option<Result<PathBuf>> {
+        (self.scheme() == UNIX_SCHEME).then(|| {
+            self.0.to_file_path().map_err(|_| {
+                let host = self.0.host_str().unwrap_or_default();
+                Error::UnsupportedScheme(format!("{UNIX_SCHEME} with the remote host {host}"))
+            })
+        })
+    }
+
+    #[cfg(not(unix))]
+    fn unix_socket(&self) -> Option<Result<std::convert::Infallible>> {
+        None
+    }
+
+    pub(crate) fn routing_context(&mut self) -> Vec<(BoltString, BoltString)> {
         vec![(
             "address".into(),
             format!("{}:{}", self.0.host().unwrap(), self.port()).into(),
@@ -512,7 +1095,17 @@ impl Display for NeoUrl {
     }
 }
 
+mod connector;
+mod happy_eyeballs;
+mod proxy;
+#[cfg(feature = "websocket")]
+mod websocket;
+
+pub use connector::{ConnectFuture, Connector, Transport};
+
 mod stream {
+    use std::{fmt, pin::Pin};
+
     use pin_project_lite::pin_project;
     use tokio::{
         io::{AsyncRead, AsyncWrite},
@@ -520,12 +1113,85 @@ mod stream {
     };
     use tokio_rustls::client::TlsStream;
 
+    #[cfg(unix)]
+    use tokio::net::UnixStream;
+    #[cfg(not(unix))]
+    type UnixStream = Unavailable;
+
+    #[cfg(feature = "websocket")]
+    use super::websocket::WebSocket;
+    #[cfg(not(feature = "websocket"))]
+    type WebSocket = Unavailable;
+
+    use super::Transport;
+
     pin_project! {
         #[project = ConnectionStreamProj]
-        #[derive(Debug)]
+        #[allow(clippy::large_enum_variant)]
         pub(super) enum ConnectionStream {
             Unencrypted { #[pin] stream: TcpStream },
             Encrypted { #[pin] stream: TlsStream<TcpStream> },
+            Unix { #[pin] stream: UnixStream },
+            WebSocket { #[pin] stream: Box<WebSocket> },
+            Custom { #[pin] stream: Box<dyn Transport> },
+        }
+    }
+
+    /// Stands in for the stream of a transport that is not available on this platform
+    /// or without its feature, there are no values of it.
+    #[derive(Debug)]
+    #[cfg_attr(all(unix, feature = "websocket"), allow(dead_code))]
+    pub(super) enum Unavailable {}
+
+    impl AsyncRead for Unavailable {
+        fn poll_read(
+            self: Pin<&mut Self>,
+            _: &mut std::task::Context<'_>,
+            _: &mut tokio::io::ReadBuf<'_>,
+        ) -> std::task::Poll<std::io::Result<()>> {
+            match *self {}
+        }
+    }
+
+    impl AsyncWrite for Unavailable {
+        fn poll_write(
+            self: Pin<&mut Self>,
+            _: &mut std::task::Context<'_>,
+            _: &[u8],
+        ) -> std::task::Poll<Result<usize, std::io::Error>> {
+            match *self {}
+        }
+
+        fn poll_flush(
+            self: Pin<&mut Self>,
+            _: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<Result<(), std::io::Error>> {
+            match *self {}
+        }
+
+        fn poll_shutdown(
+            self: Pin<&mut Self>,
+            _: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<Result<(), std::io::Error>> {
+            match *self {}
+        }
+    }
+
+    impl fmt::Debug for ConnectionStream {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                ConnectionStream::Unencrypted { stream } => {
+                    f.debug_tuple("Unencrypted").field(stream).finish()
+                }
+                ConnectionStream::Encrypted { stream } => {
+                    f.debug_tuple("Encrypted").field(stream).finish()
+                }
+                ConnectionStream::Unix { stream } => f.debug_tuple("Unix").field(stream).finish(),
+                ConnectionStream::WebSocket { stream } => {
+                    f.debug_tuple("WebSocket").field(stream).finish()
+                }
+                ConnectionStream::Custom { .. } => f.write_str("Custom(..)"),
+            }
         }
     }
 
@@ -541,53 +1207,95 @@ mod stream {
         }
     }
 
+    #[cfg(unix)]
+    impl From<UnixStream> for ConnectionStream {
+        fn from(stream: UnixStream) -> Self {
+            ConnectionStream::Unix { stream }
+        }
+    }
+
+    #[cfg(feature = "websocket")]
+    impl From<WebSocket> for ConnectionStream {
+        fn from(stream: WebSocket) -> Self {
+            ConnectionStream::WebSocket {
+                stream: Box::new(stream),
+            }
+        }
+    }
+
+    impl From<Box<dyn Transport>> for ConnectionStream {
+        fn from(stream: Box<dyn Transport>) -> Self {
+            ConnectionStream::Custom { stream }
+        }
+    }
+
+    impl From<TlsStream<Box<dyn Transport>>> for ConnectionStream {
+        fn from(stream: TlsStream<Box<dyn Transport>>) -> Self {
+            ConnectionStream::Custom {
+                stream: Box::new(stream),
+            }
+        }
+    }
+
     impl AsyncRead for ConnectionStream {
         fn poll_read(
-            self: std::pin::Pin<&mut Self>,
+            self: Pin<&mut Self>,
             cx: &mut std::task::Context<'_>,
             buf: &mut tokio::io::ReadBuf<'_>,
         ) -> std::task::Poll<std::io::Result<()>> {
             match self.project() {
                 ConnectionStreamProj::Unencrypted { stream } => stream.poll_read(cx, buf),
                 ConnectionStreamProj::Encrypted { stream } => stream.poll_read(cx, buf),
+                ConnectionStreamProj::Unix { stream } => stream.poll_read(cx, buf),
+                ConnectionStreamProj::WebSocket { stream } => stream.poll_read(cx, buf),
+                ConnectionStreamProj::Custom { stream } => stream.poll_read(cx, buf),
             }
         }
     }
 
     impl AsyncWrite for ConnectionStream {
         fn poll_write(
-            self: std::pin::Pin<&mut Self>,
+            self: Pin<&mut Self>,
             cx: &mut std::task::Context<'_>,
             buf: &[u8],
         ) -> std::task::Poll<Result<usize, std::io::Error>> {
             match self.project() {
                 ConnectionStreamProj::Unencrypted { stream } => stream.poll_write(cx, buf),
                 ConnectionStreamProj::Encrypted { stream } => stream.poll_write(cx, buf),
+                ConnectionStreamProj::Unix { stream } => stream.poll_write(cx, buf),
+                ConnectionStreamProj::WebSocket { stream } => stream.poll_write(cx, buf),
+                ConnectionStreamProj::Custom { stream } => stream.poll_write(cx, buf),
             }
         }
 
         fn poll_flush(
-            self: std::pin::Pin<&mut Self>,
+            self: Pin<&mut Self>,
             cx: &mut std::task::Context<'_>,
         ) -> std::task::Poll<Result<(), std::io::Error>> {
             match self.project() {
                 ConnectionStreamProj::Unencrypted { stream } => stream.poll_flush(cx),
                 ConnectionStreamProj::Encrypted { stream } => stream.poll_flush(cx),
+                ConnectionStreamProj::Unix { stream } => stream.poll_flush(cx),
+                ConnectionStreamProj::WebSocket { stream } => stream.poll_flush(cx),
+                ConnectionStreamProj::Custom { stream } => stream.poll_flush(cx),
             }
         }
 
         fn poll_shutdown(
-            self: std::pin::Pin<&mut Self>,
+            self: Pin<&mut Self>,
             cx: &mut std::task::Context<'_>,
         ) -> std::task::Poll<Result<(), std::io::Error>> {
             match self.project() {
                 ConnectionStreamProj::Unencrypted { stream } => stream.poll_shutdown(cx),
                 ConnectionStreamProj::Encrypted { stream } => stream.poll_shutdown(cx),
+                ConnectionStreamProj::Unix { stream } => stream.poll_shutdown(cx),
+                ConnectionStreamProj::WebSocket { stream } => stream.poll_shutdown(cx),
+                ConnectionStreamProj::Custom { stream } => stream.poll_shutdown(cx),
             }
         }
 
         fn poll_write_vectored(
-            self: std::pin::Pin<&mut Self>,
+            self: Pin<&mut Self>,
             cx: &mut std::task::Context<'_>,
             bufs: &[std::io::IoSlice<'_>],
         ) -> std::task::Poll<Result<usize, std::io::Error>> {
@@ -596,6 +1304,9 @@ mod stream {
                     stream.poll_write_vectored(cx, bufs)
                 }
                 ConnectionStreamProj::Encrypted { stream } => stream.poll_write_vectored(cx, bufs),
+                ConnectionStreamProj::Unix { stream } => stream.poll_write_vectored(cx, bufs),
+                ConnectionStreamProj::WebSocket { stream } => stream.poll_write_vectored(cx, bufs),
+                ConnectionStreamProj::Custom { stream } => stream.poll_write_vectored(cx, bufs),
             }
         }
 
@@ -603,6 +1314,9 @@ mod stream {
             match self {
                 ConnectionStream::Unencrypted { stream } => stream.is_write_vectored(),
                 ConnectionStream::Encrypted { stream } => stream.is_write_vectored(),
+                ConnectionStream::Unix { stream } => stream.is_write_vectored(),
+                ConnectionStream::WebSocket { stream } => stream.is_write_vectored(),
+                ConnectionStream::Custom { stream } => stream.is_write_vectored(),
             }
         }
     }
@@ -658,9 +1372,382 @@ impl ServerCertVerifier for NoCertificateVerification {
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
+    use tokio::{
+        io::{duplex, AsyncReadExt, AsyncWriteExt},
+        net::{TcpListener, TcpStream},
+    };
     use url::Host;
 
-    use super::NeoUrl;
+    #[cfg(unix)]
+    use super::Address;
+    use super::{put_varint, read_varint, Connection, ConnectionInfo, Hints, NeoUrl, Routing};
+    use crate::{
+        auth::{AuthToken, ConnectionTLSConfig},
+        config::{NotificationFilter, SocketOptions, TransportConfig},
+        errors::{ConnectStep, Error},
+        messages::{BoltRequest, TelemetryApi},
+        types::BoltMap,
+        version::{Version, DEFAULT_MIN_VERSION},
+        BoltType, ServerAddress,
+    };
+    use bytes::BytesMut;
+
+    #[test]
+    fn should_read_telemetry_hint() {
+        let mut hints = BoltMap::new();
+        hints.put("connection.recv_timeout_seconds".into(), 120.into());
+        hints.put("telemetry.enabled".into(), true.into());
+        let mut metadata = BoltMap::new();
+        metadata.put("hints".into(), BoltType::Map(hints));
+
+        let hints = metadata.get::<Hints>("hints").unwrap();
+
+        assert!(hints.telemetry_enabled);
+        assert_eq!(hints.recv_timeout(), Some(Duration::from_secs(120)));
+    }
+
+    #[tokio::test]
+    async fn should_only_send_written_messages_on_flush() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let client = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (mut server, _) = listener.accept().await.unwrap();
+
+        let mut connection = Connection::create(client, Version::V5_4);
+        let telemetry = || BoltRequest::telemetry(TelemetryApi::AutoCommit);
+        connection.write(telemetry()).await.unwrap();
+        connection.write(telemetry()).await.unwrap();
+
+        let mut buf = [0; 16];
+        assert_eq!(
+            server.try_read(&mut buf).unwrap_err().kind(),
+            std::io::ErrorKind::WouldBlock
+        );
+
+        connection.flush().await.unwrap();
+
+        let telemetry = [0x00, 0x03, 0xB1, 0x54, 0x02, 0x00, 0x00];
+        let mut received = [0; 14];
+        server.read_exact(&mut received).await.unwrap();
+        assert_eq!(received, [telemetry, telemetry].concat()[..]);
+    }
+
+    #[tokio::test]
+    async fn should_send_goodbye_without_waiting_for_a_response() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let client = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (mut server, _) = listener.accept().await.unwrap();
+
+        let mut connection = Connection::create(client, Version::V5_4);
+        connection.goodbye().await.unwrap();
+
+        let mut received = [0; 6];
+        server.read_exact(&mut received).await.unwrap();
+        assert_eq!(received, [0x00, 0x02, 0xB0, 0x02, 0x00, 0x00]);
+        assert_eq!(connection.pending, 0);
+        assert!(connection.is_broken());
+    }
+
+    #[tokio::test]
+    async fn should_skip_pending_responses() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let client = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (mut server, _) = listener.accept().await.unwrap();
+
+        let mut connection = Connection::create(client, Version::V5_4);
+        let telemetry = || BoltRequest::telemetry(TelemetryApi::AutoCommit);
+        connection.write(telemetry()).await.unwrap();
+        connection.write(telemetry()).await.unwrap();
+        connection.flush().await.unwrap();
+
+        let record = [0x00, 0x03, 0xB1, 0x71, 0x90, 0x00, 0x00];
+        let success = [0x00, 0x03, 0xB1, 0x70, 0xA0, 0x00, 0x00];
+        let failure = [0x00, 0x03, 0xB1, 0x7F, 0xA0, 0x00, 0x00];
+        server
+            .write_all(&[record, success, failure].concat())
+            .await
+            .unwrap();
+
+        let last = connection.recv_last_summary().await.unwrap();
+        assert_eq!(&last[..], &[0xB1, 0x7F, 0xA0]);
+        assert_eq!(connection.pending, 0);
+    }
+
+    #[tokio::test]
+    async fn should_send_telemetry_once_along_with_the_next_request() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let client = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (mut server, _) = listener.accept().await.unwrap();
+
+        let success = [0x00, 0x03, 0xB1, 0x70, 0xA0, 0x00, 0x00];
+        server
+            .write_all(&[success, success].concat())
+            .await
+            .unwrap();
+
+        let mut connection = Connection::create(client, Version::V5_4);
+        connection.telemetry = true;
+        connection
+            .write_telemetry(TelemetryApi::AutoCommit)
+            .await
+            .unwrap();
+        connection
+            .write_telemetry(TelemetryApi::AutoCommit)
+            .await
+            .unwrap();
+        connection.reset().await.unwrap();
+        assert_eq!(connection.pending, 0);
+
+        let telemetry = [0x00, 0x03, 0xB1, 0x54, 0x02, 0x00, 0x00];
+        let reset = [0x00, 0x02, 0xB0, 0x0F, 0x00, 0x00];
+        let mut received = [0; 13];
+        server.read_exact(&mut received).await.unwrap();
+        assert_eq!(received[..], [&telemetry[..], &reset[..]].concat());
+    }
+
+    #[tokio::test]
+    async fn should_reauth_with_logoff_and_logon() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let client = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (mut server, _) = listener.accept().await.unwrap();
+
+        let success = [0x00, 0x03, 0xB1, 0x70, 0xA0, 0x00, 0x00];
+        server
+            .write_all(&[success, success].concat())
+            .await
+            .unwrap();
+
+        let mut connection = Connection::create(client, Version::V5_1);
+        assert!(connection.supports_reauth());
+        connection.reauth(&AuthToken::none()).await.unwrap();
+        assert_eq!(connection.auth_token(), Some(&AuthToken::none()));
+
+        let logoff = [0x00, 0x02, 0xB0, 0x6B, 0x00, 0x00];
+        let mut received = [0; 6];
+        server.read_exact(&mut received).await.unwrap();
+        assert_eq!(received, logoff);
+
+        let mut received = [0; 4];
+        server.read_exact(&mut received).await.unwrap();
+        assert_eq!(received, [0x00, 0x0F, 0xB1, 0x6A]);
+    }
+
+    #[tokio::test]
+    async fn should_not_reauth_before_5_1() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let client = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+
+        let mut connection = Connection::create(client, Version::V5_0);
+
+        assert!(!connection.supports_reauth());
+        assert!(matches!(
+            connection.reauth(&AuthToken::none()).await,
+            Err(Error::UnsupportedFeature(_, Version::V5_0))
+        ));
+    }
+
+    #[test]
+    fn should_ignore_non_positive_recv_timeout() {
+        let hints = Hints {
+            recv_timeout_seconds: Some(0),
+            ..Hints::default()
+        };
+        assert_eq!(hints.recv_timeout(), None);
+        assert_eq!(Hints::default().recv_timeout(), None);
+    }
+
+    #[tokio::test]
+    async fn should_keep_the_version_when_the_utc_patch_is_accepted() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let client = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (mut server, _) = listener.accept().await.unwrap();
+        let info = ConnectionInfo::new(
+            "bolt://localhost:7687",
+            &AuthToken::basic("user", "pass").into(),
+            &ConnectionTLSConfig::default(),
+            &NotificationFilter::default(),
+            &TransportConfig::default(),
+        )
+        .unwrap();
+
+        let success = [
+            &[0xB1, 0x70, 0xA3, 0x86][..],
+            b"server",
+            &[0x81, b'N', 0x8D],
+            b"connection_id",
+            &[0x81, b'1', 0x8A],
+            b"patch_bolt",
+            &[0x91, 0x83],
+            b"utc",
+        ]
+        .concat();
+        server.write_u16(success.len() as u16).await.unwrap();
+        server.write_all(&success).await.unwrap();
+        server.write_u16(0).await.unwrap();
+
+        let mut connection = Connection::create(client, Version::V4_4);
+        let token = AuthToken::basic("user", "pass");
+        connection
+            .hello(info.to_hello(Version::V4_4, &token))
+            .await
+            .unwrap();
+
+        assert_eq!(connection.version(), Version::V4_4);
+        assert!(connection.encoding().utc);
+    }
+
+    #[tokio::test]
+    async fn should_time_out_when_server_goes_silent() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let client = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (_server, _) = listener.accept().await.unwrap();
+
+        let mut connection = Connection::create(client, Version::V5_4);
+        connection.recv_timeout = Some(Duration::from_millis(10));
+
+        let error = connection.recv_bytes().await.unwrap_err();
+        assert!(matches!(error, Error::RecvTimeout(_)));
+        assert!(connection.is_broken());
+    }
+
+    #[tokio::test]
+    async fn should_keep_waiting_while_server_sends_noops() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let client = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (mut server, _) = listener.accept().await.unwrap();
+
+        let mut connection = Connection::create(client, Version::V5_4);
+        connection.recv_timeout = Some(Duration::from_millis(50));
+
+        let server = tokio::spawn(async move {
+            for _ in 0..5 {
+                tokio::time::sleep(Duration::from_millis(30)).await;
+                server.write_all(&[0x00, 0x00]).await.unwrap();
+            }
+            let success = [0x00, 0x03, 0xB1, 0x70, 0xA0, 0x00, 0x00];
+            server.write_all(&success).await.unwrap();
+            server
+        });
+
+        let response = connection.recv_bytes().await.unwrap();
+        assert_eq!(&response[..], &[0xB1, 0x70, 0xA0]);
+        assert!(!connection.is_broken());
+        let _server = server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn should_roundtrip_varints() {
+        for value in [0, 1, 0x7F, 0x80, 0x3FFF, 0x4000, u64::MAX] {
+            let mut bytes = BytesMut::new();
+            put_varint(&mut bytes, value);
+            let mut bytes = &bytes[..];
+            assert_eq!(read_varint(&mut bytes).await.unwrap(), value);
+            assert!(bytes.is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn should_accept_version_picked_by_server() {
+        let (mut client, mut server) = duplex(64);
+        server.write_all(&[0, 0, 4, 4]).await.unwrap();
+
+        let version = Connection::init(&mut client, DEFAULT_MIN_VERSION)
+            .await
+            .unwrap();
+        assert_eq!(version, Version::V4_4);
+
+        let mut handshake = [0; 20];
+        server.read_exact(&mut handshake).await.unwrap();
+        assert_eq!(&handshake[..8], &[0x60, 0x60, 0xB0, 0x17, 0, 0, 1, 0xFF]);
+    }
+
+    #[tokio::test]
+    async fn should_agree_on_bolt_4_1_with_neo4j_4_2() {
+        for min_version in [Version::V3, DEFAULT_MIN_VERSION] {
+            let (mut client, mut server) = duplex(64);
+            server.write_all(&[0, 0, 1, 4]).await.unwrap();
+
+            let version = Connection::init(&mut client, min_version).await.unwrap();
+            assert_eq!(version, Version::V4_1);
+
+            let mut handshake = [0; 20];
+            server.read_exact(&mut handshake).await.unwrap();
+            assert!(handshake[4..].chunks(4).any(|slot| slot == [0, 1, 1, 4]));
+        }
+    }
+
+    #[tokio::test]
+    async fn should_agree_on_bolt_3_with_a_v3_minimum() {
+        let (mut client, mut server) = duplex(64);
+        server.write_all(&[0, 0, 0, 3]).await.unwrap();
+
+        let version = Connection::init(&mut client, Version::V3).await.unwrap();
+        assert_eq!(version, Version::V3);
+
+        let mut handshake = [0; 20];
+        server.read_exact(&mut handshake).await.unwrap();
+        assert_eq!(&handshake[4..8], &[0, 7, 7, 5]);
+        assert_eq!(&handshake[16..], &[0, 0, 0, 3]);
+    }
+
+    #[tokio::test]
+    async fn should_negotiate_version_from_manifest() {
+        let (mut client, mut server) = duplex(64);
+        server
+            .write_all(&[0, 0, 1, 0xFF, 0x02, 0, 7, 7, 5, 0, 4, 4, 4, 0x81, 0x01])
+            .await
+            .unwrap();
+
+        let version = Connection::init(&mut client, DEFAULT_MIN_VERSION)
+            .await
+            .unwrap();
+        assert_eq!(version, Version::V5_7);
+
+        let mut handshake = [0; 20];
+        server.read_exact(&mut handshake).await.unwrap();
+        let mut reply = [0; 5];
+        server.read_exact(&mut reply).await.unwrap();
+        assert_eq!(reply, [0, 0, 7, 5, 0]);
+    }
+
+    #[tokio::test]
+    async fn should_reject_manifest_without_overlap() {
+        let (mut client, mut server) = duplex(64);
+        server
+            .write_all(&[0, 0, 1, 0xFF, 0x01, 0, 0, 0, 6, 0x00])
+            .await
+            .unwrap();
+
+        let error = Connection::init(&mut client, DEFAULT_MIN_VERSION)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, Error::UnsupportedVersion(6, 0)));
+
+        let mut handshake = [0; 20];
+        server.read_exact(&mut handshake).await.unwrap();
+        let mut reply = [0; 5];
+        server.read_exact(&mut reply).await.unwrap();
+        assert_eq!(reply, [0, 0, 0, 0, 0]);
+    }
 
     #[test]
     fn should_parse_uri() {
@@ -685,4 +1772,267 @@ mod tests {
         assert_eq!(url.host(), Host::Domain("127.0.0.1"));
         assert_eq!(url.scheme(), "bolt");
     }
+
+    #[tokio::test]
+    async fn should_time_out_connect_step() {
+        let pending = std::future::pending::<std::io::Result<()>>();
+
+        let address = ServerAddress::new("localhost", 7687).to_string();
+        let timeout = Some(Duration::from_millis(10));
+
+        let error = Connection::connect_step(ConnectStep::TlsHandshake, &address, timeout, pending)
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "the TLS handshake with localhost:7687 did not complete within 10ms"
+        );
+        assert!(matches!(
+            error,
+            Error::ConnectTimeout(ConnectStep::TlsHandshake, a, timeout)
+                if a == address && timeout == Duration::from_millis(10)
+        ));
+    }
+
+    #[tokio::test]
+    async fn should_apply_socket_options() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let stream = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let options = SocketOptions {
+            keepalive: Some(Duration::from_secs(30)),
+            nodelay: true,
+            ..SocketOptions::default()
+        };
+
+        options.apply(&stream).unwrap();
+
+        assert!(stream.nodelay().unwrap());
+        assert!(socket2::SockRef::from(&stream).keepalive().unwrap());
+    }
+
+    #[tokio::test]
+    async fn should_connect_through_custom_connector() {
+        use super::{ConnectFuture, Connector, Transport};
+        use std::sync::Mutex;
+        use tokio::io::DuplexStream;
+
+        #[derive(Default)]
+        struct InMemory {
+            client: Mutex<Option<DuplexStream>>,
+            address: Mutex<Option<(String, u16)>>,
+        }
+
+        impl Connector for InMemory {
+            fn connect<'a>(&'a self, host: &'a str, port: u16) -> ConnectFuture<'a> {
+                *self.address.lock().unwrap() = Some((host.to_owned(), port));
+                let client = self.client.lock().unwrap().take().unwrap();
+                Box::pin(async move { Ok(Box::new(client) as Box<dyn Transport>) })
+            }
+        }
+
+        let (client, mut server) = duplex(64);
+        let connector = std::sync::Arc::new(InMemory {
+            client: Mutex::new(Some(client)),
+            ..InMemory::default()
+        });
+        let transport = TransportConfig {
+            connector: Some(connector.clone()),
+            ..TransportConfig::default()
+        };
+        let info = ConnectionInfo::new(
+            "bolt://[::1]:7687",
+            &AuthToken::basic("user", "pass").into(),
+            &ConnectionTLSConfig::default(),
+            &NotificationFilter::default(),
+            &transport,
+        )
+        .unwrap();
+
+        let server = tokio::spawn(async move {
+            let mut handshake = [0; 20];
+            server.read_exact(&mut handshake).await.unwrap();
+            server.write_all(&[0, 0, 4, 4]).await.unwrap();
+            server
+        });
+
+        let connection = Connection::prepare(&info).await.unwrap();
+        let _server = server.await.unwrap();
+
+        assert_eq!(connection.version(), Version::V4_4);
+        assert_eq!(
+            connector.address.lock().unwrap().take(),
+            Some(("::1".to_owned(), 7687))
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn should_time_out_the_handshake_over_a_unix_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("neo4j.sock");
+        let listener = tokio::net::UnixListener::bind(&path).unwrap();
+        let transport = TransportConfig {
+            socket: SocketOptions {
+                connect_timeout: Some(Duration::from_millis(10)),
+                ..SocketOptions::default()
+            },
+            ..TransportConfig::default()
+        };
+        let info = ConnectionInfo::new(
+            &format!("bolt+unix://{}", path.display()),
+            &AuthToken::basic("user", "pass").into(),
+            &ConnectionTLSConfig::default(),
+            &NotificationFilter::default(),
+            &transport,
+        )
+        .unwrap();
+
+        let error = Connection::prepare(&info).await.unwrap_err();
+        let _server = listener.accept().await.unwrap();
+
+        assert!(matches!(
+            error,
+            Error::ConnectTimeout(ConnectStep::BoltHandshake, address, _)
+                if address == path.display().to_string()
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn should_parse_unix_socket_uri() {
+        let info = ConnectionInfo::new(
+            "bolt+unix:///var/run/neo4j%20bolt.sock",
+            &AuthToken::basic("user", "pass").into(),
+            &ConnectionTLSConfig::default(),
+            &NotificationFilter::default(),
+            &TransportConfig::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            info.address,
+            Address::Unix(std::path::Path::new("/var/run/neo4j bolt.sock").into())
+        );
+        assert_eq!(info.routing, Routing::No);
+        assert!(info.encryption.is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn should_reject_unix_socket_uri_with_remote_host() {
+        let info = ConnectionInfo::new(
+            "bolt+unix://example.com/var/run/neo4j.sock",
+            &AuthToken::basic("user", "pass").into(),
+            &ConnectionTLSConfig::default(),
+            &NotificationFilter::default(),
+            &TransportConfig::default(),
+        );
+
+        assert!(matches!(info, Err(Error::UnsupportedScheme(_))));
+    }
+
+    #[cfg(feature = "websocket")]
+    #[test]
+    fn should_parse_websocket_uris() {
+        for (uri, routing) in [
+            ("bolt+ws://localhost:7687", false),
+            ("neo4j+ws://localhost:7687", true),
+        ] {
+            let info = ConnectionInfo::new(
+                uri,
+                &AuthToken::basic("user", "pass").into(),
+                &ConnectionTLSConfig::default(),
+                &NotificationFilter::default(),
+                &TransportConfig::default(),
+            )
+            .unwrap();
+
+            assert!(info.websocket, "{uri}");
+            assert_eq!(matches!(info.routing, Routing::Yes(_)), routing, "{uri}");
+            assert!(info.encryption.is_none(), "{uri}");
+        }
+    }
+
+    #[cfg(feature = "websocket")]
+    #[tokio::test]
+    async fn should_connect_through_websocket() {
+        use futures::{SinkExt as _, StreamExt as _};
+        use tokio_tungstenite::tungstenite::Message;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let uri = format!("bolt+ws://{}", listener.local_addr().unwrap());
+        let info = ConnectionInfo::new(
+            &uri,
+            &AuthToken::basic("user", "pass").into(),
+            &ConnectionTLSConfig::default(),
+            &NotificationFilter::default(),
+            &TransportConfig::default(),
+        )
+        .unwrap();
+
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut server = tokio_tungstenite::accept_async(stream).await.unwrap();
+            let handshake = server.next().await.unwrap().unwrap();
+            server
+                .send(Message::Binary(vec![0, 0, 4, 4]))
+                .await
+                .unwrap();
+            (server, handshake)
+        });
+
+        let connection = Connection::prepare(&info).await.unwrap();
+        let (_server, handshake) = server.await.unwrap();
+
+        assert_eq!(connection.version(), Version::V4_4);
+        assert!(matches!(handshake, Message::Binary(bytes) if bytes.len() == 20));
+    }
+
+    #[cfg(not(feature = "websocket"))]
+    #[test]
+    fn should_reject_websocket_uris_without_the_feature() {
+        let info = ConnectionInfo::new(
+            "bolt+ws://localhost:7687",
+            &AuthToken::basic("user", "pass").into(),
+            &ConnectionTLSConfig::default(),
+            &NotificationFilter::default(),
+            &TransportConfig::default(),
+        );
+
+        assert!(matches!(info, Err(Error::UnsupportedScheme(_))));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn should_connect_through_unix_socket() {
+        let path = std::env::temp_dir().join(format!("neo4rs-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = tokio::net::UnixListener::bind(&path).unwrap();
+        let uri = format!("bolt+unix://{}", path.display());
+        let info = ConnectionInfo::new(
+            &uri,
+            &AuthToken::basic("user", "pass").into(),
+            &ConnectionTLSConfig::default(),
+            &NotificationFilter::default(),
+            &TransportConfig::default(),
+        )
+        .unwrap();
+
+        let server = tokio::spawn(async move {
+            let (mut server, _) = listener.accept().await.unwrap();
+            let mut handshake = [0; 20];
+            server.read_exact(&mut handshake).await.unwrap();
+            server.write_all(&[0, 0, 4, 4]).await.unwrap();
+            server
+        });
+
+        let connection = Connection::prepare(&info).await.unwrap();
+        let _server = server.await.unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(connection.version(), Version::V4_4);
+    }
 }
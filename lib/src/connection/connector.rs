@@ -0,0 +1,45 @@
+use std::{future::Future, io, pin::Pin};
+
+use tokio::io::{AsyncRead, AsyncWrite};
+
+/// A stream that carries Bolt to the server, as returned by a [`Connector`].
+///
+/// This is implemented for all types that are `AsyncRead + AsyncWrite + Send + Sync + Unpin`,
+/// e.g. [`tokio::net::TcpStream`] or [`tokio::io::DuplexStream`].
+pub trait Transport: AsyncRead + AsyncWrite + Send + Sync + Unpin {}
+
+impl<T: AsyncRead + AsyncWrite + Send + Sync + Unpin> Transport for T {}
+
+/// The future returned by [`Connector::connect`].
+pub type ConnectFuture<'a> =
+    Pin<Box<dyn Future<Output = io::Result<Box<dyn Transport>>> + Send + 'a>>;
+
+/// Opens the streams to the server, replacing the built-in TCP connections.
+///
+/// A connector is set with [`crate::ConfigBuilder::with_connector`].
+/// It is called for every new connection, with the host and port from the URI
+/// or, when routing, from the routing table.
+///
+/// # Example
+///
+/// ```no_run
+/// use neo4rs::{ConnectFuture, Connector, Transport};
+///
+/// struct Internal;
+///
+/// impl Connector for Internal {
+///     fn connect<'a>(&'a self, host: &'a str, port: u16) -> ConnectFuture<'a> {
+///         Box::pin(async move {
+///             let host = format!("{host}.internal");
+///             let stream = tokio::net::TcpStream::connect((host, port)).await?;
+///             Ok(Box::new(stream) as Box<dyn Transport>)
+///         })
+///     }
+/// }
+/// ```
+pub trait Connector: Send + Sync {
+    /// Opens a stream to the server at `host` and `port`.
+    ///
+    /// IPv6 addresses are given without the brackets of the URI, e.g. `::1`.
+    fn connect<'a>(&'a self, host: &'a str, port: u16) -> ConnectFuture<'a>;
+}
@@ -0,0 +1,170 @@
+use std::{io, net::SocketAddr, time::Duration};
+
+use futures::{stream::FuturesUnordered, StreamExt as _};
+use log::debug;
+use tokio::net::TcpStream;
+
+use crate::errors::{Error, Result};
+
+/// How long to wait for an attempt before starting the next one in parallel, see RFC 8305.
+const ATTEMPT_DELAY: Duration = Duration::from_millis(250);
+
+/// Connects to the first of the addresses that accepts the connection,
+/// racing IPv6 and IPv4 addresses like Happy Eyeballs (RFC 8305).
+///
+/// Every `ATTEMPT_DELAY`, or once an attempt fails, the next address is tried
+/// while the previous attempts are still running.
+/// Each attempt fails after `timeout`, if one is set.
+pub(super) async fn connect(
+    addresses: Vec<SocketAddr>,
+    timeout: Option<Duration>,
+) -> Result<TcpStream> {
+    let mut next = interleave(addresses).into_iter();
+    let mut attempts = FuturesUnordered::new();
+    let mut errors = Vec::new();
+
+    loop {
+        if attempts.is_empty() {
+            match next.next() {
+                Some(address) => attempts.push(attempt(address, timeout)),
+                None => return Err(Error::ConnectFailed(errors)),
+            }
+        }
+
+        tokio::select! {
+            Some((address, result)) = attempts.next() => match result {
+                Ok(stream) => return Ok(stream),
+                Err(e) => {
+                    debug!("Connection to {address} failed: {e}");
+                    errors.push((address, e));
+                    if let Some(address) = next.next() {
+                        attempts.push(attempt(address, timeout));
+                    }
+                }
+            },
+            () = tokio::time::sleep(ATTEMPT_DELAY), if next.len() > 0 => {
+                if let Some(address) = next.next() {
+                    attempts.push(attempt(address, timeout));
+                }
+            }
+        }
+    }
+}
+
+async fn attempt(
+    address: SocketAddr,
+    timeout: Option<Duration>,
+) -> (SocketAddr, io::Result<TcpStream>) {
+    let connect = TcpStream::connect(address);
+    let Some(timeout) = timeout else {
+        return (address, connect.await);
+    };
+    let result = match tokio::time::timeout(timeout, connect).await {
+        Ok(result) => result,
+        Err(_) => Err(io::Error::new(
+            io::ErrorKind::TimedOut,
+            format!("timed out after {timeout:?}"),
+        )),
+    };
+    (address, result)
+}
+
+/// Alternates between the address families, starting with the family of the first address.
+fn interleave(addresses: Vec<SocketAddr>) -> Vec<SocketAddr> {
+    let Some(first) = addresses.first() else {
+        return addresses;
+    };
+    let first_is_ipv6 = first.is_ipv6();
+    let mut interleaved = Vec::with_capacity(addresses.len());
+    let (preferred, other): (Vec<_>, Vec<_>) = addresses
+        .into_iter()
+        .partition(|a| a.is_ipv6() == first_is_ipv6);
+
+    let (mut preferred, mut other) = (preferred.into_iter(), other.into_iter());
+    loop {
+        match (preferred.next(), other.next()) {
+            (None, None) => return interleaved,
+            (a, b) => interleaved.extend(a.into_iter().chain(b)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    fn addresses(addresses: &[&str]) -> Vec<SocketAddr> {
+        addresses.iter().map(|a| a.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn should_interleave_address_families() {
+        let interleaved = interleave(addresses(&[
+            "[::1]:1",
+            "[::2]:1",
+            "[::3]:1",
+            "127.0.0.1:1",
+            "127.0.0.2:1",
+        ]));
+
+        assert_eq!(
+            interleaved,
+            addresses(&[
+                "[::1]:1",
+                "127.0.0.1:1",
+                "[::2]:1",
+                "127.0.0.2:1",
+                "[::3]:1",
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn should_connect_to_next_address_after_failure() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let working = listener.local_addr().unwrap();
+        // a port that was just free is very likely to refuse the connection
+        let refusing = TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap();
+
+        let stream = connect(vec![refusing, working], Some(Duration::from_secs(5)))
+            .await
+            .unwrap();
+
+        assert_eq!(stream.peer_addr().unwrap(), working);
+    }
+
+    #[tokio::test]
+    async fn should_report_every_failed_address() {
+        let refusing = TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap();
+
+        let error = connect(vec![refusing, refusing], Some(Duration::from_secs(5)))
+            .await
+            .unwrap_err();
+
+        match error {
+            Error::ConnectFailed(errors) => {
+                assert_eq!(errors.len(), 2);
+                assert!(errors.iter().all(|(address, _)| *address == refusing));
+            }
+            otherwise => panic!("unexpected error: {otherwise:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn should_fail_without_addresses() {
+        let error = connect(vec![], Some(Duration::from_secs(5)))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, Error::ConnectFailed(errors) if errors.is_empty()));
+    }
+}
@@ -0,0 +1,333 @@
+use std::sync::Arc;
+
+use tokio::{
+    io::{AsyncRead, AsyncReadExt as _, AsyncWrite, AsyncWriteExt as _},
+    net::TcpStream,
+};
+use url::Host;
+
+use crate::{
+    config::{Proxy, ProxyKind},
+    errors::{Error, Result},
+};
+
+/// Connects to the proxy and opens a tunnel to the server at `host` and `port`.
+pub(super) async fn connect(proxy: &Proxy, host: &Host<Arc<str>>, port: u16) -> Result<TcpStream> {
+    let mut stream = TcpStream::connect(&*proxy.address).await?;
+    let auth = proxy.auth.as_ref().map(|(u, p)| (&**u, &**p));
+    match proxy.kind {
+        ProxyKind::Socks5 => socks5(&mut stream, auth, host, port).await?,
+        ProxyKind::HttpConnect => http_connect(&mut stream, auth, host, port).await?,
+    }
+    Ok(stream)
+}
+
+/// The SOCKS5 handshake, see RFC 1928 and RFC 1929 for the authentication.
+async fn socks5<S: AsyncRead + AsyncWrite + Unpin>(
+    stream: &mut S,
+    auth: Option<(&str, &str)>,
+    host: &Host<Arc<str>>,
+    port: u16,
+) -> Result<()> {
+    const VERSION: u8 = 0x05;
+    const NO_AUTH: u8 = 0x00;
+    const USER_PASSWORD: u8 = 0x02;
+
+    let greeting: &[u8] = match auth {
+        Some(_) => &[VERSION, 2, NO_AUTH, USER_PASSWORD],
+        None => &[VERSION, 1, NO_AUTH],
+    };
+    stream.write_all(greeting).await?;
+
+    let mut choice = [0; 2];
+    stream.read_exact(&mut choice).await?;
+    match (choice, auth) {
+        ([VERSION, NO_AUTH], _) => {}
+        ([VERSION, USER_PASSWORD], Some((user, password))) => {
+            let mut request = vec![0x01];
+            request.push(socks5_len(user, "username")?);
+            request.extend_from_slice(user.as_bytes());
+            request.push(socks5_len(password, "password")?);
+            request.extend_from_slice(password.as_bytes());
+            stream.write_all(&request).await?;
+
+            let mut status = [0; 2];
+            stream.read_exact(&mut status).await?;
+            if status[1] != 0x00 {
+                return Err(Error::ProxyError(
+                    "the SOCKS5 proxy rejected the credentials".into(),
+                ));
+            }
+        }
+        ([VERSION, _], _) => {
+            return Err(Error::ProxyError(
+                "the SOCKS5 proxy does not accept any of the offered authentication methods".into(),
+            ))
+        }
+        _ => return Err(Error::ProxyError("the proxy does not speak SOCKS5".into())),
+    }
+
+    let mut request = vec![VERSION, 0x01, 0x00];
+    match host {
+        Host::Domain(domain) => {
+            request.push(0x03);
+            request.push(socks5_len(domain, "host name")?);
+            request.extend_from_slice(domain.as_bytes());
+        }
+        Host::Ipv4(ip) => {
+            request.push(0x01);
+            request.extend_from_slice(&ip.octets());
+        }
+        Host::Ipv6(ip) => {
+            request.push(0x04);
+            request.extend_from_slice(&ip.octets());
+        }
+    }
+    request.extend_from_slice(&port.to_be_bytes());
+    stream.write_all(&request).await?;
+
+    let mut reply = [0; 4];
+    stream.read_exact(&mut reply).await?;
+    if reply[1] != 0x00 {
+        return Err(Error::ProxyError(format!(
+            "the SOCKS5 proxy could not reach {host}:{port}: {}",
+            socks5_reply(reply[1])
+        )));
+    }
+
+    // The address the proxy bound to is not needed, but has to be consumed
+    let bound_address_len = match reply[3] {
+        0x01 => 4,
+        0x04 => 16,
+        0x03 => usize::from(stream.read_u8().await?),
+        otherwise => {
+            return Err(Error::ProxyError(format!(
+                "the SOCKS5 proxy replied with an invalid address type {otherwise:#04x}"
+            )))
+        }
+    };
+    let mut bound_address = vec![0; bound_address_len + 2];
+    stream.read_exact(&mut bound_address).await?;
+
+    Ok(())
+}
+
+fn socks5_len(value: &str, what: &str) -> Result<u8> {
+    u8::try_from(value.len())
+        .map_err(|_| Error::ProxyError(format!("the {what} is too long for SOCKS5")))
+}
+
+fn socks5_reply(code: u8) -> &'static str {
+    match code {
+        0x01 => "general failure",
+        0x02 => "connection not allowed by ruleset",
+        0x03 => "network unreachable",
+        0x04 => "host unreachable",
+        0x05 => "connection refused",
+        0x06 => "TTL expired",
+        0x07 => "command not supported",
+        0x08 => "address type not supported",
+        _ => "unknown error",
+    }
+}
+
+/// The HTTP `CONNECT` handshake, see RFC 9110.
+async fn http_connect<S: AsyncRead + AsyncWrite + Unpin>(
+    stream: &mut S,
+    auth: Option<(&str, &str)>,
+    host: &Host<Arc<str>>,
+    port: u16,
+) -> Result<()> {
+    let target = format!("{host}:{port}");
+    let mut request = format!("CONNECT {target} HTTP/1.1\r\nHost: {target}\r\n");
+    if let Some((user, password)) = auth {
+        let credentials = base64(format!("{user}:{password}").as_bytes());
+        request.push_str(&format!("Proxy-Authorization: Basic {credentials}\r\n"));
+    }
+    request.push_str("\r\n");
+    stream.write_all(request.as_bytes()).await?;
+
+    // Read byte by byte, so that nothing of what the server sends is consumed
+    const MAX_RESPONSE_LEN: usize = 8 * 1024;
+    let mut response = Vec::new();
+    while !response.ends_with(b"\r\n\r\n") {
+        if response.len() >= MAX_RESPONSE_LEN {
+            return Err(Error::ProxyError(
+                "the response of the HTTP proxy is too long".into(),
+            ));
+        }
+        response.push(stream.read_u8().await?);
+    }
+
+    let response = String::from_utf8_lossy(&response);
+    let status_line = response.lines().next().unwrap_or_default();
+    match status_line.split(' ').nth(1) {
+        Some(status) if status.starts_with('2') && status_line.starts_with("HTTP/1.") => Ok(()),
+        _ => Err(Error::ProxyError(format!(
+            "the HTTP proxy could not reach {target}: {status_line}"
+        ))),
+    }
+}
+
+fn base64(input: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut output = String::with_capacity(input.len().div_ceil(3) * 4);
+    for chunk in input.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = u32::from_be_bytes([0, b[0], b[1], b[2]]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                output.push(char::from(ALPHABET[(n >> (18 - 6 * i)) as usize & 0x3F]));
+            } else {
+                output.push('=');
+            }
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    fn host(host: &str) -> Host<Arc<str>> {
+        match Host::parse(host).unwrap() {
+            Host::Domain(d) => Host::Domain(d.into()),
+            Host::Ipv4(ip) => Host::Ipv4(ip),
+            Host::Ipv6(ip) => Host::Ipv6(ip),
+        }
+    }
+
+    #[test]
+    fn should_encode_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"user:pass"), "dXNlcjpwYXNz");
+    }
+
+    #[tokio::test]
+    async fn should_open_socks5_tunnel_with_auth() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy = Proxy::socks5(listener.local_addr().unwrap().to_string()).with_auth("u", "p");
+
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut greeting = [0; 4];
+            stream.read_exact(&mut greeting).await.unwrap();
+            stream.write_all(&[0x05, 0x02]).await.unwrap();
+
+            let mut auth = [0; 5];
+            stream.read_exact(&mut auth).await.unwrap();
+            stream.write_all(&[0x01, 0x00]).await.unwrap();
+
+            let mut request = [0; 18];
+            stream.read_exact(&mut request).await.unwrap();
+            stream
+                .write_all(&[0x05, 0x00, 0x00, 0x01, 127, 0, 0, 1, 0x1F, 0x90])
+                .await
+                .unwrap();
+            stream.write_all(b"bolt").await.unwrap();
+            (greeting, auth, request)
+        });
+
+        let mut stream = connect(&proxy, &host("example.com"), 7687).await.unwrap();
+        let mut tunneled = [0; 4];
+        stream.read_exact(&mut tunneled).await.unwrap();
+        let (greeting, auth, request) = server.await.unwrap();
+
+        assert_eq!(greeting, [0x05, 0x02, 0x00, 0x02]);
+        assert_eq!(auth, [0x01, 0x01, b'u', 0x01, b'p']);
+        assert_eq!(request[..5], [0x05, 0x01, 0x00, 0x03, 11]);
+        assert_eq!(&request[5..16], b"example.com");
+        assert_eq!(request[16..], 7687_u16.to_be_bytes());
+        assert_eq!(&tunneled, b"bolt");
+    }
+
+    #[tokio::test]
+    async fn should_fail_when_socks5_proxy_refuses() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy = Proxy::socks5(listener.local_addr().unwrap().to_string());
+
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut greeting = [0; 3];
+            stream.read_exact(&mut greeting).await.unwrap();
+            stream.write_all(&[0x05, 0x00]).await.unwrap();
+            let mut request = [0; 10];
+            stream.read_exact(&mut request).await.unwrap();
+            stream
+                .write_all(&[0x05, 0x05, 0x00, 0x01, 0, 0, 0, 0, 0, 0])
+                .await
+                .unwrap();
+        });
+
+        let error = connect(&proxy, &host("10.0.0.1"), 7687).await.unwrap_err();
+
+        assert!(matches!(error, Error::ProxyError(e) if e.contains("connection refused")));
+    }
+
+    #[tokio::test]
+    async fn should_open_http_connect_tunnel() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy = Proxy::http_connect(listener.local_addr().unwrap().to_string())
+            .with_auth("user", "pass");
+
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            while !request.ends_with(b"\r\n\r\n") {
+                request.push(stream.read_u8().await.unwrap());
+            }
+            stream
+                .write_all(b"HTTP/1.1 200 Connection established\r\n\r\nbolt")
+                .await
+                .unwrap();
+            String::from_utf8(request).unwrap()
+        });
+
+        let mut stream = connect(&proxy, &host("[::1]"), 7687).await.unwrap();
+        let mut tunneled = [0; 4];
+        stream.read_exact(&mut tunneled).await.unwrap();
+        let request = server.await.unwrap();
+
+        assert_eq!(
+            request,
+            "CONNECT [::1]:7687 HTTP/1.1\r\n\
+             Host: [::1]:7687\r\n\
+             Proxy-Authorization: Basic dXNlcjpwYXNz\r\n\r\n"
+        );
+        assert_eq!(&tunneled, b"bolt");
+    }
+
+    #[tokio::test]
+    async fn should_fail_when_http_proxy_denies() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy = Proxy::http_connect(listener.local_addr().unwrap().to_string());
+
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            while !request.ends_with(b"\r\n\r\n") {
+                request.push(stream.read_u8().await.unwrap());
+            }
+            stream
+                .write_all(b"HTTP/1.1 407 Proxy Authentication Required\r\n\r\n")
+                .await
+                .unwrap();
+        });
+
+        let error = connect(&proxy, &host("example.com"), 7687)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, Error::ProxyError(e) if e.contains("407")));
+    }
+}
@@ -0,0 +1,138 @@
+use std::{
+    io,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+use bytes::{Buf, Bytes, BytesMut};
+use futures::{SinkExt as _, StreamExt as _};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio_tungstenite::{client_async, tungstenite::Message, WebSocketStream};
+
+use super::stream::ConnectionStream;
+use crate::errors::Result;
+
+/// Carries Bolt over WebSocket, inside of binary frames.
+///
+/// Everything that is written until a flush is sent as a single frame,
+/// which usually holds all the chunks of the messages that were written.
+#[derive(Debug)]
+pub(super) struct WebSocket {
+    inner: WebSocketStream<ConnectionStream>,
+    /// The part of the last received frame that was not read yet.
+    read: Bytes,
+    /// What was written since the last flush.
+    write: BytesMut,
+}
+
+impl WebSocket {
+    /// Runs the WebSocket opening handshake over the already connected stream.
+    pub(super) async fn connect(stream: ConnectionStream, uri: String) -> Result<Self> {
+        let (inner, _) = client_async(uri, stream).await.map_err(io::Error::other)?;
+        Ok(Self {
+            inner,
+            read: Bytes::new(),
+            write: BytesMut::new(),
+        })
+    }
+}
+
+impl AsyncRead for WebSocket {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        loop {
+            if !self.read.is_empty() {
+                let len = self.read.len().min(buf.remaining());
+                buf.put_slice(&self.read[..len]);
+                self.read.advance(len);
+                return Poll::Ready(Ok(()));
+            }
+            match ready!(self.inner.poll_next_unpin(cx)) {
+                Some(Ok(Message::Binary(data))) => self.read = data.into(),
+                // pings are answered by the WebSocket stream itself
+                Some(Ok(Message::Ping(_) | Message::Pong(_))) => {}
+                Some(Ok(Message::Close(_))) | None => return Poll::Ready(Ok(())),
+                Some(Ok(message)) => {
+                    return Poll::Ready(Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("expected a binary WebSocket frame, got {message:?}"),
+                    )))
+                }
+                Some(Err(e)) => return Poll::Ready(Err(io::Error::other(e))),
+            }
+        }
+    }
+}
+
+impl AsyncWrite for WebSocket {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.write.extend_from_slice(buf);
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        if !self.write.is_empty() {
+            ready!(self.inner.poll_ready_unpin(cx)).map_err(io::Error::other)?;
+            let frame = self.write.split().to_vec();
+            self.inner
+                .start_send_unpin(Message::Binary(frame))
+                .map_err(io::Error::other)?;
+        }
+        self.inner.poll_flush_unpin(cx).map_err(io::Error::other)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        ready!(self.as_mut().poll_flush(cx))?;
+        self.inner.poll_close_unpin(cx).map_err(io::Error::other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::{
+        io::{AsyncReadExt as _, AsyncWriteExt as _},
+        net::{TcpListener, TcpStream},
+    };
+    use tokio_tungstenite::accept_async;
+
+    #[tokio::test]
+    async fn should_send_flushed_bytes_in_one_binary_frame() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut server = accept_async(stream).await.unwrap();
+            let frame = server.next().await.unwrap().unwrap();
+            server
+                .send(Message::Binary(vec![0x00, 0x00, 0x04, 0x04]))
+                .await
+                .unwrap();
+            frame
+        });
+
+        let stream = TcpStream::connect(address).await.unwrap();
+        let mut client = WebSocket::connect(stream.into(), format!("ws://{address}"))
+            .await
+            .unwrap();
+        client.write_all(&[0x60, 0x60]).await.unwrap();
+        client.write_all(&[0xB0, 0x17]).await.unwrap();
+        client.flush().await.unwrap();
+
+        let mut response = [0; 4];
+        client.read_exact(&mut response).await.unwrap();
+
+        assert_eq!(response, [0x00, 0x00, 0x04, 0x04]);
+        assert_eq!(
+            server.await.unwrap(),
+            Message::Binary(vec![0x60, 0x60, 0xB0, 0x17])
+        );
+    }
+}
@@ -225,6 +225,17 @@ impl TryFrom<BoltType> for UnboundedRelation {
     }
 }
 
+impl TryFrom<BoltType> for BoltVector {
+    type Error = Error;
+
+    fn try_from(input: BoltType) -> Result<BoltVector> {
+        match input {
+            BoltType::Vector(v) => Ok(v),
+            _ => Err(Error::ConversionError),
+        }
+    }
+}
+
 impl TryFrom<BoltType> for BoltList {
     type Error = Error;
     fn try_from(input: BoltType) -> Result<BoltList> {
@@ -297,6 +308,12 @@ impl From<(chrono::NaiveDateTime, &str)> for BoltType {
     }
 }
 
+impl From<chrono::DateTime<chrono_tz::Tz>> for BoltType {
+    fn from(value: chrono::DateTime<chrono_tz::Tz>) -> Self {
+        BoltType::DateTimeZoneId(value.into())
+    }
+}
+
 impl<A: Into<BoltType> + Clone> From<Vec<A>> for BoltType {
     fn from(value: Vec<A>) -> BoltType {
         BoltType::List(BoltList {
@@ -328,6 +345,12 @@ where
     }
 }
 
+impl From<BoltVector> for BoltType {
+    fn from(value: BoltVector) -> Self {
+        BoltType::Vector(value)
+    }
+}
+
 impl From<Vec<u8>> for BoltType {
     fn from(value: Vec<u8>) -> Self {
         BoltType::Bytes(BoltBytes::new(value.into()))
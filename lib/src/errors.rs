@@ -1,6 +1,7 @@
 #[cfg(feature = "unstable-serde-packstream-format")]
 use crate::packstream::{de, ser};
 use crate::DeError;
+use serde::Deserialize;
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
@@ -33,6 +34,38 @@ pub enum Error {
     #[error("connection error")]
     ConnectionError,
 
+    /// The proxy configured with [`crate::ConfigBuilder::with_proxy`] failed to open a tunnel to the server.
+    #[error("the proxy could not connect to the server: {0}")]
+    ProxyError(String),
+
+    /// A step of establishing a connection to the server at the given address took longer
+    /// than configured with [`crate::ConfigBuilder::connect_timeout`]
+    /// or [`crate::ConfigBuilder::tls_handshake_timeout`].
+    #[error("{0} {1} did not complete within {2:?}")]
+    ConnectTimeout(ConnectStep, String, std::time::Duration),
+
+    /// None of the addresses of the server accepted the connection.
+    /// Contains the error of every address that was tried.
+    #[error("could not connect to any address of the server{}", display_address_errors(.0))]
+    ConnectFailed(Vec<(std::net::SocketAddr, std::io::Error)>),
+
+    /// The server did not send a response within the `connection.recv_timeout_seconds` hint.
+    /// The connection is broken afterwards and will not be returned to the pool.
+    #[error("the server did not respond within {0:?}")]
+    RecvTimeout(std::time::Duration),
+
+    /// Servers before Bolt 5.1 need a separate connection pool for every auth token
+    /// that is passed with a query or transaction. There can be up to `max_connections`
+    /// of them, and all of them have connections in use.
+    #[error(
+        "the limit of {0} connection pools for other users was reached and none of them is idle"
+    )]
+    UserPoolLimitReached(usize),
+
+    /// The [`crate::Graph`] was closed with [`crate::Graph::close`] and does not accept new work.
+    #[error("the graph has been closed")]
+    GraphClosed,
+
     #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
     #[error("The connection has been closed [{}]: {}", _0.code, _0.message)]
     ConnectionClosed(crate::bolt::Failure),
@@ -58,6 +91,9 @@ pub enum Error {
     #[error("invalid config")]
     InvalidConfig,
 
+    #[error("Invalid client certificate or private key: {0}")]
+    InvalidClientIdentity(String),
+
     #[error("Bolt Version {0}.{1} is not supported")]
     UnsupportedVersion(u8, u8),
 
@@ -79,6 +115,12 @@ pub enum Error {
     #[error("{0}")]
     UnknownType(String),
 
+    #[error("{0} values are not supported by Bolt {1}")]
+    UnsupportedType(&'static str, crate::Version),
+
+    #[error("{0} is not supported by Bolt {1}")]
+    UnsupportedFeature(&'static str, crate::Version),
+
     #[error("{0}")]
     UnknownMessage(String),
 
@@ -113,6 +155,41 @@ pub enum Error {
     ServerUnavailableError(String),
 }
 
+/// The step of establishing a connection that timed out, see [`Error::ConnectTimeout`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConnectStep {
+    /// Opening the socket, or the stream of a custom [`crate::Connector`].
+    Connect,
+    /// Opening the tunnel through the proxy configured with [`crate::ConfigBuilder::with_proxy`].
+    Proxy,
+    /// The TLS handshake.
+    TlsHandshake,
+    /// Agreeing on the Bolt protocol version with the server.
+    BoltHandshake,
+}
+
+impl std::fmt::Display for ConnectStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ConnectStep::Connect => "connecting to",
+            ConnectStep::Proxy => "connecting through the proxy to",
+            ConnectStep::TlsHandshake => "the TLS handshake with",
+            ConnectStep::BoltHandshake => "the Bolt handshake with",
+        })
+    }
+}
+
+fn display_address_errors(errors: &[(std::net::SocketAddr, std::io::Error)]) -> String {
+    if errors.is_empty() {
+        return ", the host name did not resolve to any address".to_owned();
+    }
+    errors
+        .iter()
+        .map(|(address, error)| format!("\n  {address}: {error}"))
+        .collect()
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Neo4jErrorKind {
     Client(Neo4jClientErrorKind),
@@ -141,6 +218,26 @@ pub enum Neo4jSecurityErrorKind {
     Unknown,
 }
 
+/// The GQL classification of an error, sent by servers since Bolt 5.7.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GqlErrorClassification {
+    ClientError,
+    DatabaseError,
+    TransientError,
+    Unknown,
+}
+
+impl GqlErrorClassification {
+    fn new(classification: &str) -> Self {
+        match classification {
+            "CLIENT_ERROR" => Self::ClientError,
+            "DATABASE_ERROR" => Self::DatabaseError,
+            "TRANSIENT_ERROR" => Self::TransientError,
+            _ => Self::Unknown,
+        }
+    }
+}
+
 impl Neo4jErrorKind {
     pub(crate) fn new(code: &str) -> Self {
         let code = Self::adjust_code(code).unwrap_or(code);
@@ -230,6 +327,7 @@ impl Neo4jErrorKind {
             kind: self,
             code,
             message,
+            gql: None,
         }
     }
 }
@@ -240,11 +338,101 @@ impl From<&str> for Neo4jErrorKind {
     }
 }
 
+/// The diagnostic record of a GQL error, sent by servers since Bolt 5.7.
+#[derive(Clone, Debug, PartialEq, Eq, Default, Deserialize)]
+pub struct GqlDiagnosticRecord {
+    #[serde(rename = "OPERATION")]
+    operation: Option<String>,
+    #[serde(rename = "OPERATION_CODE")]
+    operation_code: Option<String>,
+    #[serde(rename = "CURRENT_SCHEMA")]
+    current_schema: Option<String>,
+    #[serde(rename = "_classification")]
+    classification: Option<String>,
+}
+
+impl GqlDiagnosticRecord {
+    pub fn operation(&self) -> Option<&str> {
+        self.operation.as_deref()
+    }
+
+    pub fn operation_code(&self) -> Option<&str> {
+        self.operation_code.as_deref()
+    }
+
+    pub fn current_schema(&self) -> Option<&str> {
+        self.current_schema.as_deref()
+    }
+
+    pub fn classification(&self) -> Option<GqlErrorClassification> {
+        self.classification
+            .as_deref()
+            .map(GqlErrorClassification::new)
+    }
+}
+
+/// A GQL error, either the GQL data of a [`Neo4jError`] or one of its causes.
+#[derive(Clone, Debug, PartialEq, Eq, Default, Deserialize)]
+#[serde(default)]
+pub struct GqlError {
+    gql_status: String,
+    message: String,
+    description: String,
+    diagnostic_record: Option<GqlDiagnosticRecord>,
+    cause: Option<Box<GqlError>>,
+}
+
+impl GqlError {
+    #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+    pub(crate) fn new(
+        gql_status: String,
+        message: String,
+        description: String,
+        diagnostic_record: Option<GqlDiagnosticRecord>,
+        cause: Option<Box<GqlError>>,
+    ) -> Self {
+        Self {
+            gql_status,
+            message,
+            description,
+            diagnostic_record,
+            cause,
+        }
+    }
+
+    pub fn gql_status(&self) -> &str {
+        &self.gql_status
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn diagnostic_record(&self) -> Option<&GqlDiagnosticRecord> {
+        self.diagnostic_record.as_ref()
+    }
+
+    pub fn classification(&self) -> Option<GqlErrorClassification> {
+        self.diagnostic_record
+            .as_ref()
+            .and_then(GqlDiagnosticRecord::classification)
+    }
+
+    pub fn cause(&self) -> Option<&GqlError> {
+        self.cause.as_deref()
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Neo4jError {
     kind: Neo4jErrorKind,
     code: String,
     message: String,
+    gql: Option<Box<GqlError>>,
 }
 
 impl Neo4jError {
@@ -252,6 +440,16 @@ impl Neo4jError {
         Neo4jErrorKind::new(&code).new_error(code, message)
     }
 
+    /// Creates an error from a failure that carries GQL data, sent by servers since Bolt 5.7.
+    /// The kind, and with it whether the error can be retried, only depends on the Neo4j code.
+    pub(crate) fn with_gql(code: String, gql: GqlError) -> Self {
+        let kind = Neo4jErrorKind::new(&code);
+        let message = gql.message.clone();
+        let mut error = kind.new_error(code, message);
+        error.gql = Some(Box::new(gql));
+        error
+    }
+
     pub fn kind(&self) -> Neo4jErrorKind {
         self.kind
     }
@@ -264,6 +462,29 @@ impl Neo4jError {
         &self.message
     }
 
+    pub fn gql_status(&self) -> Option<&str> {
+        self.gql.as_ref().map(|gql| gql.gql_status())
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.gql.as_ref().map(|gql| gql.description())
+    }
+
+    pub fn diagnostic_record(&self) -> Option<&GqlDiagnosticRecord> {
+        self.gql.as_ref().and_then(|gql| gql.diagnostic_record())
+    }
+
+    /// The GQL classification as sent by the server.
+    /// It does not change the [`Neo4jError::kind`], which is derived from the Neo4j code.
+    pub fn classification(&self) -> Option<GqlErrorClassification> {
+        self.gql.as_ref().and_then(|gql| gql.classification())
+    }
+
+    /// The GQL error that caused this error, if any.
+    pub fn cause(&self) -> Option<&GqlError> {
+        self.gql.as_ref().and_then(|gql| gql.cause())
+    }
+
     pub(crate) fn can_retry(&self) -> bool {
         self.kind.can_retry()
     }
@@ -273,7 +494,65 @@ impl std::convert::From<deadpool::managed::PoolError<Error>> for Error {
     fn from(e: deadpool::managed::PoolError<Error>) -> Self {
         match e {
             deadpool::managed::PoolError::Backend(e) => e,
+            deadpool::managed::PoolError::Closed => Error::GraphClosed,
             _ => Error::ConnectionError,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classify_by_code() {
+        assert_eq!(
+            Neo4jErrorKind::new("Neo.TransientError.General.DatabaseUnavailable"),
+            Neo4jErrorKind::Transient
+        );
+        assert_eq!(
+            Neo4jErrorKind::new("Neo.TransientError.Transaction.Terminated"),
+            Neo4jErrorKind::Client(Neo4jClientErrorKind::TransactionTerminated)
+        );
+    }
+
+    #[test]
+    fn classify_by_code_regardless_of_gql_classification() {
+        let gql = |classification: &str| GqlError {
+            gql_status: "50N42".into(),
+            diagnostic_record: Some(GqlDiagnosticRecord {
+                classification: Some(classification.into()),
+                ..GqlDiagnosticRecord::default()
+            }),
+            ..GqlError::default()
+        };
+
+        let error = Neo4jError::with_gql(
+            "Neo.TransientError.Transaction.Terminated".into(),
+            gql("TRANSIENT_ERROR"),
+        );
+        assert_eq!(
+            error.kind(),
+            Neo4jErrorKind::Client(Neo4jClientErrorKind::TransactionTerminated)
+        );
+        assert_eq!(error.code(), "Neo.ClientError.Transaction.Terminated");
+        assert_eq!(
+            error.classification(),
+            Some(GqlErrorClassification::TransientError)
+        );
+        assert!(!error.kind().can_retry());
+
+        let error = Neo4jError::with_gql(
+            "Neo.ClientError.Statement.SyntaxError".into(),
+            gql("DATABASE_ERROR"),
+        );
+        assert_eq!(
+            error.kind(),
+            Neo4jErrorKind::Client(Neo4jClientErrorKind::Other)
+        );
+        assert_eq!(
+            error.classification(),
+            Some(GqlErrorClassification::DatabaseError)
+        );
+    }
+}
@@ -11,16 +11,26 @@ use crate::graph::ConnectionPoolManager::Direct;
 use crate::pool::ManagedConnection;
 use crate::RunResult;
 use crate::{
-    config::{Config, ConfigBuilder, Database, LiveConfig},
-    errors::Result,
-    pool::{create_pool, ConnectionPool},
+    auth::{Auth, AuthToken},
+    config::{Config, ConfigBuilder, Database, LiveConfig, NotificationFilter},
+    errors::{Neo4jClientErrorKind, Neo4jErrorKind, Neo4jSecurityErrorKind, Result},
+    pool::{close_pool, create_pool, get_connection, ConnectionPool},
     query::Query,
     stream::DetachedRowStream,
-    txn::Txn,
+    txn::{Txn, TxnOptions},
     Operation,
 };
 use backoff::{Error, ExponentialBackoff};
-use std::time::Duration;
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+/// How long [`Graph::close`] waits for connections that are still in use.
+const CLOSE_TIMEOUT: Duration = Duration::from_secs(30);
 
 #[derive(Clone)]
 enum ConnectionPoolManager {
@@ -31,11 +41,16 @@ enum ConnectionPoolManager {
 
 impl ConnectionPoolManager {
     #[allow(unused_variables)]
-    async fn get(&self, operation: Option<Operation>) -> Result<ManagedConnection> {
+    async fn get(
+        &self,
+        operation: Option<Operation>,
+        imp_user: Option<&str>,
+        auth: Option<&AuthToken>,
+    ) -> Result<ManagedConnection> {
         match self {
             #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
-            Routed(manager) => manager.get(operation).await,
-            Direct(pool) => pool.get().await.map_err(crate::Error::from),
+            Routed(manager) => manager.get(operation, imp_user, auth).await,
+            Direct(pool) => get_connection(pool, auth).await,
         }
     }
 
@@ -46,6 +61,14 @@ impl ConnectionPoolManager {
             Direct(pool) => pool.manager().backoff(),
         }
     }
+
+    async fn close(&self, deadline: Instant) {
+        match self {
+            #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+            Routed(manager) => manager.close(deadline).await,
+            Direct(pool) => close_pool(pool, deadline).await,
+        }
+    }
 }
 
 /// A neo4j database abstraction.
@@ -55,6 +78,7 @@ impl ConnectionPoolManager {
 pub struct Graph {
     config: LiveConfig,
     pool: ConnectionPoolManager,
+    closed: Arc<AtomicBool>,
 }
 
 /// Returns a [`Query`] which provides methods like [`Query::param`] to add parameters to the query
@@ -71,25 +95,28 @@ impl Graph {
         {
             let info = ConnectionInfo::new(
                 &config.uri,
-                &config.user,
-                &config.password,
+                &config.auth,
                 &config.tls_config,
+                &config.notifications,
+                &config.transport,
             )?;
             if matches!(info.routing, Routing::Yes(_)) {
                 debug!("Routing enabled, creating a routed connection manager");
                 let pool = Routed(RoutedConnectionManager::new(
                     &config,
-                    Box::new(ClusterRoutingTableProvider),
+                    Box::new(ClusterRoutingTableProvider::default()),
                 )?);
                 Ok(Graph {
                     config: config.into_live_config(),
                     pool,
+                    closed: Arc::default(),
                 })
             } else {
                 let pool = Direct(create_pool(&config)?);
                 Ok(Graph {
                     config: config.into_live_config(),
                     pool,
+                    closed: Arc::default(),
                 })
             }
         }
@@ -99,6 +126,7 @@ impl Graph {
             Ok(Graph {
                 config: config.into_live_config(),
                 pool,
+                closed: Arc::default(),
             })
         }
     }
@@ -148,10 +176,57 @@ impl Graph {
             .await
     }
 
-    #[allow(unused_variables)]
+    /// Starts a new transaction with the given options,
+    /// e.g. on another database, on behalf of another user, or with other credentials.
+    /// All queries that needs to be run/executed within the transaction
+    /// should be executed using either [`Txn::run`] or [`Txn::execute`]
+    ///
+    /// Transactions will not be automatically retried on any failure.
+    pub async fn start_txn_with(&self, options: TxnOptions) -> Result<Txn> {
+        let TxnOptions {
+            operation,
+            db,
+            imp_user,
+            auth,
+            notifications,
+        } = options;
+        self.impl_start_txn(
+            db.or_else(|| self.config.db.clone()),
+            imp_user,
+            auth.as_ref(),
+            operation.unwrap_or(Operation::Write),
+            notifications.as_ref(),
+        )
+        .await
+    }
+
     async fn impl_start_txn_on(&self, db: Option<Database>, operation: Operation) -> Result<Txn> {
-        let connection = self.pool.get(Some(operation.clone())).await?;
-        Txn::new(db, self.config.fetch_size, connection, operation).await
+        self.impl_start_txn(db, None, None, operation, None).await
+    }
+
+    #[allow(unused_variables)]
+    async fn impl_start_txn(
+        &self,
+        db: Option<Database>,
+        imp_user: Option<String>,
+        auth: Option<&AuthToken>,
+        operation: Operation,
+        notifications: Option<&NotificationFilter>,
+    ) -> Result<Txn> {
+        self.ensure_open()?;
+        let connection = self
+            .pool
+            .get(Some(operation.clone()), imp_user.as_deref(), auth)
+            .await?;
+        Txn::new(
+            db,
+            imp_user,
+            self.config.fetch_size,
+            connection,
+            operation,
+            notifications,
+        )
+        .await
     }
 
     /// Runs a query on the configured database using a connection from the connection pool,
@@ -204,6 +279,7 @@ impl Graph {
         q: Query,
         operation: Operation,
     ) -> Result<RunResult> {
+        self.ensure_open()?;
         backoff::future::retry_notify(
             self.pool.backoff(),
             || {
@@ -220,10 +296,17 @@ impl Graph {
                         Operation::Write => "w",
                     },
                 );
+                let auth = &self.config.auth;
                 async move {
-                    let mut connection =
-                        pool.get(Some(operation)).await.map_err(Error::Permanent)?; // an error when retrieving a connection is considered permanent
-                    query.run_retryable(&mut connection).await
+                    let mut connection = pool
+                        .get(Some(operation), query.imp_user(), query.auth())
+                        .await
+                        .map_err(Error::Permanent)?; // an error when retrieving a connection is considered permanent
+                    let token = Self::managed_token(&query, &connection);
+                    query
+                        .run_retryable(&mut connection)
+                        .await
+                        .map_err(|e| Self::retry_on_expired_token(auth, token.as_ref(), e))
                 }
             },
             Self::log_retry,
@@ -292,6 +375,7 @@ impl Graph {
         q: Query,
         operation: Operation,
     ) -> Result<DetachedRowStream> {
+        self.ensure_open()?;
         backoff::future::retry_notify(
             self.pool.backoff(),
             || {
@@ -310,9 +394,17 @@ impl Graph {
                         Operation::Write => "w",
                     },
                 );
+                let auth = &self.config.auth;
                 async move {
-                    let connection = pool.get(Some(operation)).await.map_err(Error::Permanent)?; // an error when retrieving a connection is considered permanent
-                    query.execute_retryable(fetch_size, connection).await
+                    let connection = pool
+                        .get(Some(operation), query.imp_user(), query.auth())
+                        .await
+                        .map_err(Error::Permanent)?; // an error when retrieving a connection is considered permanent
+                    let token = Self::managed_token(&query, &connection);
+                    query
+                        .execute_retryable(fetch_size, connection)
+                        .await
+                        .map_err(|e| Self::retry_on_expired_token(auth, token.as_ref(), e))
                 }
             },
             Self::log_retry,
@@ -320,6 +412,65 @@ impl Graph {
         .await
     }
 
+    /// Closes all connections of this graph, waiting up to 30 seconds for the ones that are in use.
+    ///
+    /// See [`Graph::close_with_timeout`] for details.
+    pub async fn close(&self) {
+        self.close_with_timeout(CLOSE_TIMEOUT).await
+    }
+
+    /// Closes all connections of this graph and all of its clones.
+    ///
+    /// Starting new transactions or queries fails with [`crate::Error::GraphClosed`] right away.
+    /// Connections that are in use, e.g. by a [`Txn`] or a [`DetachedRowStream`], are waited for until
+    /// the timeout passed. Every pooled connection is then closed with a GOODBYE message.
+    /// Connections that are given back after the timeout are closed without one.
+    /// With routing, the routing table stops being refreshed.
+    pub async fn close_with_timeout(&self, timeout: Duration) {
+        if self.closed.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        self.pool.close(Instant::now() + timeout).await
+    }
+
+    fn ensure_open(&self) -> Result<()> {
+        if self.closed.load(Ordering::Acquire) {
+            return Err(crate::Error::GraphClosed);
+        }
+        Ok(())
+    }
+
+    /// The token of the connection, unless it was passed with the query instead of coming from the config.
+    fn managed_token(query: &Query, connection: &ManagedConnection) -> Option<AuthToken> {
+        match query.auth() {
+            Some(_) => None,
+            None => connection.auth_token().cloned(),
+        }
+    }
+
+    /// Lets the token manager handle security errors.
+    /// Queries that failed because the token expired are retried once the manager has a fresh token,
+    /// pooled connections that still use the expired token are replaced or authenticate again when they are taken from the pool.
+    fn retry_on_expired_token(
+        auth: &Auth,
+        token: Option<&AuthToken>,
+        error: Error<crate::Error>,
+    ) -> Error<crate::Error> {
+        let (Error::Permanent(crate::Error::Neo4j(e)), Some(token)) = (&error, token) else {
+            return error;
+        };
+        let Neo4jErrorKind::Client(Neo4jClientErrorKind::Security(kind)) = e.kind() else {
+            return error;
+        };
+        let handled = auth.handle_security_error(token, e);
+        match error {
+            Error::Permanent(e) if handled && kind == Neo4jSecurityErrorKind::TokenExpired => {
+                Error::transient(e)
+            }
+            otherwise => otherwise,
+        }
+    }
+
     fn log_retry(e: crate::Error, delay: Duration) {
         let level = match delay.as_millis() {
             0..=499 => log::Level::Debug,
@@ -334,3 +485,62 @@ const _: () = {
     const fn assert_send_sync<T: ?Sized + Send + Sync>() {}
     assert_send_sync::<Graph>();
 };
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn should_reject_work_after_close() {
+        let graph = Graph::new("bolt://localhost:7687", "user", "pass").unwrap();
+        let clone = graph.clone();
+
+        graph.close().await;
+
+        assert!(matches!(
+            clone.run(query("RETURN 1")).await,
+            Err(crate::Error::GraphClosed)
+        ));
+        assert!(matches!(
+            clone.start_txn().await,
+            Err(crate::Error::GraphClosed)
+        ));
+    }
+
+    struct Renewing;
+
+    impl crate::AuthTokenManager for Renewing {
+        fn get_token(&self) -> crate::AuthTokenFuture<'_> {
+            Box::pin(async { Ok(AuthToken::bearer("token")) })
+        }
+
+        fn handle_security_error(&self, _: &AuthToken, _: &crate::Neo4jError) -> bool {
+            true
+        }
+    }
+
+    fn security_error(code: &str) -> Error<crate::Error> {
+        Error::permanent(crate::Error::Neo4j(crate::Neo4jError::new(
+            code.into(),
+            "security error".into(),
+        )))
+    }
+
+    #[test]
+    fn should_retry_expired_tokens_with_a_manager() {
+        let managed = Auth::Manager(Arc::new(Renewing));
+        let fixed = Auth::from(AuthToken::bearer("token"));
+        let token = AuthToken::bearer("token");
+        let expired = "Neo.ClientError.Security.TokenExpired";
+
+        let error = Graph::retry_on_expired_token(&managed, Some(&token), security_error(expired));
+        assert!(matches!(error, Error::Transient { .. }));
+
+        let error = Graph::retry_on_expired_token(&fixed, Some(&token), security_error(expired));
+        assert!(matches!(error, Error::Permanent(_)));
+
+        let unauthorized = security_error("Neo.ClientError.Security.Unauthorized");
+        let error = Graph::retry_on_expired_token(&managed, Some(&token), unauthorized);
+        assert!(matches!(error, Error::Permanent(_)));
+    }
+}
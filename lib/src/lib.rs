@@ -461,6 +461,7 @@ mod messages;
 mod packstream;
 mod pool;
 mod query;
+mod resolver;
 #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
 mod routing;
 mod row;
@@ -471,28 +472,38 @@ mod txn;
 mod types;
 mod version;
 
-pub use crate::auth::ClientCertificate;
-pub use crate::config::{Config, ConfigBuilder, Database};
+pub use crate::auth::{
+    AuthToken, AuthTokenFuture, AuthTokenManager, ClientCertificate, ClientIdentity,
+};
+pub use crate::config::{
+    Config, ConfigBuilder, Database, NotificationCategory, NotificationFilter,
+    NotificationMinimumSeverity, Proxy,
+};
+pub use crate::connection::{ConnectFuture, Connector, Transport};
 pub use crate::errors::{
-    Error, Neo4jClientErrorKind, Neo4jError, Neo4jErrorKind, Neo4jSecurityErrorKind, Result,
+    ConnectStep, Error, GqlDiagnosticRecord, GqlError, GqlErrorClassification,
+    Neo4jClientErrorKind, Neo4jError, Neo4jErrorKind, Neo4jSecurityErrorKind, Result,
 };
 pub use crate::graph::{query, Graph};
 pub use crate::query::{Query, QueryParameter, RunResult};
+pub use crate::resolver::{ResolveFuture, ServerAddress, ServerAddressResolver};
 pub use crate::row::{Node, Path, Point2D, Point3D, Relation, Row, UnboundedRelation};
 pub use crate::stream::{DetachedRowStream, RowStream};
-pub use crate::txn::Txn;
+pub use crate::txn::{Txn, TxnOptions};
 pub use crate::types::serde::{
-    DeError, EndNodeId, Id, Indices, Keys, Labels, Nodes, Offset, Relationships, StartNodeId,
-    Timezone, Type,
+    DeError, ElementId, EndNodeElementId, EndNodeId, Id, Indices, Keys, Labels, Nodes, Offset,
+    Relationships, StartNodeElementId, StartNodeId, Timezone, Type,
 };
 pub use crate::types::{
     BoltBoolean, BoltBytes, BoltDate, BoltDateTime, BoltDateTimeZoneId, BoltDuration, BoltFloat,
     BoltInteger, BoltList, BoltLocalDateTime, BoltLocalTime, BoltMap, BoltNode, BoltNull, BoltPath,
     BoltPoint2D, BoltPoint3D, BoltRelation, BoltString, BoltTime, BoltType, BoltUnboundedRelation,
+    BoltVector,
 };
 pub use crate::version::Version;
 use std::fmt::Display;
 
+#[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
 pub(crate) use messages::Success;
 
 #[derive(Debug, PartialEq, Clone)]
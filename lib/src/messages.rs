@@ -5,17 +5,22 @@ mod discard;
 mod failure;
 mod hello;
 mod ignore;
+mod logoff;
+mod logon;
 mod pull;
 mod record;
 mod reset;
 mod rollback;
 mod run;
 mod success;
+mod telemetry;
 
 use crate::{
+    auth::AuthToken,
+    config::NotificationFilter,
     errors::{Error, Result},
     types::{BoltMap, BoltWireFormat},
-    version::Version,
+    version::{Encoding, Version, BOLT_AGENT},
     BoltString, BoltType,
 };
 use begin::Begin;
@@ -25,6 +30,7 @@ use ignore::Ignore;
 use record::Record;
 use run::Run;
 pub(crate) use success::Success;
+pub(crate) use telemetry::TelemetryApi;
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum BoltResponse {
@@ -48,11 +54,21 @@ pub enum BoltRequest {
         deprecated(since = "0.9.0", note = "Use `crate::bolt::Pull` instead.")
     )]
     Pull(pull::Pull),
+    #[cfg_attr(
+        feature = "unstable-bolt-protocol-impl-v2",
+        deprecated(since = "0.9.0", note = "Use `crate::bolt::PullAll` instead.")
+    )]
+    PullAll(pull::PullAll),
     #[cfg_attr(
         feature = "unstable-bolt-protocol-impl-v2",
         deprecated(since = "0.9.0", note = "Use `crate::bolt::Discard` instead.")
     )]
     Discard(discard::Discard),
+    #[cfg_attr(
+        feature = "unstable-bolt-protocol-impl-v2",
+        deprecated(since = "0.9.0", note = "Use `crate::bolt::DiscardAll` instead.")
+    )]
+    DiscardAll(discard::DiscardAll),
     Begin(Begin),
     #[cfg_attr(
         feature = "unstable-bolt-protocol-impl-v2",
@@ -69,24 +85,32 @@ pub enum BoltRequest {
         deprecated(since = "0.9.0", note = "Use `crate::bolt::Reset` instead.")
     )]
     Reset(reset::Reset),
+    #[cfg_attr(
+        feature = "unstable-bolt-protocol-impl-v2",
+        deprecated(since = "0.9.0", note = "Use `crate::bolt::Goodbye` instead.")
+    )]
+    Goodbye(bye::Bye),
+    Logon(logon::Logon),
+    Logoff(logoff::Logoff),
+    Telemetry(telemetry::Telemetry),
 }
 
 #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
 pub struct HelloBuilder {
     agent: BoltString,
-    principal: BoltString,
-    credentials: BoltString,
+    auth: AuthToken,
     routing: Option<BoltMap>,
+    notifications: NotificationFilter,
 }
 
 #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
 impl HelloBuilder {
-    pub fn new(principal: impl Into<BoltString>, credentials: impl Into<BoltString>) -> Self {
+    pub fn new(auth: AuthToken) -> Self {
         Self {
             agent: "neo4rs".into(),
-            principal: principal.into(),
-            credentials: credentials.into(),
+            auth,
             routing: None,
+            notifications: NotificationFilter::default(),
         }
     }
 
@@ -97,15 +121,22 @@ impl HelloBuilder {
         }
     }
 
+    pub fn with_notification_filter(self, notifications: NotificationFilter) -> Self {
+        Self {
+            notifications,
+            ..self
+        }
+    }
+
     #[cfg_attr(feature = "unstable-bolt-protocol-impl-v2", allow(deprecated))]
     pub fn build(self, version: Version) -> BoltRequest {
         let HelloBuilder {
             agent,
-            principal,
-            credentials,
+            auth,
             routing,
+            notifications,
         } = self;
-        BoltRequest::hello(agent, principal, credentials, routing, version)
+        BoltRequest::hello(agent, &auth, routing, &notifications, version)
     }
 }
 
@@ -117,34 +148,78 @@ impl BoltRequest {
     )]
     pub fn hello(
         agent: BoltString,
-        principal: BoltString,
-        credentials: BoltString,
+        auth: &AuthToken,
         routing: Option<BoltMap>,
+        notifications: &NotificationFilter,
         version: Version,
     ) -> BoltRequest {
         let mut data = BoltMap::default();
         data.put("user_agent".into(), BoltType::String(agent));
-        data.put("scheme".into(), "basic".into());
-        data.put("principal".into(), BoltType::String(principal));
-        data.put("credentials".into(), BoltType::String(credentials));
+        // Starting with 5.1, the credentials are sent with a separate LOGON message
+        if version < Version::V5_1 {
+            auth.write_into(&mut data);
+        }
         if version >= Version::V4_1 {
             if let Some(context) = routing {
                 data.put("routing".into(), BoltType::Map(context));
             }
         }
+        if version == Version::V4_4 {
+            data.put("patch_bolt".into(), vec!["utc"].into());
+        }
+        if version >= Version::V5_2 {
+            notifications.write_into(version, &mut data);
+        }
+        if version >= Version::V5_3 {
+            let mut agent = BoltMap::default();
+            agent.put("product".into(), BOLT_AGENT.into());
+            data.put("bolt_agent".into(), BoltType::Map(agent));
+        }
         BoltRequest::Hello(hello::Hello::new(data))
     }
 
-    pub fn run(query: &str, params: BoltMap, extra: BoltMap) -> BoltRequest {
-        BoltRequest::Run(Run::new(query.into(), params, extra))
+    pub fn logon(auth: &AuthToken) -> BoltRequest {
+        let mut data = BoltMap::default();
+        auth.write_into(&mut data);
+        BoltRequest::Logon(logon::Logon::new(data))
+    }
+
+    #[cfg_attr(feature = "unstable-bolt-protocol-impl-v2", allow(dead_code))]
+    pub fn logoff() -> BoltRequest {
+        BoltRequest::Logoff(logoff::Logoff)
+    }
+
+    #[cfg_attr(feature = "unstable-bolt-protocol-impl-v2", allow(dead_code))]
+    pub(crate) fn telemetry(api: TelemetryApi) -> BoltRequest {
+        BoltRequest::Telemetry(telemetry::Telemetry::new(api))
     }
 
+    pub fn run(
+        query: &str,
+        params: BoltMap,
+        extra: BoltMap,
+        version: Version,
+    ) -> Result<BoltRequest> {
+        if version < Version::V4 && extra.value.contains_key("db") {
+            return Err(Error::UnsupportedFeature("Selecting a database", version));
+        }
+        if version < Version::V4_4 && extra.value.contains_key("imp_user") {
+            return Err(Error::UnsupportedFeature("Impersonation", version));
+        }
+        Ok(BoltRequest::Run(Run::new(query.into(), params, extra)))
+    }
+
+    /// Bolt 3 has no query ids and can only pull all records at once.
     #[cfg_attr(
         feature = "unstable-bolt-protocol-impl-v2",
         deprecated(since = "0.9.0", note = "Use `crate::bolt::Pull` instead.")
     )]
-    pub fn pull(n: usize, qid: i64) -> BoltRequest {
-        BoltRequest::Pull(pull::Pull::new(n as i64, qid))
+    pub fn pull(n: usize, qid: i64, version: Version) -> BoltRequest {
+        if version < Version::V4 {
+            BoltRequest::PullAll(pull::PullAll)
+        } else {
+            BoltRequest::Pull(pull::Pull::new(n as i64, qid))
+        }
     }
 
     #[cfg_attr(
@@ -159,14 +234,35 @@ impl BoltRequest {
         feature = "unstable-bolt-protocol-impl-v2",
         deprecated(since = "0.9.0", note = "Use `crate::bolt::Discard` instead.")
     )]
-    pub fn discard_all_for(query_id: i64) -> BoltRequest {
-        BoltRequest::Discard(discard::Discard::new(-1, query_id))
+    pub fn discard_all_for(query_id: i64, version: Version) -> BoltRequest {
+        if version < Version::V4 {
+            BoltRequest::DiscardAll(discard::DiscardAll)
+        } else {
+            BoltRequest::Discard(discard::Discard::new(-1, query_id))
+        }
     }
 
-    pub fn begin(db: Option<&str>) -> BoltRequest {
-        let extra = db.into_iter().map(|db| ("db".into(), db.into())).collect();
+    pub fn begin(
+        db: Option<&str>,
+        imp_user: Option<&str>,
+        notifications: Option<&NotificationFilter>,
+        version: Version,
+    ) -> Result<BoltRequest> {
+        if version < Version::V4 && db.is_some() {
+            return Err(Error::UnsupportedFeature("Selecting a database", version));
+        }
+        if version < Version::V4_4 && imp_user.is_some() {
+            return Err(Error::UnsupportedFeature("Impersonation", version));
+        }
+        let mut extra: BoltMap = db.into_iter().map(|db| ("db".into(), db.into())).collect();
+        if let Some(imp_user) = imp_user {
+            extra.put("imp_user".into(), imp_user.into());
+        }
+        if let Some(notifications) = notifications {
+            notifications.write_into(version, &mut extra);
+        }
         let begin = Begin::new(extra);
-        BoltRequest::Begin(begin)
+        Ok(BoltRequest::Begin(begin))
     }
 
     #[cfg_attr(
@@ -192,20 +288,35 @@ impl BoltRequest {
     pub fn reset() -> BoltRequest {
         BoltRequest::Reset(reset::Reset::new())
     }
+
+    #[cfg_attr(
+        feature = "unstable-bolt-protocol-impl-v2",
+        deprecated(since = "0.9.0", note = "Use `crate::bolt::Goodbye` instead.")
+    )]
+    pub fn goodbye() -> BoltRequest {
+        BoltRequest::Goodbye(bye::Bye)
+    }
 }
 
 impl BoltRequest {
     #[cfg_attr(feature = "unstable-bolt-protocol-impl-v2", allow(deprecated))]
-    pub fn into_bytes(self, version: Version) -> Result<Bytes> {
+    pub(crate) fn into_bytes(self, encoding: impl Into<Encoding>) -> Result<Bytes> {
+        let encoding = encoding.into();
         let bytes: Bytes = match self {
-            BoltRequest::Hello(hello) => hello.into_bytes(version)?,
-            BoltRequest::Run(run) => run.into_bytes(version)?,
-            BoltRequest::Pull(pull) => pull.into_bytes(version)?,
-            BoltRequest::Discard(discard) => discard.into_bytes(version)?,
-            BoltRequest::Begin(begin) => begin.into_bytes(version)?,
-            BoltRequest::Commit(commit) => commit.into_bytes(version)?,
-            BoltRequest::Rollback(rollback) => rollback.into_bytes(version)?,
-            BoltRequest::Reset(reset) => reset.into_bytes(version)?,
+            BoltRequest::Hello(hello) => hello.into_bytes(encoding)?,
+            BoltRequest::Run(run) => run.into_bytes(encoding)?,
+            BoltRequest::Pull(pull) => pull.into_bytes(encoding)?,
+            BoltRequest::PullAll(pull_all) => pull_all.into_bytes(encoding)?,
+            BoltRequest::Discard(discard) => discard.into_bytes(encoding)?,
+            BoltRequest::DiscardAll(discard_all) => discard_all.into_bytes(encoding)?,
+            BoltRequest::Begin(begin) => begin.into_bytes(encoding)?,
+            BoltRequest::Commit(commit) => commit.into_bytes(encoding)?,
+            BoltRequest::Rollback(rollback) => rollback.into_bytes(encoding)?,
+            BoltRequest::Reset(reset) => reset.into_bytes(encoding)?,
+            BoltRequest::Goodbye(goodbye) => goodbye.into_bytes(encoding)?,
+            BoltRequest::Logon(logon) => logon.into_bytes(encoding)?,
+            BoltRequest::Logoff(logoff) => logoff.into_bytes(encoding)?,
+            BoltRequest::Telemetry(telemetry) => telemetry.into_bytes(encoding)?,
         };
         Ok(bytes)
     }
@@ -247,3 +358,91 @@ impl BoltResponse {
         }
     }
 }
+
+#[cfg(test)]
+#[cfg_attr(feature = "unstable-bolt-protocol-impl-v2", allow(deprecated))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_pull_all_on_bolt_3() {
+        assert!(matches!(
+            BoltRequest::pull(42, 1, Version::V3),
+            BoltRequest::PullAll(_)
+        ));
+        assert!(matches!(
+            BoltRequest::pull(42, 1, Version::V4),
+            BoltRequest::Pull(_)
+        ));
+        assert!(matches!(
+            BoltRequest::discard_all_for(1, Version::V3),
+            BoltRequest::DiscardAll(_)
+        ));
+        assert!(matches!(
+            BoltRequest::discard_all_for(1, Version::V4),
+            BoltRequest::Discard(_)
+        ));
+    }
+
+    #[test]
+    fn should_reject_database_on_bolt_3() {
+        let extra: BoltMap = [("db".into(), "neo4j".into())].into_iter().collect();
+
+        assert!(matches!(
+            BoltRequest::run("RETURN 1", BoltMap::default(), extra.clone(), Version::V3),
+            Err(Error::UnsupportedFeature(_, Version::V3))
+        ));
+        assert!(BoltRequest::run("RETURN 1", BoltMap::default(), extra, Version::V4).is_ok());
+        assert!(matches!(
+            BoltRequest::begin(Some("neo4j"), None, None, Version::V3),
+            Err(Error::UnsupportedFeature(_, Version::V3))
+        ));
+        assert!(BoltRequest::begin(None, None, None, Version::V3).is_ok());
+    }
+
+    #[test]
+    fn should_reject_impersonation_before_4_4() {
+        let extra: BoltMap = [("imp_user".into(), "alice".into())].into_iter().collect();
+
+        assert!(matches!(
+            BoltRequest::run("RETURN 1", BoltMap::default(), extra.clone(), Version::V4_3),
+            Err(Error::UnsupportedFeature("Impersonation", Version::V4_3))
+        ));
+        assert!(BoltRequest::run("RETURN 1", BoltMap::default(), extra, Version::V4_4).is_ok());
+        assert!(matches!(
+            BoltRequest::begin(None, Some("alice"), None, Version::V4_3),
+            Err(Error::UnsupportedFeature("Impersonation", Version::V4_3))
+        ));
+        assert!(BoltRequest::begin(None, Some("alice"), None, Version::V4_4).is_ok());
+    }
+
+    #[test]
+    fn should_send_auth_token() {
+        let auth = AuthToken::bearer("token");
+        let expected: BoltMap = [
+            ("scheme".into(), "bearer".into()),
+            ("credentials".into(), "token".into()),
+        ]
+        .into_iter()
+        .collect();
+
+        match BoltRequest::logon(&auth) {
+            BoltRequest::Logon(logon) => assert_eq!(logon, logon::Logon::new(expected.clone())),
+            otherwise => panic!("unexpected request: {otherwise:?}"),
+        }
+
+        let mut expected = expected;
+        expected.put("user_agent".into(), "neo4rs".into());
+        let request = BoltRequest::hello(
+            "neo4rs".into(),
+            &auth,
+            None,
+            &NotificationFilter::default(),
+            Version::V4_1,
+        );
+        match request {
+            BoltRequest::Hello(hello) => assert_eq!(hello, hello::Hello::new(expected)),
+            otherwise => panic!("unexpected request: {otherwise:?}"),
+        }
+    }
+}
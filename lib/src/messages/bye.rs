@@ -6,7 +6,7 @@ use neo4rs_macros::BoltStruct;
 #[signature(0xB0, 0x02)]
 #[cfg_attr(
     feature = "unstable-bolt-protocol-impl-v2",
-    deprecated(since = "0.9.0", note = "Use `crate::bolt::Bye` instead.")
+    deprecated(since = "0.9.0", note = "Use `crate::bolt::Goodbye` instead.")
 )]
 pub struct Bye;
 
@@ -13,6 +13,15 @@ pub struct Discard {
     extra: BoltMap,
 }
 
+// DISCARD_ALL from Bolt 3, which always discards all records of the last query.
+#[derive(Debug, PartialEq, Eq, Clone, BoltStruct)]
+#[signature(0xB0, 0x2F)]
+#[cfg_attr(
+    feature = "unstable-bolt-protocol-impl-v2",
+    deprecated(since = "0.9.0", note = "Use `crate::bolt::DiscardAll` instead.")
+)]
+pub struct DiscardAll;
+
 #[cfg_attr(feature = "unstable-bolt-protocol-impl-v2", allow(dead_code))]
 impl Default for Discard {
     fn default() -> Self {
@@ -59,4 +68,11 @@ mod tests {
         assert_eq!(extra.get::<i64>("n").unwrap(), 255);
         assert_eq!(extra.get::<i64>("qid").unwrap(), 255);
     }
+
+    #[test]
+    fn should_serialize_discard_all() {
+        let bytes: Bytes = DiscardAll.into_bytes(Version::V3).unwrap();
+
+        assert_eq!(bytes, Bytes::from_static(&[0xB0, 0x2F]));
+    }
 }
@@ -1,7 +1,6 @@
 use crate::{
-    errors::Neo4jError,
+    errors::{GqlError, Neo4jError},
     types::{serde::DeError, BoltMap},
-    BoltType,
 };
 use ::serde::Deserialize;
 use neo4rs_macros::BoltStruct;
@@ -21,12 +20,16 @@ impl Failure {
     }
 
     pub(crate) fn into_error(self) -> Neo4jError {
-        let mut meta = self.metadata.value;
-        let (code, message) = (meta.remove("code"), meta.remove("message"));
-        let (code, message) = match (code, message) {
-            (Some(BoltType::String(s)), Some(BoltType::String(m))) => (s.value, m.value),
-            _ => (String::new(), String::new()),
-        };
+        let code = self
+            .get::<String>("neo4j_code")
+            .or_else(|_| self.get::<String>("code"))
+            .unwrap_or_default();
+        if self.metadata.value.contains_key("gql_status") {
+            if let Ok(gql) = self.metadata.to::<GqlError>() {
+                return Neo4jError::with_gql(code, gql);
+            }
+        }
+        let message = self.get::<String>("message").unwrap_or_default();
         Neo4jError::new(code, message)
     }
 }
@@ -36,6 +39,7 @@ mod tests {
     use super::*;
     use crate::types::BoltWireFormat;
     use crate::version::Version;
+    use crate::BoltType;
     use bytes::Bytes;
 
     #[test]
@@ -61,4 +65,65 @@ mod tests {
             "The client is unauthorized due to authentication failure."
         );
     }
+
+    #[test]
+    fn should_convert_gql_failure() {
+        let mut cause = BoltMap::new();
+        cause.put("gql_status".into(), "22N62".into());
+        cause.put("message".into(), "Invalid input.".into());
+        cause.put("description".into(), "error: data exception".into());
+
+        let mut record = BoltMap::new();
+        record.put("_classification".into(), "CLIENT_ERROR".into());
+
+        let mut metadata = BoltMap::new();
+        metadata.put(
+            "neo4j_code".into(),
+            "Neo.ClientError.Statement.SyntaxError".into(),
+        );
+        metadata.put("gql_status".into(), "42001".into());
+        metadata.put("message".into(), "Invalid syntax.".into());
+        metadata.put(
+            "description".into(),
+            "error: syntax error or access rule violation".into(),
+        );
+        metadata.put("diagnostic_record".into(), BoltType::Map(record));
+        metadata.put("cause".into(), BoltType::Map(cause));
+
+        let failure = Failure { metadata }.into_error();
+
+        assert_eq!(failure.code(), "Neo.ClientError.Statement.SyntaxError");
+        assert_eq!(failure.message(), "Invalid syntax.");
+        assert_eq!(failure.gql_status(), Some("42001"));
+        assert_eq!(
+            failure.classification(),
+            Some(crate::errors::GqlErrorClassification::ClientError)
+        );
+
+        let cause = failure.cause().unwrap();
+        assert_eq!(cause.gql_status(), "22N62");
+        assert_eq!(cause.message(), "Invalid input.");
+        assert!(cause.cause().is_none());
+    }
+
+    #[test]
+    fn should_fall_back_to_the_classic_code() {
+        let mut metadata = BoltMap::new();
+        metadata.put(
+            "code".into(),
+            "Neo.TransientError.General.DatabaseUnavailable".into(),
+        );
+        metadata.put("gql_status".into(), "50N00".into());
+        metadata.put("message".into(), "Database unavailable.".into());
+
+        let failure = Failure { metadata }.into_error();
+
+        assert_eq!(
+            failure.code(),
+            "Neo.TransientError.General.DatabaseUnavailable"
+        );
+        assert_eq!(failure.message(), "Database unavailable.");
+        assert_eq!(failure.kind(), crate::errors::Neo4jErrorKind::Transient);
+        assert_eq!(failure.gql_status(), Some("50N00"));
+    }
 }
@@ -13,6 +13,15 @@ pub struct Pull {
     extra: BoltMap,
 }
 
+// PULL_ALL from Bolt 3, which always streams all records of the last query.
+#[derive(Debug, PartialEq, Eq, Clone, BoltStruct)]
+#[signature(0xB0, 0x3F)]
+#[cfg_attr(
+    feature = "unstable-bolt-protocol-impl-v2",
+    deprecated(since = "0.9.0", note = "Use `crate::bolt::PullAll` instead.")
+)]
+pub struct PullAll;
+
 impl Default for Pull {
     fn default() -> Self {
         Pull::new(-1, -1)
@@ -59,4 +68,11 @@ mod tests {
         assert_eq!(extra.get::<i64>("n").unwrap(), 255);
         assert_eq!(extra.get::<i64>("qid").unwrap(), 255);
     }
+
+    #[test]
+    fn should_serialize_pull_all() {
+        let bytes: Bytes = PullAll.into_bytes(Version::V3).unwrap();
+
+        assert_eq!(bytes, Bytes::from_static(&[0xB0, 0x3F]));
+    }
 }
@@ -0,0 +1,48 @@
+use crate::types::BoltInteger;
+use neo4rs_macros::BoltStruct;
+
+/// The driver API that a query was sent from, reported with a TELEMETRY message.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum TelemetryApi {
+    /// A transaction function that is retried by the driver.
+    #[allow(dead_code)]
+    ManagedTransaction = 0,
+    /// A transaction started with [`crate::Graph::start_txn`].
+    UnmanagedTransaction = 1,
+    /// An auto-commit query, e.g. [`crate::Graph::run`] or [`crate::Graph::execute`].
+    AutoCommit = 2,
+    /// A query sent through a driver-level execute query API.
+    #[allow(dead_code)]
+    ExecuteQuery = 3,
+}
+
+#[derive(Debug, PartialEq, Clone, BoltStruct)]
+#[signature(0xB1, 0x54)]
+pub struct Telemetry {
+    api: BoltInteger,
+}
+
+impl Telemetry {
+    #[cfg_attr(feature = "unstable-bolt-protocol-impl-v2", allow(dead_code))]
+    pub(crate) fn new(api: TelemetryApi) -> Telemetry {
+        Telemetry {
+            api: (api as i64).into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{types::BoltWireFormat, version::Version};
+    use bytes::*;
+
+    #[test]
+    fn should_serialize_telemetry() {
+        let telemetry = Telemetry::new(TelemetryApi::AutoCommit);
+
+        let bytes: Bytes = telemetry.into_bytes(Version::V5_4).unwrap();
+
+        assert_eq!(bytes, Bytes::from_static(&[0xB1, 0x54, 0x02]));
+    }
+}
@@ -58,9 +58,32 @@ impl<'de> Deserialize<'de> for Data {
     }
 }
 
+/// The name of the newtype struct that marks already encoded packstream data.
+const RAW_BYTES: &str = "__neo4rs::RawBytes";
+
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub(crate) struct RawBytes(pub(crate) Bytes);
 
+impl serde::Serialize for RawBytes {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        struct AsBytes<'a>(&'a [u8]);
+
+        impl serde::Serialize for AsBytes<'_> {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                serializer.serialize_bytes(self.0)
+            }
+        }
+
+        serializer.serialize_newtype_struct(RAW_BYTES, &AsBytes(&self.0))
+    }
+}
+
 impl<'de> Deserialize<'de> for RawBytes {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -101,7 +124,7 @@ impl<'de> Deserialize<'de> for RawBytes {
         }
 
         deserializer
-            .deserialize_newtype_struct("__neo4rs::RawBytes", RawBytesVisitor)
+            .deserialize_newtype_struct(RAW_BYTES, RawBytesVisitor)
             .map(Self)
     }
 }
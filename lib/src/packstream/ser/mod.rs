@@ -7,6 +7,7 @@ mod map;
 
 pub(super) struct Serializer {
     bytes: BytesMut,
+    raw: bool,
 }
 
 impl Serializer {
@@ -15,7 +16,7 @@ impl Serializer {
     }
 
     pub(super) fn new(bytes: BytesMut) -> Self {
-        Self { bytes }
+        Self { bytes, raw: false }
     }
 
     pub(super) fn end(self) -> Bytes {
@@ -170,6 +171,11 @@ impl<'a> ser::Serializer for &'a mut Serializer {
     }
 
     fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        if self.raw {
+            self.bytes.extend_from_slice(v);
+            return Ok(());
+        }
+
         let len = v.len();
         match len {
             0..=255 => {
@@ -226,12 +232,19 @@ impl<'a> ser::Serializer for &'a mut Serializer {
 
     fn serialize_newtype_struct<T>(
         self,
-        _name: &'static str,
+        name: &'static str,
         value: &T,
     ) -> Result<Self::Ok, Self::Error>
     where
         T: ser::Serialize + ?Sized,
     {
+        if name == super::RAW_BYTES {
+            // already encoded packstream data, written as is
+            self.raw = true;
+            let result = value.serialize(&mut *self);
+            self.raw = false;
+            return result;
+        }
         value.serialize(self)
     }
 
@@ -1,66 +1,285 @@
-use std::time::Duration;
+use std::{
+    collections::HashMap,
+    mem,
+    ops::{Deref, DerefMut},
+    sync::{Arc, Mutex, OnceLock},
+    time::{Duration, Instant},
+};
 
-use crate::auth::ConnectionTLSConfig;
+use crate::auth::{Auth, AuthToken, ConnectionTLSConfig};
 use crate::{
-    config::Config,
+    config::{Config, NotificationFilter, TransportConfig},
     connection::{Connection, ConnectionInfo},
     errors::{Error, Result},
 };
 use backoff::{ExponentialBackoff, ExponentialBackoffBuilder};
-use deadpool::managed::{Manager, Metrics, Object, Pool, RecycleResult};
-use log::{info, trace};
+use deadpool::managed::{Manager, Metrics, Object, Pool, RecycleError, RecycleResult};
+use futures::future::join_all;
+use log::{debug, info, trace};
+use tokio::sync::{Notify, OwnedSemaphorePermit, Semaphore};
+
+pub type ConnectionPool = Pool<ConnectionManager, ManagedConnection>;
+
+/// A connection in the pool, which counts against the limit of connections
+/// shared by the pool and the pools of other users.
+pub struct PooledConnection {
+    connection: Connection,
+    _permit: OwnedSemaphorePermit,
+}
+
+/// A connection taken from the pool, which is given back when it is dropped.
+pub struct ManagedConnection {
+    connection: Object<ConnectionManager>,
+    // dropped after the connection, so that it is back in the pool when the waiters wake up
+    _returned: Returned,
+}
+
+struct Returned(Option<Arc<Notify>>);
+
+impl Drop for Returned {
+    fn drop(&mut self) {
+        if let Some(returned) = &self.0 {
+            returned.notify_waiters();
+        }
+    }
+}
+
+impl From<Object<ConnectionManager>> for ManagedConnection {
+    fn from(connection: Object<ConnectionManager>) -> Self {
+        let returned = Object::pool(&connection).map(|pool| pool.manager().returned.clone());
+        ManagedConnection {
+            connection,
+            _returned: Returned(returned),
+        }
+    }
+}
+
+impl Deref for ManagedConnection {
+    type Target = Connection;
+
+    fn deref(&self) -> &Self::Target {
+        &self.connection.connection
+    }
+}
 
-pub type ConnectionPool = Pool<ConnectionManager>;
-pub type ManagedConnection = Object<ConnectionManager>;
+impl DerefMut for ManagedConnection {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.connection.connection
+    }
+}
 
 pub struct ConnectionManager {
     info: ConnectionInfo,
     backoff: ExponentialBackoff,
+    max_size: usize,
+    /// Whether the server can authenticate connections again, known after the first connection.
+    supports_reauth: OnceLock<bool>,
+    /// A pool for every token that was passed with a query or transaction,
+    /// for servers that can only authenticate a connection once.
+    /// There are at most as many of them as connections in the pool.
+    user_pools: Mutex<HashMap<AuthToken, ConnectionPool>>,
+    /// The connections of the pool and of the user pools together are limited to `max_size`.
+    permits: Arc<Semaphore>,
+    /// Notified whenever a connection is given back to the pool or to one of the user pools.
+    returned: Arc<Notify>,
 }
 
 impl ConnectionManager {
-    pub fn new(
+    pub(crate) fn new(
         uri: &str,
-        user: &str,
-        password: &str,
+        auth: &Auth,
         tls_config: &ConnectionTLSConfig,
+        notifications: &NotificationFilter,
+        transport: &TransportConfig,
+        max_size: usize,
     ) -> Result<Self> {
-        let info = ConnectionInfo::new(uri, user, password, tls_config)?;
+        let info = ConnectionInfo::new(uri, auth, tls_config, notifications, transport)?;
         let backoff = ExponentialBackoffBuilder::new()
             .with_initial_interval(Duration::from_millis(1))
             .with_randomization_factor(0.42)
             .with_multiplier(2.0)
             .with_max_elapsed_time(Some(Duration::from_secs(60)))
             .build();
-        Ok(ConnectionManager { info, backoff })
+        Ok(ConnectionManager {
+            info,
+            backoff,
+            max_size,
+            supports_reauth: OnceLock::new(),
+            user_pools: Mutex::default(),
+            permits: Arc::new(Semaphore::new(max_size)),
+            returned: Arc::default(),
+        })
     }
 
     pub fn backoff(&self) -> ExponentialBackoff {
         self.backoff.clone()
     }
+
+    /// Returns the pool of connections that authenticated with `token`, creating it if needed.
+    ///
+    /// Once there are as many user pools as connections in the pool,
+    /// a pool that has no connection in use is closed to make room for the new one.
+    /// If all of them are in use, this fails with [`Error::UserPoolLimitReached`].
+    async fn user_pool(&self, token: &AuthToken) -> Result<ConnectionPool> {
+        let (pool, evicted) = {
+            let mut user_pools = self.user_pools.lock().unwrap();
+            if let Some(pool) = user_pools.get(token) {
+                return Ok(pool.clone());
+            }
+            let evicted = if user_pools.len() >= self.max_size {
+                let idle = user_pools
+                    .iter()
+                    .find(|(_, pool)| pool.status().available == pool.status().size)
+                    .map(|(token, _)| token.clone())
+                    .ok_or(Error::UserPoolLimitReached(self.max_size))?;
+                user_pools.remove(&idle)
+            } else {
+                None
+            };
+            debug!("creating connection pool for {:?}", token);
+            let manager = ConnectionManager {
+                info: self.info.with_auth(token.clone().into()),
+                backoff: self.backoff(),
+                max_size: self.max_size,
+                supports_reauth: OnceLock::new(),
+                user_pools: Mutex::default(),
+                permits: Arc::clone(&self.permits),
+                returned: Arc::clone(&self.returned),
+            };
+            let pool = ConnectionPool::builder(manager)
+                .max_size(self.max_size)
+                .build()
+                .expect("No timeouts configured");
+            user_pools.insert(token.clone(), pool.clone());
+            (pool, evicted)
+        };
+        if let Some(evicted) = evicted {
+            close_idle(&evicted, Instant::now()).await;
+        }
+        Ok(pool)
+    }
+
+    /// Waits until `target` can hand out a connection without exceeding the limit
+    /// shared with the other pools, closing an idle connection of another pool if needed.
+    async fn reserve(&self, pool: &ConnectionPool, target: &ConnectionPool) {
+        loop {
+            let returned = self.returned.notified();
+            if target.status().available > 0
+                || self.permits.available_permits() > 0
+                || self.permits.is_closed()
+            {
+                return;
+            }
+            let user_pools = self
+                .user_pools
+                .lock()
+                .unwrap()
+                .values()
+                .cloned()
+                .collect::<Vec<_>>();
+            let other = [pool]
+                .into_iter()
+                .chain(&user_pools)
+                .filter(|other| !std::ptr::eq(other.manager(), target.manager()))
+                .find(|other| other.status().available > 0);
+            match other {
+                Some(other) => {
+                    let mut evicted = false;
+                    let idle = other
+                        .retain(|_, _| mem::replace(&mut evicted, true))
+                        .removed;
+                    debug!("closing {} idle connections of another pool", idle.len());
+                    join_all(idle.into_iter().map(goodbye)).await;
+                }
+                None => returned.await,
+            }
+        }
+    }
 }
 
 impl Manager for ConnectionManager {
-    type Type = Connection;
+    type Type = PooledConnection;
     type Error = Error;
 
     async fn create(&self) -> Result<Self::Type, Self::Error> {
+        let permit = Arc::clone(&self.permits)
+            .acquire_owned()
+            .await
+            .map_err(|_| Error::GraphClosed)?;
         trace!("creating new connection");
-        Connection::new(&self.info).await
+        let connection = Connection::new(&self.info).await?;
+        let _ = self.supports_reauth.set(connection.supports_reauth());
+        Ok(PooledConnection {
+            connection,
+            _permit: permit,
+        })
     }
 
     async fn recycle(&self, obj: &mut Self::Type, _: &Metrics) -> RecycleResult<Self::Error> {
+        let obj = &mut obj.connection;
         trace!("recycling connection");
+        if obj.is_broken() {
+            return Err(RecycleError::message("connection is broken"));
+        }
+        let Some(current) = obj.auth_token() else {
+            return Err(RecycleError::message("connection is not authenticated"));
+        };
+        // connections that support it authenticate again when they are taken, see `get_connection`
+        if !obj.supports_reauth() && *current != self.info.auth.token().await? {
+            return Err(RecycleError::message("the auth token has changed"));
+        }
         Ok(obj.reset().await?)
     }
 }
 
+/// Takes a connection from the pool that is authenticated with `auth`,
+/// or with the credentials of the config if there is none.
+///
+/// Since Bolt 5.1, a pooled connection switches to the token with a LOGOFF and a LOGON.
+/// Older servers can only authenticate a connection once,
+/// so those connections are taken from a separate pool for every token.
+pub(crate) async fn get_connection(
+    pool: &ConnectionPool,
+    auth: Option<&AuthToken>,
+) -> Result<ManagedConnection> {
+    let manager = pool.manager();
+    if let (Some(token), Some(false)) = (auth, manager.supports_reauth.get()) {
+        let user_pool = manager.user_pool(token).await?;
+        manager.reserve(pool, &user_pool).await;
+        return Ok(user_pool.get().await?);
+    }
+    manager.reserve(pool, pool).await;
+    let mut connection = pool.get().await?;
+    if !connection.supports_reauth() {
+        return match auth {
+            Some(token) if connection.auth_token() != Some(token) => {
+                drop(connection);
+                let user_pool = manager.user_pool(token).await?;
+                manager.reserve(pool, &user_pool).await;
+                Ok(user_pool.get().await?)
+            }
+            _ => Ok(connection),
+        };
+    }
+    let token = match auth {
+        Some(token) => token.clone(),
+        None => manager.info.auth.token().await?,
+    };
+    if connection.auth_token() != Some(&token) {
+        trace!("authenticating connection again");
+        connection.reauth(&token).await?;
+    }
+    Ok(connection)
+}
+
 pub fn create_pool(config: &Config) -> Result<ConnectionPool> {
     let mgr = ConnectionManager::new(
         &config.uri,
-        &config.user,
-        &config.password,
+        &config.auth,
         &config.tls_config,
+        &config.notifications,
+        &config.transport,
+        config.max_connections,
     )?;
     info!(
         "creating connection pool with max size {}",
@@ -71,3 +290,163 @@ pub fn create_pool(config: &Config) -> Result<ConnectionPool> {
         .build()
         .expect("No timeouts configured"))
 }
+
+/// Closes the pool and the pools of other users,
+/// after the connections that are in use were given back or the deadline passed.
+/// Every connection that is idle by then is closed with a GOODBYE,
+/// the others are dropped once they are given back.
+pub(crate) async fn close_pool(pool: &ConnectionPool, deadline: Instant) {
+    pool.manager().permits.close();
+    let user_pools = mem::take(&mut *pool.manager().user_pools.lock().unwrap());
+    let pools = user_pools.values().chain([pool]);
+    join_all(pools.map(|pool| close_idle(pool, deadline))).await;
+}
+
+async fn close_idle(pool: &ConnectionPool, deadline: Instant) {
+    let returned = &pool.manager().returned;
+    loop {
+        let notified = returned.notified();
+        let status = pool.status();
+        if status.available >= status.size {
+            break;
+        }
+        let deadline = tokio::time::Instant::from_std(deadline);
+        if tokio::time::timeout_at(deadline, notified).await.is_err() {
+            break;
+        }
+    }
+    let idle = pool.retain(|_, _| false).removed;
+    pool.close();
+    debug!("closing {} idle connections", idle.len());
+    join_all(idle.into_iter().map(goodbye)).await;
+}
+
+async fn goodbye(mut connection: PooledConnection) {
+    if let Err(e) = connection.connection.goodbye().await {
+        debug!("failed to send GOODBYE: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::{duplex, AsyncReadExt, AsyncWriteExt, DuplexStream};
+
+    use super::*;
+    use crate::{ConfigBuilder, ConnectFuture, Connector, Transport};
+
+    /// Connects to a new in-memory server for every connection.
+    struct InMemory;
+
+    impl Connector for InMemory {
+        fn connect<'a>(&'a self, _host: &'a str, _port: u16) -> ConnectFuture<'a> {
+            let (client, server) = duplex(1024);
+            tokio::spawn(serve(server));
+            Box::pin(async move { Ok(Box::new(client) as Box<dyn Transport>) })
+        }
+    }
+
+    /// Speaks Bolt 4.4, accepts the HELLO and ignores everything after it.
+    async fn serve(mut server: DuplexStream) {
+        let mut handshake = [0; 20];
+        server.read_exact(&mut handshake).await.unwrap();
+        server.write_all(&[0, 0, 4, 4]).await.unwrap();
+        loop {
+            let size = usize::from(server.read_u16().await.unwrap());
+            if size == 0 {
+                break;
+            }
+            server.read_exact(&mut vec![0; size]).await.unwrap();
+        }
+        let hello = [
+            0xB1, 0x70, 0xA2, 0x86, b's', b'e', b'r', b'v', b'e', b'r', 0x81, b'N', 0x8D, b'c',
+            b'o', b'n', b'n', b'e', b'c', b't', b'i', b'o', b'n', b'_', b'i', b'd', 0x81, b'1',
+        ];
+        server.write_u16(hello.len() as u16).await.unwrap();
+        server.write_all(&hello).await.unwrap();
+        server.write_u16(0).await.unwrap();
+        let _ = tokio::io::copy(&mut server, &mut tokio::io::sink()).await;
+    }
+
+    fn manager() -> ConnectionManager {
+        ConnectionManager::new(
+            "bolt://localhost:7687",
+            &AuthToken::basic("neo4j", "secret").into(),
+            &ConnectionTLSConfig::default(),
+            &NotificationFilter::default(),
+            &TransportConfig::default(),
+            4,
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn should_keep_a_pool_for_every_token() {
+        let pool = ConnectionPool::builder(manager()).build().unwrap();
+        let alice = AuthToken::basic("alice", "secret");
+        let bob = AuthToken::bearer("bob");
+
+        let first = pool.manager().user_pool(&alice).await.unwrap();
+        pool.manager().user_pool(&alice).await.unwrap();
+        pool.manager().user_pool(&bob).await.unwrap();
+
+        assert_eq!(first.status().max_size, 4);
+        assert_eq!(first.manager().info.auth.token().await.unwrap(), alice);
+        assert_eq!(pool.manager().user_pools.lock().unwrap().len(), 2);
+
+        close_pool(&pool, Instant::now()).await;
+        assert!(pool.manager().user_pools.lock().unwrap().is_empty());
+        assert!(first.is_closed());
+    }
+
+    #[tokio::test]
+    async fn should_close_an_idle_user_pool_when_there_are_too_many() {
+        let pool = ConnectionPool::builder(manager()).build().unwrap();
+        let tokens = (0..5)
+            .map(|i| AuthToken::bearer(format!("token-{i}")))
+            .collect::<Vec<_>>();
+
+        let mut user_pools = Vec::new();
+        for token in &tokens {
+            user_pools.push(pool.manager().user_pool(token).await.unwrap());
+        }
+
+        assert_eq!(pool.manager().user_pools.lock().unwrap().len(), 4);
+        assert_eq!(user_pools.iter().filter(|p| p.is_closed()).count(), 1);
+        assert!(!user_pools[4].is_closed());
+    }
+
+    #[tokio::test]
+    async fn should_limit_the_connections_of_all_pools_together() {
+        let config = ConfigBuilder::default()
+            .uri("bolt://localhost:7687")
+            .user("neo4j")
+            .password("secret")
+            .max_connections(1)
+            .with_connector(InMemory)
+            .build()
+            .unwrap();
+        let pool = create_pool(&config).unwrap();
+        let alice = AuthToken::basic("alice", "secret");
+        let bob = AuthToken::bearer("bob");
+
+        let connection = get_connection(&pool, Some(&alice)).await.unwrap();
+        assert_eq!(connection.auth_token(), Some(&alice));
+        assert_eq!(pool.status().size, 0);
+
+        let error = get_connection(&pool, Some(&bob)).await.err().unwrap();
+        assert!(matches!(error, Error::UserPoolLimitReached(1)), "{error:?}");
+
+        drop(connection);
+        let connection = get_connection(&pool, None).await.unwrap();
+        assert_eq!(
+            connection.auth_token(),
+            Some(&AuthToken::basic("neo4j", "secret"))
+        );
+        assert_eq!(
+            pool.manager().user_pools.lock().unwrap()[&alice]
+                .status()
+                .size,
+            0
+        );
+    }
+}
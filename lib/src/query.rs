@@ -1,14 +1,25 @@
 use std::cell::{Cell, RefCell};
 
-#[cfg(feature = "unstable-bolt-protocol-impl-v2")]
-use crate::{bolt::Summary, summary::ResultSummary};
 use crate::{
+    auth::AuthToken,
+    config::NotificationFilter,
     errors::Result,
-    messages::{BoltRequest, BoltResponse},
+    messages::TelemetryApi,
     pool::ManagedConnection,
     stream::{DetachedRowStream, RowStream},
     types::{BoltList, BoltMap, BoltString, BoltType},
-    Error, Success,
+    version::Encoding,
+    Error, Version,
+};
+#[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+use crate::{
+    bolt::{Begin, ExpectedResponse, Run, RunResponse, Summary, TxExtra},
+    summary::ResultSummary,
+};
+#[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
+use crate::{
+    messages::{BoltRequest, BoltResponse},
+    Success,
 };
 
 #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
@@ -16,12 +27,31 @@ pub type RunResult = ResultSummary;
 #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
 pub type RunResult = ();
 
+/// The BEGIN that is sent along with the first query of a transaction.
+#[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+pub(crate) type BeginRequest<'a> = Begin<'a>;
+#[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
+pub(crate) type BeginRequest<'a> = BoltRequest;
+
+#[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+type RunRequest<'a> = Run<'a>;
+#[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
+type RunRequest<'a> = BoltRequest;
+
+#[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+type RunSuccess = RunResponse;
+#[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
+type RunSuccess = Success;
+
 /// Abstracts a cypher query that is sent to neo4j server.
 #[derive(Clone)]
 pub struct Query {
     query: String,
     params: BoltMap,
     extra: BoltMap,
+    notifications: Option<NotificationFilter>,
+    imp_user: Option<String>,
+    auth: Option<AuthToken>,
 }
 
 impl Query {
@@ -30,9 +60,43 @@ impl Query {
             query,
             params: BoltMap::default(),
             extra: BoltMap::default(),
+            notifications: None,
+            imp_user: None,
+            auth: None,
         }
     }
 
+    /// Only receive the notifications allowed by the given filter, instead of the one in [`crate::Config`].
+    ///
+    /// This applies when the query runs in an auto-commit transaction, e.g. with [`crate::Graph::run`].
+    /// Inside of a [`crate::Txn`], the filter of the transaction is used.
+    pub fn with_notification_filter(mut self, notifications: NotificationFilter) -> Self {
+        self.notifications = Some(notifications);
+        self
+    }
+
+    /// Run the query on behalf of another user, with the privileges and the home database of that user.
+    ///
+    /// This applies when the query runs in an auto-commit transaction, e.g. with [`crate::Graph::run`].
+    /// Inside of a [`crate::Txn`], the user of the transaction is used,
+    /// see [`crate::TxnOptions::with_impersonated_user`].
+    /// The authenticated user needs the `IMPERSONATE` privilege, and the server at least Neo4j 4.4.
+    pub fn with_impersonated_user(mut self, user: impl Into<String>) -> Self {
+        self.imp_user = Some(user.into());
+        self
+    }
+
+    /// Run the query with the credentials of another user, instead of the ones in [`crate::Config`].
+    ///
+    /// This applies when the query runs in an auto-commit transaction, e.g. with [`crate::Graph::run`].
+    /// Inside of a [`crate::Txn`], see [`crate::TxnOptions::with_auth`].
+    /// Since Neo4j 5.5 (Bolt 5.1), a pooled connection authenticates again with the token,
+    /// older servers get a separate pool of connections for every token.
+    pub fn with_auth(mut self, auth: AuthToken) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
     pub fn with_params(mut self, params: BoltMap) -> Self {
         self.params = params;
         self
@@ -88,9 +152,30 @@ impl Query {
         &self.params
     }
 
-    pub(crate) async fn run(self, connection: &mut ManagedConnection) -> Result<RunResult> {
-        let request = BoltRequest::run(&self.query, self.params, self.extra);
-        Self::try_run(request, connection)
+    pub(crate) fn imp_user(&self) -> Option<&str> {
+        self.imp_user.as_deref()
+    }
+
+    pub(crate) fn auth(&self) -> Option<&AuthToken> {
+        self.auth.as_ref()
+    }
+
+    /// Runs the query in a transaction, `begin` is sent along if the transaction is not started yet.
+    pub(crate) async fn run(
+        self,
+        connection: &mut ManagedConnection,
+        begin: Option<BeginRequest<'_>>,
+    ) -> Result<RunResult> {
+        #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
+        let request = BoltRequest::run(&self.query, self.params, self.extra, connection.version())?;
+        #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+        let request = Run::with_encoding(
+            &self.query,
+            &self.params,
+            TxExtra::from_map(&self.extra)?,
+            connection.encoding(),
+        )?;
+        Self::try_run(begin, request, connection)
             .await
             .map_err(unwrap_backoff)
     }
@@ -99,8 +184,11 @@ impl Query {
         &self,
         connection: &mut ManagedConnection,
     ) -> QueryResult<RunResult> {
-        let request = BoltRequest::run(&self.query, self.params.clone(), self.extra.clone());
-        Self::try_run(request, connection).await
+        let request = self
+            .auto_commit_request(connection.encoding())
+            .map_err(into_backoff)?;
+        Self::try_telemetry(connection).await?;
+        Self::try_run(None, request, connection).await
     }
 
     pub(crate) async fn execute_retryable(
@@ -108,62 +196,233 @@ impl Query {
         fetch_size: usize,
         mut connection: ManagedConnection,
     ) -> QueryResult<DetachedRowStream> {
-        let request = BoltRequest::run(&self.query, self.params.clone(), self.extra.clone());
-        Self::try_execute(request, fetch_size, &mut connection)
+        let request = self
+            .auto_commit_request(connection.encoding())
+            .map_err(into_backoff)?;
+        Self::try_telemetry(&mut connection).await?;
+        Self::try_execute(None, request, fetch_size, &mut connection)
             .await
             .map(|stream| DetachedRowStream::new(stream, connection))
     }
 
+    /// Executes the query in a transaction, `begin` is sent along if the transaction is not started yet.
     pub(crate) async fn execute_mut(
         self,
         fetch_size: usize,
         connection: &mut ManagedConnection,
+        begin: Option<BeginRequest<'_>>,
     ) -> Result<RowStream> {
-        let run = BoltRequest::run(&self.query, self.params, self.extra);
-        Self::try_execute(run, fetch_size, connection)
+        #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
+        let run = BoltRequest::run(&self.query, self.params, self.extra, connection.version())?;
+        #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+        let run = Run::with_encoding(
+            &self.query,
+            &self.params,
+            TxExtra::from_map(&self.extra)?,
+            connection.encoding(),
+        )?;
+        // Bolt 3 has no query ids, a PULL_ALL always reads the last result,
+        // so all records are read before another query of the transaction can run
+        if connection.version() < Version::V4 {
+            let stream = Self::try_execute(begin, run, fetch_size, connection)
+                .await
+                .map_err(unwrap_backoff)?;
+            return stream.buffered(connection).await;
+        }
+        // other streams of the transaction may still be open, so the records are only pulled
+        // once this stream is read, with its query id, instead of being pipelined with the RUN
+        let success = Self::try_request(begin, run, Consume::Later, connection)
             .await
-            .map_err(unwrap_backoff)
+            .map_err(unwrap_backoff)?;
+        Ok(Self::row_stream(success, fetch_size))
+    }
+
+    #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
+    fn auto_commit_request(&self, encoding: Encoding) -> Result<RunRequest<'_>> {
+        let version = encoding.version;
+        let extra = self.auto_commit_extra(version);
+        BoltRequest::run(&self.query, self.params.clone(), extra, version)
+    }
+
+    #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
+    fn auto_commit_extra(&self, version: Version) -> BoltMap {
+        let mut extra = self.extra.clone();
+        if let Some(notifications) = self.notifications.as_ref() {
+            if version >= Version::V5_2 {
+                notifications.write_into(version, &mut extra);
+            }
+        }
+        if let Some(imp_user) = self.imp_user.as_deref() {
+            extra.put("imp_user".into(), imp_user.into());
+        }
+        extra
+    }
+
+    #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+    fn auto_commit_request(&self, encoding: Encoding) -> Result<RunRequest<'_>> {
+        let mut extra = TxExtra::from_map(&self.extra)?;
+        if let Some(notifications) = self.notifications.as_ref() {
+            extra = extra.with_notification_filter(notifications);
+        }
+        if let Some(imp_user) = self.imp_user.as_deref() {
+            extra = extra.with_imp_user(imp_user);
+        }
+        Run::with_encoding(&self.query, &self.params, extra, encoding)
+    }
+
+    async fn try_telemetry(connection: &mut ManagedConnection) -> QueryResult<()> {
+        connection
+            .write_telemetry(TelemetryApi::AutoCommit)
+            .await
+            .map_err(into_backoff)
     }
 
     async fn try_run(
-        request: BoltRequest,
+        begin: Option<BeginRequest<'_>>,
+        request: RunRequest<'_>,
         connection: &mut ManagedConnection,
     ) -> QueryResult<RunResult> {
-        let result = Self::try_execute(request, 4096, connection).await?;
+        let success = Self::try_request(begin, request, Consume::Discard, connection).await?;
+        let result = Self::row_stream(success, 0).discarding();
         Ok(result.finish(connection).await?)
     }
 
     async fn try_execute(
-        request: BoltRequest,
+        begin: Option<BeginRequest<'_>>,
+        request: RunRequest<'_>,
         fetch_size: usize,
         connection: &mut ManagedConnection,
     ) -> QueryResult<RowStream> {
-        Self::try_request(request, connection).await.map(|success| {
+        let success =
+            Self::try_request(begin, request, Consume::Pull(fetch_size), connection).await?;
+        Ok(Self::row_stream(success, fetch_size).pulling())
+    }
+
+    fn row_stream(success: RunSuccess, fetch_size: usize) -> RowStream {
+        #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+        {
+            let fields = success
+                .fields
+                .into_iter()
+                .map(BoltType::from)
+                .collect::<Vec<_>>();
+            let qid = success.qid.unwrap_or(-1);
+            let available = success.t_first.unwrap_or(-1);
+            RowStream::new(qid, available, BoltList::from(fields), fetch_size)
+        }
+
+        #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
+        {
             let fields: BoltList = success.get("fields").unwrap_or_default();
             let qid: i64 = success.get("qid").unwrap_or(-1);
+            RowStream::new(qid, fields, fetch_size)
+        }
+    }
 
-            #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
-            {
-                let available: i64 = success.get("t_first").unwrap_or(-1);
-                RowStream::new(qid, available, fields, fetch_size)
+    /// Sends BEGIN (if any), RUN, and the first PULL or DISCARD in a single flush,
+    /// then reads the responses in the same order.
+    async fn try_request(
+        begin: Option<BeginRequest<'_>>,
+        request: RunRequest<'_>,
+        consume: Consume,
+        connection: &mut ManagedConnection,
+    ) -> QueryResult<RunSuccess> {
+        let has_begin = begin.is_some();
+        Self::write_pipeline(begin, request, consume, connection)
+            .await
+            .map_err(into_backoff)?;
+
+        #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
+        {
+            if has_begin {
+                match connection.recv().await {
+                    Ok(BoltResponse::Success(_)) => {}
+                    otherwise => {
+                        Self::skip_responses(connection).await?;
+                        return wrap_error(otherwise, "BEGIN");
+                    }
+                }
+            }
+
+            match connection.recv().await {
+                Ok(BoltResponse::Success(success)) => Ok(success),
+                otherwise => {
+                    Self::skip_responses(connection).await?;
+                    wrap_error(otherwise, "RUN")
+                }
+            }
+        }
+
+        #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+        {
+            if has_begin {
+                match connection
+                    .recv_as::<<Begin as ExpectedResponse>::Response>()
+                    .await
+                {
+                    Ok(Summary::Success(_)) => {}
+                    otherwise => {
+                        Self::skip_responses(connection).await?;
+                        return wrap_error(otherwise, "BEGIN");
+                    }
+                }
             }
 
-            #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
+            match connection
+                .recv_as::<<Run as ExpectedResponse>::Response>()
+                .await
             {
-                RowStream::new(qid, fields, fetch_size)
+                Ok(Summary::Success(success)) => Ok(success.metadata),
+                otherwise => {
+                    Self::skip_responses(connection).await?;
+                    wrap_error(otherwise, "RUN")
+                }
             }
-        })
+        }
     }
 
-    async fn try_request(
-        request: BoltRequest,
+    async fn write_pipeline(
+        begin: Option<BeginRequest<'_>>,
+        request: RunRequest<'_>,
+        consume: Consume,
         connection: &mut ManagedConnection,
-    ) -> QueryResult<Success> {
-        match connection.send_recv(request).await {
-            Ok(BoltResponse::Success(success)) => Ok(success),
-            otherwise => wrap_error(otherwise, "RUN"),
+    ) -> Result<()> {
+        #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
+        {
+            if let Some(begin) = begin {
+                connection.write(begin).await?;
+            }
+            connection.write(request).await?;
         }
+        #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+        {
+            if let Some(begin) = begin {
+                connection.write_as(begin).await?;
+            }
+            connection.write_as(request).await?;
+        }
+        // the query id of the RUN is not known yet, -1 refers to the last query
+        match consume {
+            Consume::Pull(fetch_size) => RowStream::write_pull(connection, fetch_size, -1).await?,
+            Consume::Discard => RowStream::write_discard(connection, -1).await?,
+            Consume::Later => {}
+        }
+        connection.flush().await
     }
+
+    /// Reads the responses to the requests that were pipelined after a failed one.
+    /// The server ignores those requests, but still answers them.
+    async fn skip_responses(connection: &mut ManagedConnection) -> QueryResult<()> {
+        connection.skip_pending().await.map_err(into_backoff)
+    }
+}
+
+/// How the records of a query are consumed, right after its RUN.
+enum Consume {
+    Pull(usize),
+    Discard,
+    /// Nothing is sent along, the records are pulled when the stream is read.
+    Later,
 }
 
 impl From<String> for Query {
@@ -190,16 +449,19 @@ impl std::fmt::Debug for Query {
 type QueryResult<T> = Result<T, backoff::Error<Error>>;
 
 fn wrap_error<T>(resp: impl IntoError, req: &'static str) -> QueryResult<T> {
-    let error = resp.into_error(req);
+    Err(into_backoff(resp.into_error(req)))
+}
+
+fn into_backoff(error: Error) -> backoff::Error<Error> {
     let can_retry = match &error {
         Error::Neo4j(e) => e.can_retry(),
         _ => false,
     };
 
     if can_retry {
-        Err(backoff::Error::transient(error))
+        backoff::Error::transient(error)
     } else {
-        Err(backoff::Error::permanent(error))
+        backoff::Error::permanent(error)
     }
 }
 
@@ -207,6 +469,7 @@ trait IntoError {
     fn into_error(self, msg: &'static str) -> Error;
 }
 
+#[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
 impl IntoError for Result<BoltResponse> {
     fn into_error(self, msg: &'static str) -> Error {
         match self {
@@ -373,6 +636,7 @@ macro_rules! query {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Version;
 
     #[test]
     fn add_params() {
@@ -392,6 +656,92 @@ mod tests {
         assert!(!q.has_param_key("country"));
     }
 
+    #[test]
+    #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
+    fn add_notification_filter_for_auto_commit() {
+        let q = Query::new("RETURN 1".to_owned())
+            .extra("db", "neo4j")
+            .with_notification_filter(NotificationFilter::disable_all());
+
+        let extra = q.auto_commit_extra(Version::V5_2);
+        assert_eq!(extra.get::<String>("db").unwrap(), "neo4j");
+        assert_eq!(
+            extra
+                .get::<String>("notifications_minimum_severity")
+                .unwrap(),
+            "OFF"
+        );
+
+        let extra = q.auto_commit_extra(Version::V5_1);
+        assert_eq!(extra, q.extra);
+    }
+
+    #[test]
+    #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+    fn add_notification_filter_for_auto_commit() {
+        use crate::bolt::Message as _;
+
+        let notifications = NotificationFilter::disable_all();
+        let q = Query::new("RETURN 1".to_owned())
+            .extra("db", "neo4j")
+            .with_notification_filter(notifications.clone());
+
+        let extra = TxExtra::new()
+            .with_db("neo4j")
+            .with_notification_filter(&notifications);
+        let expected = Run::new("RETURN 1", &BoltMap::default(), extra, Version::V5_2).unwrap();
+        assert_eq!(
+            q.auto_commit_request(Version::V5_2.into())
+                .unwrap()
+                .to_bytes()
+                .unwrap(),
+            expected.to_bytes().unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
+    fn add_impersonated_user_for_auto_commit() {
+        let q = Query::new("RETURN 1".to_owned()).with_impersonated_user("alice");
+
+        let extra = q.auto_commit_extra(Version::V4_4);
+        assert_eq!(extra.get::<String>("imp_user").unwrap(), "alice");
+        assert!(matches!(
+            q.auto_commit_request(Version::V4_3.into()),
+            Err(Error::UnsupportedFeature("Impersonation", Version::V4_3))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+    fn add_impersonated_user_for_auto_commit() {
+        use crate::bolt::Message as _;
+
+        let q = Query::new("RETURN 1".to_owned()).with_impersonated_user("alice");
+
+        let extra = TxExtra::new().with_imp_user("alice");
+        let expected = Run::new("RETURN 1", &BoltMap::default(), extra, Version::V4_4).unwrap();
+        assert_eq!(
+            q.auto_commit_request(Version::V4_4.into())
+                .unwrap()
+                .to_bytes()
+                .unwrap(),
+            expected.to_bytes().unwrap()
+        );
+        assert!(matches!(
+            q.auto_commit_request(Version::V4_3.into()),
+            Err(Error::UnsupportedFeature("Impersonation", Version::V4_3))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+    fn reject_unknown_extra() {
+        let q = Query::new("RETURN 1".to_owned()).extra("foo", "bar");
+
+        assert!(q.auto_commit_request(Version::V5_2.into()).is_err());
+    }
+
     #[test]
     fn query_macro() {
         let q = query!(
@@ -0,0 +1,111 @@
+use std::{fmt, future::Future, io, pin::Pin, sync::Arc};
+
+/// The host and port of a server.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ServerAddress {
+    host: Arc<str>,
+    port: u16,
+}
+
+impl ServerAddress {
+    /// IPv6 addresses can be given with or without brackets, e.g. `::1` or `[::1]`.
+    pub fn new(host: impl Into<String>, port: u16) -> Self {
+        let host = host.into();
+        let host = match host.strip_prefix('[').and_then(|h| h.strip_suffix(']')) {
+            Some(ipv6) => ipv6.to_owned(),
+            None => host,
+        };
+        Self {
+            host: host.into(),
+            port,
+        }
+    }
+
+    /// The host name or IP address, IPv6 addresses are without brackets.
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+}
+
+impl fmt::Display for ServerAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.host.contains(':') {
+            write!(f, "[{}]:{}", self.host, self.port)
+        } else {
+            write!(f, "{}:{}", self.host, self.port)
+        }
+    }
+}
+
+/// The future returned by [`ServerAddressResolver::resolve`].
+pub type ResolveFuture<'a> =
+    Pin<Box<dyn Future<Output = io::Result<Vec<ServerAddress>>> + Send + 'a>>;
+
+/// Maps the address of a `neo4j://` URI to the addresses of the routers
+/// that are asked for the routing table.
+///
+/// A resolver is set with [`crate::ConfigBuilder::with_resolver`].
+/// It is called before the first routing table is fetched, and again when none of the routers
+/// of the last routing table respond. The returned routers are tried in turn
+/// until one of them returns a routing table.
+///
+/// # Example
+///
+/// ```no_run
+/// use neo4rs::{ResolveFuture, ServerAddress, ServerAddressResolver};
+///
+/// struct Seeds;
+///
+/// impl ServerAddressResolver for Seeds {
+///     fn resolve<'a>(&'a self, address: &'a ServerAddress) -> ResolveFuture<'a> {
+///         Box::pin(async move {
+///             Ok(vec![
+///                 ServerAddress::new("core-1.example.com", address.port()),
+///                 ServerAddress::new("core-2.example.com", address.port()),
+///             ])
+///         })
+///     }
+/// }
+/// ```
+pub trait ServerAddressResolver: Send + Sync {
+    /// Returns the routers for the `address` of the URI, in the order they should be tried.
+    ///
+    /// If no routers are returned, the address of the URI is used.
+    fn resolve<'a>(&'a self, address: &'a ServerAddress) -> ResolveFuture<'a>;
+}
+
+/// A shared [`ServerAddressResolver`] in the [`crate::Config`].
+#[derive(Clone)]
+#[cfg_attr(not(feature = "unstable-bolt-protocol-impl-v2"), allow(dead_code))]
+pub(crate) struct Resolver(pub(crate) Arc<dyn ServerAddressResolver>);
+
+impl fmt::Debug for Resolver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Resolver(..)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_strip_ipv6_brackets() {
+        let address = ServerAddress::new("[::1]", 7687);
+
+        assert_eq!(address.host(), "::1");
+        assert_eq!(address.to_string(), "[::1]:7687");
+        assert_eq!(address, ServerAddress::new("::1", 7687));
+    }
+
+    #[test]
+    fn should_display_host_and_port() {
+        let address = ServerAddress::new("core-1.example.com", 7688);
+
+        assert_eq!(address.to_string(), "core-1.example.com:7688");
+    }
+}
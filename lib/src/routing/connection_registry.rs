@@ -1,14 +1,17 @@
 use crate::connection::NeoUrl;
-use crate::pool::{create_pool, ConnectionPool};
+use crate::pool::{close_pool, create_pool, ConnectionPool};
 use crate::routing::routing_table_provider::RoutingTableProvider;
 use crate::routing::Server;
 use crate::{Config, Error};
 use dashmap::DashMap;
+use futures::future::join_all;
 use log::debug;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 use tokio::sync::mpsc::Sender;
+use tokio::task::JoinHandle;
 
 /// Represents a Bolt server, with its address, port and role.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -41,12 +44,21 @@ impl BoltServer {
 /// A registry of connection pools, indexed by the Bolt server they connect to.
 pub type Registry = DashMap<BoltServer, ConnectionPool>;
 
+/// The servers of an impersonated user's home database, until the routing table expires.
 #[derive(Clone)]
+struct HomeTable {
+    servers: Vec<BoltServer>,
+    expires: Instant,
+}
+
 pub(crate) struct ConnectionRegistry {
     pub(crate) connections: Registry,
+    /// The routing tables of the impersonated users, by user name.
+    home_tables: DashMap<String, HomeTable>,
+    /// Set once the registry is closed, after which no pools are registered anymore.
+    closed: AtomicBool,
 }
 
-#[allow(dead_code)]
 pub(crate) enum RegistryCommand {
     Refresh,
     Stop,
@@ -56,6 +68,8 @@ impl Default for ConnectionRegistry {
     fn default() -> Self {
         ConnectionRegistry {
             connections: Registry::new(),
+            home_tables: DashMap::new(),
+            closed: AtomicBool::new(false),
         }
     }
 }
@@ -66,9 +80,34 @@ async fn refresh_routing_table(
     provider: Arc<Box<dyn RoutingTableProvider>>,
 ) -> Result<u64, Error> {
     debug!("Routing table expired or empty, refreshing...");
-    let routing_table = provider.fetch_routing_table(&config).await?;
+    let routing_table = provider.fetch_routing_table(&config, None).await?;
     debug!("Routing table refreshed: {:?}", routing_table);
     let servers = routing_table.resolve();
+    register_servers(&config, &registry, &servers)?;
+    let now = Instant::now();
+    registry.home_tables.retain(|_, table| table.expires > now);
+    registry.connections.retain(|k, _| {
+        servers.contains(k)
+            || registry
+                .home_tables
+                .iter()
+                .any(|table| table.servers.contains(k))
+    });
+    debug!(
+        "Registry updated. New size is {} with TTL {}s",
+        registry.connections.len(),
+        routing_table.ttl
+    );
+    Ok(routing_table.ttl)
+}
+
+/// Creates the connection pools for the servers that are not in the registry yet.
+/// Fails with [`Error::GraphClosed`] once the registry is closed.
+fn register_servers(
+    config: &Config,
+    registry: &ConnectionRegistry,
+    servers: &[BoltServer],
+) -> Result<(), Error> {
     let url = NeoUrl::parse(config.uri.as_str())?;
     // Convert neo4j scheme to bolt scheme to create connection pools.
     // We need to use the bolt scheme since we don't want new connections to be routed
@@ -76,10 +115,14 @@ async fn refresh_routing_table(
         "neo4j" => "bolt",
         "neo4j+s" => "bolt+s",
         "neo4j+ssc" => "bolt+ssc",
+        #[cfg(feature = "websocket")]
+        "neo4j+ws" => "bolt+ws",
+        #[cfg(feature = "websocket")]
+        "neo4j+wss" => "bolt+wss",
         _ => panic!("Unsupported scheme: {}", url.scheme()),
     };
 
-    for server in servers.iter() {
+    for server in servers {
         if registry.connections.contains_key(server) {
             continue;
         }
@@ -92,26 +135,68 @@ async fn refresh_routing_table(
                 ..config.clone()
             })?,
         );
+        // checked after inserting, so that a pool is either seen by `close` or removed here
+        if registry.closed.load(Ordering::SeqCst) {
+            registry.connections.remove(server);
+            return Err(Error::GraphClosed);
+        }
     }
-    registry.connections.retain(|k, _| servers.contains(k));
-    debug!(
-        "Registry updated. New size is {} with TTL {}s",
-        registry.connections.len(),
-        routing_table.ttl
-    );
-    Ok(routing_table.ttl)
+    Ok(())
 }
 
+/// Returns the available servers of the home database of `imp_user`,
+/// fetching its routing table if there is none or if it expired.
+///
+/// The routing tables of impersonated users are fetched on demand,
+/// and dropped once they expire instead of being refreshed in the background.
+pub(crate) async fn home_servers(
+    config: &Config,
+    registry: &ConnectionRegistry,
+    provider: &dyn RoutingTableProvider,
+    imp_user: &str,
+) -> Result<Vec<BoltServer>, Error> {
+    let cached = registry
+        .home_tables
+        .get(imp_user)
+        .filter(|table| table.expires > Instant::now())
+        .map(|table| table.servers.clone());
+    let servers = match cached {
+        Some(servers) => servers,
+        None => {
+            debug!("Fetching the routing table for the impersonated user {imp_user}");
+            let routing_table = provider.fetch_routing_table(config, Some(imp_user)).await?;
+            let servers = routing_table.resolve();
+            register_servers(config, registry, &servers)?;
+            let expires = Instant::now() + Duration::from_secs(routing_table.ttl);
+            registry.home_tables.insert(
+                imp_user.to_owned(),
+                HomeTable {
+                    servers: servers.clone(),
+                    expires,
+                },
+            );
+            servers
+        }
+    };
+    // servers that were marked unavailable stay unavailable until the table is fetched again
+    Ok(servers
+        .into_iter()
+        .filter(|server| registry.connections.contains_key(server))
+        .collect())
+}
+
+/// Refreshes the routing table until [`RegistryCommand::Stop`] is sent,
+/// returns the sender of the commands and the task, which ends after the stop.
 pub(crate) fn start_background_updater(
     config: &Config,
     registry: Arc<ConnectionRegistry>,
     provider: Arc<Box<dyn RoutingTableProvider>>,
-) -> Sender<RegistryCommand> {
+) -> (Sender<RegistryCommand>, JoinHandle<()>) {
     let config_clone = config.clone();
     let (tx, mut rx) = mpsc::channel(1);
 
     // This thread is in charge of refreshing the routing table periodically
-    tokio::spawn(async move {
+    let updater = tokio::spawn(async move {
         let mut ttl =
             refresh_routing_table(config_clone.clone(), registry.clone(), provider.clone())
                 .await
@@ -156,7 +241,7 @@ pub(crate) fn start_background_updater(
             interval.tick().await;
         }
     });
-    tx
+    (tx, updater)
 }
 
 impl ConnectionRegistry {
@@ -169,18 +254,36 @@ impl ConnectionRegistry {
         self.connections.remove(server);
     }
 
+    /// Drops the routing table of `imp_user`, so that it is fetched again when it is needed next.
+    pub(crate) fn forget_home_table(&self, imp_user: &str) {
+        self.home_tables.remove(imp_user);
+    }
+
     pub fn servers(&self) -> Vec<BoltServer> {
         self.connections
             .iter()
             .map(|entry| entry.key().clone())
             .collect()
     }
+
+    /// Closes the pools of all servers and removes them from the registry.
+    /// No more pools can be registered afterwards.
+    pub(crate) async fn close(&self, deadline: Instant) {
+        self.closed.store(true, Ordering::SeqCst);
+        let pools = self
+            .servers()
+            .iter()
+            .filter_map(|server| self.connections.remove(server))
+            .map(|(_, pool)| pool)
+            .collect::<Vec<_>>();
+        join_all(pools.iter().map(|pool| close_pool(pool, deadline))).await;
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::auth::ConnectionTLSConfig;
+    use crate::auth::{AuthToken, ConnectionTLSConfig};
     use crate::routing::load_balancing::LoadBalancingStrategy;
     use crate::routing::Server;
     use crate::routing::{RoundRobinStrategy, RoutingTable};
@@ -201,6 +304,7 @@ mod tests {
         fn fetch_routing_table(
             &self,
             _: &Config,
+            _: Option<&str>,
         ) -> Pin<Box<dyn Future<Output = Result<RoutingTable, Error>> + Send>> {
             let routing_table = self.routing_table.clone();
             Box::pin(async move { Ok(routing_table) })
@@ -245,12 +349,14 @@ mod tests {
         };
         let config = Config {
             uri: "neo4j://localhost:7687".to_string(),
-            user: "user".to_string(),
-            password: "password".to_string(),
+            auth: AuthToken::basic("user", "password").into(),
             max_connections: 10,
             db: Some("neo4j".into()),
             fetch_size: 0,
-            tls_config: ConnectionTLSConfig::None,
+            tls_config: ConnectionTLSConfig::default(),
+            notifications: Default::default(),
+            transport: Default::default(),
+            resolver: None,
         };
         let registry = Arc::new(ConnectionRegistry::default());
         let ttl = refresh_routing_table(
@@ -278,4 +384,98 @@ mod tests {
         let writer = strategy.select_writer(&registry.servers());
         assert!(writer.is_none());
     }
+
+    /// Returns a table with a single writer, named after the impersonated user.
+    struct HomeTableProvider {
+        fetched: Arc<std::sync::Mutex<Vec<Option<String>>>>,
+    }
+
+    impl RoutingTableProvider for HomeTableProvider {
+        fn fetch_routing_table(
+            &self,
+            _: &Config,
+            imp_user: Option<&str>,
+        ) -> Pin<Box<dyn Future<Output = Result<RoutingTable, Error>> + Send>> {
+            let imp_user = imp_user.map(str::to_owned);
+            self.fetched.lock().unwrap().push(imp_user.clone());
+            let host = imp_user.unwrap_or_else(|| "default".to_owned());
+            let routing_table = RoutingTable {
+                ttl: 300,
+                db: None,
+                servers: vec![Server {
+                    addresses: vec![format!("{host}:7687")],
+                    role: "WRITE".to_string(),
+                }],
+            };
+            Box::pin(async move { Ok(routing_table) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_home_servers_of_impersonated_users() {
+        let config = crate::ConfigBuilder::default()
+            .uri("neo4j://localhost:7687")
+            .user("user")
+            .password("password")
+            .build()
+            .unwrap();
+        let registry = Arc::new(ConnectionRegistry::default());
+        let fetched = Arc::default();
+        let provider = Arc::new(Box::new(HomeTableProvider {
+            fetched: Arc::clone(&fetched),
+        }) as Box<dyn RoutingTableProvider>);
+        refresh_routing_table(config.clone(), registry.clone(), provider.clone())
+            .await
+            .unwrap();
+
+        for _ in 0..2 {
+            let servers = home_servers(&config, &registry, &**provider, "alice")
+                .await
+                .unwrap();
+            assert_eq!(servers.len(), 1);
+            assert_eq!(servers[0].address, "alice");
+        }
+        assert_eq!(registry.connections.len(), 2);
+
+        // the pools of impersonated users are kept when the default table is refreshed
+        refresh_routing_table(config.clone(), registry.clone(), provider.clone())
+            .await
+            .unwrap();
+        assert_eq!(registry.connections.len(), 2);
+
+        registry.forget_home_table("alice");
+        home_servers(&config, &registry, &**provider, "alice")
+            .await
+            .unwrap();
+        let alice = Some("alice".to_owned());
+        assert_eq!(*fetched.lock().unwrap(), [None, alice.clone(), None, alice]);
+    }
+
+    #[tokio::test]
+    async fn test_no_pools_are_registered_after_close() {
+        let config = crate::ConfigBuilder::default()
+            .uri("neo4j://localhost:7687")
+            .user("user")
+            .password("password")
+            .build()
+            .unwrap();
+        let registry = Arc::new(ConnectionRegistry::default());
+        let provider = Arc::new(Box::new(HomeTableProvider {
+            fetched: Arc::default(),
+        }) as Box<dyn RoutingTableProvider>);
+        let (channel, updater) =
+            start_background_updater(&config, registry.clone(), provider.clone());
+
+        channel.send(RegistryCommand::Stop).await.unwrap();
+        tokio::time::timeout(Duration::from_secs(5), updater)
+            .await
+            .unwrap()
+            .unwrap();
+        registry.close(Instant::now()).await;
+        assert!(registry.servers().is_empty());
+
+        let result = refresh_routing_table(config.clone(), registry.clone(), provider).await;
+        assert!(matches!(result, Err(Error::GraphClosed)));
+        assert!(registry.servers().is_empty());
+    }
 }
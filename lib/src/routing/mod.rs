@@ -22,10 +22,8 @@ pub struct Route<'a> {
     pub(crate) extra: RouteExtra,
 }
 
-// NOTE: this structure will be needed in the future when we implement the Bolt protocol v4.4
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
-#[allow(dead_code)]
 pub struct Extra {
     pub(crate) db: Option<Database>,
     pub(crate) imp_user: Option<String>,
@@ -81,7 +79,6 @@ impl<'a> RouteBuilder<'a> {
         }
     }
 
-    #[allow(dead_code)]
     pub fn with_imp_user(self, imp_user: &'a str) -> Self {
         Self {
             imp_user: Some(imp_user.to_string()),
@@ -1,6 +1,7 @@
-use crate::pool::ManagedConnection;
+use crate::auth::AuthToken;
+use crate::pool::{get_connection, ManagedConnection};
 use crate::routing::connection_registry::{
-    start_background_updater, BoltServer, ConnectionRegistry, RegistryCommand,
+    home_servers, start_background_updater, BoltServer, ConnectionRegistry, RegistryCommand,
 };
 use crate::routing::load_balancing::LoadBalancingStrategy;
 use crate::routing::routing_table_provider::RoutingTableProvider;
@@ -11,8 +12,9 @@ use backoff::{ExponentialBackoff, ExponentialBackoffBuilder};
 use futures::lock::Mutex;
 use log::{debug, error};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc::Sender;
+use tokio::task::JoinHandle;
 
 #[derive(Clone)]
 pub struct RoutedConnectionManager {
@@ -22,6 +24,10 @@ pub struct RoutedConnectionManager {
     bookmarks: Arc<Mutex<Vec<String>>>,
     backoff: Arc<ExponentialBackoff>,
     channel: Sender<RegistryCommand>,
+    /// The task that refreshes the routing table, until it is awaited by [`Self::close`].
+    updater: Arc<std::sync::Mutex<Option<JoinHandle<()>>>>,
+    config: Arc<Config>,
+    provider: Arc<Box<dyn RoutingTableProvider>>,
 }
 
 impl RoutedConnectionManager {
@@ -36,36 +42,61 @@ impl RoutedConnectionManager {
         );
 
         let connection_registry = Arc::new(ConnectionRegistry::default());
-        let channel =
-            start_background_updater(config, connection_registry.clone(), provider.into());
+        let provider = Arc::new(provider);
+        let (channel, updater) =
+            start_background_updater(config, connection_registry.clone(), provider.clone());
         Ok(RoutedConnectionManager {
             load_balancing_strategy: Arc::new(RoundRobinStrategy::default()),
             bookmarks: Arc::new(Mutex::new(vec![])),
             connection_registry,
             backoff,
             channel,
+            updater: Arc::new(std::sync::Mutex::new(Some(updater))),
+            config: Arc::new(config.clone()),
+            provider,
         })
     }
 
+    /// Returns a connection to a server of the configured database or,
+    /// when impersonating without a configured database, of the home database of `imp_user`.
+    /// The connection is authenticated with `auth`, if given.
     pub(crate) async fn get(
         &self,
         operation: Option<Operation>,
+        imp_user: Option<&str>,
+        auth: Option<&AuthToken>,
     ) -> Result<ManagedConnection, Error> {
         let op = operation.unwrap_or(Operation::Write);
+        let imp_user = imp_user.filter(|_| self.config.db.is_none());
+        let mut servers = match imp_user {
+            Some(imp_user) => {
+                home_servers(
+                    &self.config,
+                    &self.connection_registry,
+                    &**self.provider,
+                    imp_user,
+                )
+                .await?
+            }
+            None => self.connection_registry.servers(),
+        };
         while let Some(server) = match op {
-            Operation::Write => self.select_writer(),
-            _ => self.select_reader(),
+            Operation::Write => self.select_writer(&servers),
+            _ => self.select_reader(&servers),
         } {
             debug!("requesting connection for server: {:?}", server);
             if let Some(pool) = self.connection_registry.get_pool(&server) {
-                match pool.get().await {
+                match get_connection(&pool, auth).await {
                     Ok(connection) => return Ok(connection),
+                    // the server is fine, but rejected the credentials of the caller
+                    Err(e @ Error::AuthenticationError(_)) if auth.is_some() => return Err(e),
                     Err(e) => {
                         error!(
                             "Failed to get connection from pool for server `{}`: {}",
                             server.address, e
                         );
                         self.connection_registry.mark_unavailable(&server);
+                        servers.retain(|s| s != &server);
                         continue;
                     }
                 }
@@ -81,6 +112,15 @@ impl RoutedConnectionManager {
                 )));
             }
         }
+        if let Some(imp_user) = imp_user {
+            debug!(
+                "Routing table of {imp_user} is empty for requested {op} operation, dropping it"
+            );
+            self.connection_registry.forget_home_table(imp_user);
+            return Err(Error::ServerUnavailableError(format!(
+                "No server available for {op} operation"
+            )));
+        }
         debug!("Routing table is empty for requested {op} operation, forcing refresh");
         self.channel
             .send(RegistryCommand::Refresh)
@@ -100,14 +140,35 @@ impl RoutedConnectionManager {
         self.backoff.as_ref().clone()
     }
 
-    fn select_reader(&self) -> Option<BoltServer> {
-        self.load_balancing_strategy
-            .select_reader(&self.connection_registry.servers())
+    /// Stops the routing table updates and closes the connections to all servers.
+    ///
+    /// The updater is given until `deadline` to finish a refresh that is in progress,
+    /// afterwards it is aborted.
+    pub(crate) async fn close(&self, deadline: Instant) {
+        if self.channel.send(RegistryCommand::Stop).await.is_err() {
+            debug!("Background updater was already stopped");
+        }
+        let updater = self.updater.lock().unwrap().take();
+        if let Some(mut updater) = updater {
+            let deadline = tokio::time::Instant::from_std(deadline);
+            match tokio::time::timeout_at(deadline, &mut updater).await {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => debug!("Background updater failed: {}", e),
+                Err(_) => {
+                    debug!("Background updater did not stop in time, aborting it");
+                    updater.abort();
+                }
+            }
+        }
+        self.connection_registry.close(deadline).await;
+    }
+
+    fn select_reader(&self, servers: &[BoltServer]) -> Option<BoltServer> {
+        self.load_balancing_strategy.select_reader(servers)
     }
 
-    fn select_writer(&self) -> Option<BoltServer> {
-        self.load_balancing_strategy
-            .select_writer(&self.connection_registry.servers())
+    fn select_writer(&self, servers: &[BoltServer]) -> Option<BoltServer> {
+        self.load_balancing_strategy.select_writer(servers)
     }
 
     #[allow(dead_code)]
@@ -1,37 +1,281 @@
-use crate::connection::{Connection, ConnectionInfo};
+use crate::connection::{Connection, ConnectionInfo, NeoUrl, Routing};
 use crate::routing::{RouteBuilder, RoutingTable};
-use crate::{Config, Error};
+use crate::{Config, Error, ServerAddress, Version};
+use log::{debug, warn};
 use std::future::Future;
 use std::pin::Pin;
+use std::sync::{Arc, Mutex};
 
 pub(crate) trait RoutingTableProvider: Send + Sync {
+    /// Fetches the routing table of the configured database or,
+    /// if none is configured, of the home database of `imp_user` or the authenticated user.
     fn fetch_routing_table(
         &self,
         config: &Config,
+        imp_user: Option<&str>,
     ) -> Pin<Box<dyn Future<Output = Result<RoutingTable, Error>> + Send>>;
 }
 
-pub struct ClusterRoutingTableProvider;
+#[derive(Default)]
+pub struct ClusterRoutingTableProvider {
+    /// The routers of the last routing table, which are asked before the initial routers.
+    routers: Arc<Mutex<Vec<NeoUrl>>>,
+}
 
 impl RoutingTableProvider for ClusterRoutingTableProvider {
     fn fetch_routing_table(
         &self,
         config: &Config,
+        imp_user: Option<&str>,
     ) -> Pin<Box<dyn Future<Output = Result<RoutingTable, Error>> + Send>> {
         let config = config.clone();
+        let imp_user = imp_user.map(str::to_owned);
+        let routers = Arc::clone(&self.routers);
         Box::pin(async move {
-            let info = ConnectionInfo::new(
-                &config.uri,
-                &config.user,
-                &config.password,
-                &config.tls_config,
-            )?;
-            let mut connection = Connection::new(&info).await?;
-            let mut builder = RouteBuilder::new(info.routing, vec![]);
-            if let Some(db) = config.db.clone() {
-                builder = builder.with_db(db);
-            }
-            connection.route(builder.build(connection.version())).await
+            let imp_user = imp_user.as_deref();
+            let known = routers.lock().unwrap().clone();
+            let mut errors = Vec::new();
+            let routing_table = match fetch_from_any(&config, &known, imp_user, &mut errors).await {
+                Some(routing_table) => routing_table,
+                None => {
+                    if !known.is_empty() {
+                        debug!("None of the known routers responded, trying the initial routers");
+                    }
+                    let initial = initial_routers(&config).await?;
+                    match &initial[..] {
+                        [router] if errors.is_empty() => {
+                            fetch_from(&config, router, imp_user).await?
+                        }
+                        _ => fetch_from_any(&config, &initial, imp_user, &mut errors)
+                            .await
+                            .ok_or_else(|| {
+                                Error::RoutingTableRefreshFailed(format!(
+                                    "Failed to fetch the routing table from any router: {}",
+                                    errors.join("; ")
+                                ))
+                            })?,
+                    }
+                }
+            };
+            *routers.lock().unwrap() = table_routers(&config, &routing_table)?;
+            Ok(routing_table)
         })
     }
 }
+
+/// The URIs of the routers to start with, as returned by the resolver.
+/// The resolver is only asked when there is no routing table yet,
+/// or when none of the routers of the last routing table responded.
+async fn initial_routers(config: &Config) -> Result<Vec<NeoUrl>, Error> {
+    let url = NeoUrl::parse(&config.uri)?;
+    let Some(resolver) = &config.resolver else {
+        return Ok(vec![url]);
+    };
+
+    let address = ServerAddress::new(url.host().to_string(), url.port());
+    let addresses = resolver.0.resolve(&address).await?;
+    debug!("Resolved {} to the routers {:?}", address, addresses);
+    if addresses.is_empty() {
+        return Ok(vec![url]);
+    }
+    addresses
+        .iter()
+        .map(|address| url.with_address(address))
+        .collect()
+}
+
+/// The URIs of the routers in `routing_table`.
+fn table_routers(config: &Config, routing_table: &RoutingTable) -> Result<Vec<NeoUrl>, Error> {
+    let url = NeoUrl::parse(&config.uri)?;
+    routing_table
+        .resolve()
+        .into_iter()
+        .filter(|server| server.role == "ROUTE")
+        .map(|server| url.with_address(&ServerAddress::new(server.address, server.port)))
+        .collect()
+}
+
+/// Asks the routers in turn, returning the first routing table.
+/// The error of every router that fails is added to `errors`.
+async fn fetch_from_any(
+    config: &Config,
+    routers: &[NeoUrl],
+    imp_user: Option<&str>,
+    errors: &mut Vec<String>,
+) -> Option<RoutingTable> {
+    for router in routers {
+        match fetch_from(config, router, imp_user).await {
+            Ok(routing_table) => return Some(routing_table),
+            Err(e) => {
+                warn!("Failed to fetch the routing table from {}: {}", router, e);
+                errors.push(format!("{}: {}", router, e));
+            }
+        }
+    }
+    None
+}
+
+async fn fetch_from(
+    config: &Config,
+    router: &NeoUrl,
+    imp_user: Option<&str>,
+) -> Result<RoutingTable, Error> {
+    let mut info = ConnectionInfo::new(
+        &router.to_string(),
+        &config.auth,
+        &config.tls_config,
+        &config.notifications,
+        &config.transport,
+    )?;
+    // the server is told the address of the URI, not the one of the router that is asked
+    info.routing = Routing::Yes(NeoUrl::parse(&config.uri)?.routing_context());
+    let mut connection = Connection::new(&info).await?;
+    let version = connection.version();
+    let mut builder = RouteBuilder::new(info.routing, vec![]);
+    if let Some(db) = config.db.clone() {
+        builder = builder.with_db(db);
+    }
+    if let Some(imp_user) = imp_user {
+        // Impersonation was introduced with 4.4
+        if version < Version::V4_4 {
+            return Err(Error::UnsupportedFeature("Impersonation", version));
+        }
+        builder = builder.with_imp_user(imp_user);
+    }
+    connection.route(builder.build(version)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routing::Server;
+    use crate::{ConfigBuilder, ResolveFuture, ServerAddressResolver};
+    use std::time::Duration;
+
+    struct Fixed(Vec<ServerAddress>);
+
+    impl ServerAddressResolver for Fixed {
+        fn resolve<'a>(&'a self, address: &'a ServerAddress) -> ResolveFuture<'a> {
+            assert_eq!(address, &ServerAddress::new("cluster.example.com", 7687));
+            Box::pin(async move { Ok(self.0.clone()) })
+        }
+    }
+
+    async fn refusing_addresses(count: usize) -> Vec<ServerAddress> {
+        let mut refusing = Vec::new();
+        for _ in 0..count {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let port = listener.local_addr().unwrap().port();
+            refusing.push(ServerAddress::new("127.0.0.1", port));
+        }
+        refusing
+    }
+
+    fn config(resolver: Option<Fixed>) -> Config {
+        let builder = ConfigBuilder::default()
+            .uri("neo4j://cluster.example.com")
+            .user("user")
+            .password("password")
+            .connect_timeout(Duration::from_secs(5));
+        match resolver {
+            Some(resolver) => builder.with_resolver(resolver),
+            None => builder,
+        }
+        .build()
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn should_use_uri_without_resolver() {
+        let routers = initial_routers(&config(None)).await.unwrap();
+
+        let routers = routers.iter().map(|r| r.to_string()).collect::<Vec<_>>();
+        assert_eq!(routers, ["neo4j://cluster.example.com"]);
+    }
+
+    #[tokio::test]
+    async fn should_use_resolved_routers() {
+        let resolver = Fixed(vec![
+            ServerAddress::new("core-1.example.com", 7688),
+            ServerAddress::new("::1", 7687),
+        ]);
+
+        let routers = initial_routers(&config(Some(resolver))).await.unwrap();
+
+        let routers = routers.iter().map(|r| r.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            routers,
+            ["neo4j://core-1.example.com:7688", "neo4j://[::1]:7687"]
+        );
+    }
+
+    #[tokio::test]
+    async fn should_use_uri_when_nothing_is_resolved() {
+        let routers = initial_routers(&config(Some(Fixed(vec![])))).await.unwrap();
+
+        let routers = routers.iter().map(|r| r.to_string()).collect::<Vec<_>>();
+        assert_eq!(routers, ["neo4j://cluster.example.com"]);
+    }
+
+    #[tokio::test]
+    async fn should_try_every_resolved_router() {
+        let refusing = refusing_addresses(2).await;
+        let config = config(Some(Fixed(refusing.clone())));
+
+        let error = ClusterRoutingTableProvider::default()
+            .fetch_routing_table(&config, None)
+            .await
+            .unwrap_err();
+
+        let Error::RoutingTableRefreshFailed(message) = error else {
+            panic!("unexpected error: {error:?}");
+        };
+        for router in refusing {
+            assert!(message.contains(&router.to_string()), "{message}");
+        }
+    }
+
+    #[tokio::test]
+    async fn should_resolve_again_when_the_known_routers_fail() {
+        let addresses = refusing_addresses(2).await;
+        let (known, resolved) = (addresses[0].clone(), addresses[1].clone());
+        let config = config(Some(Fixed(vec![resolved.clone()])));
+        let url = NeoUrl::parse(&config.uri).unwrap();
+        let provider = ClusterRoutingTableProvider::default();
+        *provider.routers.lock().unwrap() = vec![url.with_address(&known).unwrap()];
+
+        let error = provider
+            .fetch_routing_table(&config, None)
+            .await
+            .unwrap_err();
+
+        let Error::RoutingTableRefreshFailed(message) = error else {
+            panic!("unexpected error: {error:?}");
+        };
+        assert!(message.contains(&known.to_string()), "{message}");
+        assert!(message.contains(&resolved.to_string()), "{message}");
+    }
+
+    #[test]
+    fn should_remember_the_routers_of_the_routing_table() {
+        let routing_table = RoutingTable {
+            ttl: 300,
+            db: None,
+            servers: vec![
+                Server {
+                    addresses: vec!["core-1:7687".into(), "[::1]:7688".into()],
+                    role: "ROUTE".into(),
+                },
+                Server {
+                    addresses: vec!["core-2:7687".into()],
+                    role: "READ".into(),
+                },
+            ],
+        };
+
+        let routers = table_routers(&config(None), &routing_table).unwrap();
+
+        let routers = routers.iter().map(|r| r.to_string()).collect::<Vec<_>>();
+        assert_eq!(routers, ["neo4j://core-1:7687", "neo4j://[::1]:7688"]);
+    }
+}
@@ -151,7 +151,7 @@ impl Point3D {
 impl Row {
     pub fn new(fields: BoltList, data: BoltList) -> Self {
         let mut attributes = BoltMap::with_capacity(fields.len());
-        for (field, value) in fields.into_iter().zip(data.into_iter()) {
+        for (field, value) in fields.into_iter().zip(data) {
             if let Ok(key) = field.try_into() {
                 attributes.put(key, value);
             }
@@ -204,6 +204,11 @@ impl Node {
         self.inner.id.value
     }
 
+    /// Element id of the node, only available when connected to a server using Bolt 5.0 or newer
+    pub fn element_id(&self) -> Option<&str> {
+        self.inner.element_id.as_ref().map(|id| id.value.as_str())
+    }
+
     /// various labels attached to this node
     pub fn labels(&self) -> Vec<&str> {
         self.to::<crate::Labels<_>>().unwrap().0
@@ -248,6 +253,27 @@ impl Relation {
         self.inner.end_node_id.value
     }
 
+    /// Element id of the relationship, only available when connected to a server using Bolt 5.0 or newer
+    pub fn element_id(&self) -> Option<&str> {
+        self.inner.element_id.as_ref().map(|id| id.value.as_str())
+    }
+
+    /// Element id of the start node, only available when connected to a server using Bolt 5.0 or newer
+    pub fn start_node_element_id(&self) -> Option<&str> {
+        self.inner
+            .start_node_element_id
+            .as_ref()
+            .map(|id| id.value.as_str())
+    }
+
+    /// Element id of the end node, only available when connected to a server using Bolt 5.0 or newer
+    pub fn end_node_element_id(&self) -> Option<&str> {
+        self.inner
+            .end_node_element_id
+            .as_ref()
+            .map(|id| id.value.as_str())
+    }
+
     pub fn typ(&self) -> &str {
         self.to::<crate::Type<_>>().unwrap().0
     }
@@ -283,6 +309,11 @@ impl UnboundedRelation {
         self.inner.id.value
     }
 
+    /// Element id of the relationship, only available when connected to a server using Bolt 5.0 or newer
+    pub fn element_id(&self) -> Option<&str> {
+        self.inner.element_id.as_ref().map(|id| id.value.as_str())
+    }
+
     pub fn typ(&self) -> &str {
         self.to::<crate::Type<_>>().unwrap().0
     }
@@ -399,4 +430,55 @@ mod tests {
 
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn row_keeps_element_ids() {
+        let node = BoltNode::new(
+            42.into(),
+            vec![BoltType::from("Person")].into(),
+            BoltMap::default(),
+        )
+        .with_element_id("4:db:42");
+        let rel = BoltRelation::new(
+            1.into(),
+            42.into(),
+            43.into(),
+            "KNOWS".into(),
+            BoltMap::default(),
+        )
+        .with_element_ids("5:db:1", "4:db:42", "4:db:43");
+        let legacy = BoltNode::new(43.into(), BoltList::new(), BoltMap::default());
+
+        let fields = BoltList::from(vec![
+            BoltType::from("n"),
+            BoltType::from("r"),
+            BoltType::from("m"),
+        ]);
+        let data = BoltList::from(vec![
+            BoltType::Node(node),
+            BoltType::Relation(rel),
+            BoltType::Node(legacy),
+        ]);
+        let row = Row::new(fields, data);
+
+        let node = row.get::<Node>("n").unwrap();
+        assert_eq!(node.id(), 42);
+        assert_eq!(node.element_id(), Some("4:db:42"));
+
+        let rel = row.get::<Relation>("r").unwrap();
+        assert_eq!(rel.element_id(), Some("5:db:1"));
+        assert_eq!(rel.start_node_element_id(), Some("4:db:42"));
+        assert_eq!(rel.end_node_element_id(), Some("4:db:43"));
+
+        let legacy = row.get::<Node>("m").unwrap();
+        assert_eq!(legacy.element_id(), None);
+
+        #[derive(Debug, Deserialize)]
+        struct WithElementId {
+            element_id: crate::ElementId,
+        }
+
+        let with_id = row.get::<WithElementId>("n").unwrap();
+        assert_eq!(with_id.element_id, crate::ElementId("4:db:42".to_owned()));
+    }
 }
@@ -4,10 +4,11 @@ use crate::messages::{BoltRequest, BoltResponse};
 use crate::summary::{ResultSummary, Streaming};
 #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
 use crate::{
-    bolt::{Bolt, Discard, Pull, Response, Summary, WrapExtra as _},
-    BoltType,
+    bolt::{Bolt, Discard, DiscardAll, Pull, PullAll, Response, WrapExtra as _},
+    BoltType, Version,
 };
 use crate::{
+    connection::Connection,
     errors::{Error, Result},
     pool::ManagedConnection,
     row::Row,
@@ -85,67 +86,15 @@ impl RowStream {
                 return Ok(Some(row));
             }
 
-            #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
-            {
-                if self.state == State::Ready {
-                    let pull = Pull::some(self.fetch_size as i64).for_query(self.qid);
+            match self.state {
+                State::Ready => {
                     let connection = handle.connection();
-                    connection.send_as(pull).await?;
-                    self.state = loop {
-                        let response = connection
-                            .recv_as::<Response<Vec<Bolt>, Streaming>>()
-                            .await?;
-                        match response {
-                            Response::Detail(record) => {
-                                let record = BoltList::from(
-                                    record
-                                        .into_iter()
-                                        .map(BoltType::from)
-                                        .collect::<Vec<BoltType>>(),
-                                );
-                                let row = Row::new(self.fields.clone(), record);
-                                self.buffer.push_back(row);
-                            }
-                            Response::Success(Streaming::HasMore) => break State::Ready,
-                            Response::Success(Streaming::Done(mut s)) => {
-                                s.set_t_first(self.available_after);
-                                break State::Complete(s);
-                            }
-                            otherwise => return Err(otherwise.into_error("PULL")),
-                        }
-                    };
-                } else if let State::Complete(_) = self.state {
-                    break Ok(None);
+                    Self::write_pull(connection, self.fetch_size, self.qid).await?;
+                    connection.flush().await?;
+                    self.state = State::Pulling;
                 }
-            }
-
-            #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
-            {
-                if self.state == State::Ready {
-                    let pull = BoltRequest::pull(self.fetch_size, self.qid);
-                    let connection = handle.connection();
-                    connection.send(pull).await?;
-
-                    self.state = loop {
-                        match connection.recv().await {
-                            Ok(BoltResponse::Success(s)) => {
-                                break if s.get("has_more").unwrap_or(false) {
-                                    State::Ready
-                                } else {
-                                    State::Complete(())
-                                };
-                            }
-                            Ok(BoltResponse::Record(record)) => {
-                                let row = Row::new(self.fields.clone(), record.data);
-                                self.buffer.push_back(row);
-                            }
-                            Ok(msg) => return Err(msg.into_error("PULL")),
-                            Err(e) => return Err(e),
-                        }
-                    };
-                } else if let State::Complete(_) = self.state {
-                    break Ok(None);
-                };
+                State::Pulling | State::Discarding => self.receive(handle.connection()).await?,
+                State::Complete(_) => return Ok(None),
             }
         }
     }
@@ -246,55 +195,144 @@ impl RowStream {
     /// Stop consuming the stream and return a summary, if available.
     /// Stopping the stream will also discard any messages on the server side.
     pub async fn finish(mut self, mut handle: impl TransactionHandle) -> Result<RunResult> {
-        self.buffer.clear();
+        loop {
+            self.buffer.clear();
+            match self.state {
+                State::Ready => {
+                    let connection = handle.connection();
+                    Self::write_discard(connection, self.qid).await?;
+                    connection.flush().await?;
+                    self.state = State::Discarding;
+                }
+                State::Pulling | State::Discarding => self.receive(handle.connection()).await?,
+                #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+                State::Complete(summary) => return Ok(*summary),
+                #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
+                State::Complete(()) => return Ok(()),
+            }
+        }
+    }
+
+    /// Marks the first PULL as already sent, pipelined with the RUN that started this stream.
+    pub(crate) fn pulling(self) -> Self {
+        RowStream {
+            state: State::Pulling,
+            ..self
+        }
+    }
+
+    /// Marks a DISCARD as already sent, pipelined with the RUN that started this stream.
+    pub(crate) fn discarding(self) -> Self {
+        RowStream {
+            state: State::Discarding,
+            ..self
+        }
+    }
 
+    /// Reads all records of the PULL in flight into the buffer, until the result is complete.
+    pub(crate) async fn buffered(mut self, connection: &mut Connection) -> Result<Self> {
+        while matches!(self.state, State::Pulling) {
+            self.receive(connection).await?;
+        }
+        Ok(self)
+    }
+
+    /// Buffers a PULL for the next batch of records, it is sent with the next flush.
+    pub(crate) async fn write_pull(
+        connection: &mut Connection,
+        fetch_size: usize,
+        qid: i64,
+    ) -> Result<()> {
         #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
-        match self.state {
-            State::Ready => {
-                let summary = {
-                    let connected = handle.connection();
-                    connected
-                        .send_recv_as(Discard::all().for_query(self.qid))
-                        .await
-                }?;
-                let summary = match summary {
-                    Summary::Success(s) => match s.metadata {
-                        Streaming::Done(summary) => *summary,
-                        Streaming::HasMore => {
-                            unreachable!("Query returned has_more after a discard_all");
-                        }
-                    },
-                    Summary::Ignored => {
-                        return Err(Error::RequestIgnoredError);
-                    }
-                    Summary::Failure(f) => {
-                        return Err(f.into_error());
-                    }
-                };
-                Ok(summary)
+        {
+            if connection.version() < Version::V4 {
+                connection.write_as(PullAll).await
+            } else {
+                let pull = Pull::some(fetch_size as i64).for_query(qid);
+                connection.write_as(pull).await
             }
-            State::Complete(summary) => Ok(*summary),
         }
 
         #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
-        match self.state {
-            State::Ready => {
-                let summary = {
-                    let connected = handle.connection();
-                    connected
-                        .send_recv(BoltRequest::discard_all_for(self.qid))
-                        .await
-                }?;
-                let summary = match summary {
-                    crate::messages::BoltResponse::Success(_) => Ok(()),
-                    crate::messages::BoltResponse::Failure(f) => Err(Error::Neo4j(f.into_error())),
-                    msg => Err(msg.into_error("DISCARD")),
-                };
-                self.state = State::Complete(());
-                summary
+        {
+            let pull = BoltRequest::pull(fetch_size, qid, connection.version());
+            connection.write(pull).await
+        }
+    }
+
+    /// Buffers a DISCARD for all remaining records, it is sent with the next flush.
+    pub(crate) async fn write_discard(connection: &mut Connection, qid: i64) -> Result<()> {
+        #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+        {
+            if connection.version() < Version::V4 {
+                connection.write_as(DiscardAll).await
+            } else {
+                connection.write_as(Discard::all().for_query(qid)).await
             }
-            State::Complete(_) => Ok(()),
         }
+
+        #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
+        {
+            let discard = BoltRequest::discard_all_for(qid, connection.version());
+            connection.write(discard).await
+        }
+    }
+
+    /// Reads the responses to the PULL or DISCARD in flight, until its summary.
+    async fn receive(&mut self, connection: &mut Connection) -> Result<()> {
+        let request = match self.state {
+            State::Discarding => "DISCARD",
+            _ => "PULL",
+        };
+
+        #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+        {
+            self.state = loop {
+                let response = connection
+                    .recv_as::<Response<Vec<Bolt>, Streaming>>()
+                    .await?;
+                match response {
+                    Response::Detail(record) => {
+                        let record = BoltList::from(
+                            record
+                                .into_iter()
+                                .map(BoltType::from)
+                                .collect::<Vec<BoltType>>(),
+                        );
+                        let row = Row::new(self.fields.clone(), record);
+                        self.buffer.push_back(row);
+                    }
+                    Response::Success(Streaming::HasMore) => break State::Ready,
+                    Response::Success(Streaming::Done(mut s)) => {
+                        s.set_t_first(self.available_after);
+                        break State::Complete(s);
+                    }
+                    otherwise => return Err(otherwise.into_error(request)),
+                }
+            };
+        }
+
+        #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
+        {
+            self.state = loop {
+                match connection.recv().await? {
+                    BoltResponse::Success(s) => {
+                        break if s.get("has_more").unwrap_or(false) {
+                            State::Ready
+                        } else {
+                            State::Complete(())
+                        };
+                    }
+                    BoltResponse::Record(record) => {
+                        let row = Row::new(self.fields.clone(), record.data);
+                        self.buffer.push_back(row);
+                    }
+                    msg => return Err(msg.into_error(request)),
+                }
+            };
+        }
+
+        Ok(())
     }
 
     /// Turns this RowStream into a [`futures::stream::TryStream`] where
@@ -477,6 +515,11 @@ impl DetachedRowStream {
 
 #[derive(Clone, PartialEq, Debug)]
 enum State {
+    /// More records are available on the server.
     Ready,
+    /// A PULL has been sent, its records have not been read yet.
+    Pulling,
+    /// A DISCARD has been sent, its summary has not been read yet.
+    Discarding,
     Complete(BoxedSummary),
 }
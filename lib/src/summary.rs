@@ -66,6 +66,44 @@ pub struct Notification {
     pub position: Option<InputPosition>,
 }
 
+/// A GQL status object, sent by servers since Bolt 5.5 in place of the notifications.
+///
+/// Objects with a `neo4j_code` are notifications, the others describe the outcome of the query.
+#[derive(Clone, Debug, PartialEq, Eq, Default, Deserialize)]
+#[serde(from = "GqlStatusObjectWire")]
+pub struct GqlStatusObject {
+    pub gql_status: String,
+    pub status_description: String,
+    pub neo4j_code: Option<String>,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub severity: Option<NotificationSeverity>,
+    pub classification: Option<NotificationClassification>,
+    pub position: Option<InputPosition>,
+    pub operation: Option<String>,
+    pub operation_code: Option<String>,
+    pub current_schema: Option<String>,
+}
+
+impl GqlStatusObject {
+    pub fn is_notification(&self) -> bool {
+        self.neo4j_code.is_some()
+    }
+}
+
+impl From<&GqlStatusObject> for Notification {
+    fn from(value: &GqlStatusObject) -> Self {
+        Notification {
+            code: value.neo4j_code.clone(),
+            title: value.title.clone(),
+            description: value.description.clone(),
+            severity: value.severity,
+            category: value.classification,
+            position: value.position,
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Default, Deserialize)]
 #[serde(rename_all = "kebab-case", default)]
 pub struct Counters {
@@ -124,6 +162,7 @@ pub struct ResultSummary {
     pub(crate) plan: Option<Map>,
     pub(crate) profile: Option<Map>,
     pub notifications: Vec<Notification>,
+    pub gql_status_objects: Vec<GqlStatusObject>,
 }
 
 impl ResultSummary {
@@ -147,10 +186,17 @@ impl ResultSummary {
         &self.stats
     }
 
+    /// The notifications of the query.
+    /// Since Bolt 5.5, they are taken from the GQL status objects that are notifications.
     pub fn notifications(&self) -> &[Notification] {
         &self.notifications
     }
 
+    /// The GQL status objects of the query, only sent by servers since Bolt 5.5.
+    pub fn gql_status_objects(&self) -> &[GqlStatusObject] {
+        &self.gql_status_objects
+    }
+
     pub fn nodes_created(&self) -> u64 {
         self.stats.nodes_created
     }
@@ -217,6 +263,53 @@ struct NotificationWire {
     position: Option<InputPosition>,
 }
 
+#[derive(Deserialize)]
+struct GqlStatusObjectWire {
+    #[serde(default)]
+    gql_status: String,
+    #[serde(default)]
+    status_description: String,
+    neo4j_code: Option<String>,
+    title: Option<String>,
+    description: Option<String>,
+    diagnostic_record: Option<DiagnosticRecordWire>,
+}
+
+#[derive(Default, Deserialize)]
+struct DiagnosticRecordWire {
+    #[serde(rename = "OPERATION")]
+    operation: Option<String>,
+    #[serde(rename = "OPERATION_CODE")]
+    operation_code: Option<String>,
+    #[serde(rename = "CURRENT_SCHEMA")]
+    current_schema: Option<String>,
+    #[serde(rename = "_severity")]
+    severity: Option<Optional<NotificationSeverity>>,
+    #[serde(rename = "_classification")]
+    classification: Option<Optional<NotificationClassification>>,
+    #[serde(rename = "_position")]
+    position: Option<InputPosition>,
+}
+
+impl From<GqlStatusObjectWire> for GqlStatusObject {
+    fn from(value: GqlStatusObjectWire) -> Self {
+        let record = value.diagnostic_record.unwrap_or_default();
+        GqlStatusObject {
+            gql_status: value.gql_status,
+            status_description: value.status_description,
+            neo4j_code: value.neo4j_code,
+            title: value.title,
+            description: value.description,
+            severity: record.severity.and_then(Optional::into_option),
+            classification: record.classification.and_then(Optional::into_option),
+            position: record.position,
+            operation: record.operation,
+            operation_code: record.operation_code,
+            current_schema: record.current_schema,
+        }
+    }
+}
+
 impl From<NotificationWire> for Notification {
     fn from(value: NotificationWire) -> Self {
         Notification {
@@ -241,6 +334,7 @@ struct SummaryBuilder {
     plan: Option<Map>,
     profile: Option<Map>,
     notifications: Option<Vec<Notification>>,
+    statuses: Option<Vec<GqlStatusObject>>,
 }
 
 impl From<SummaryBuilder> for Streaming {
@@ -255,11 +349,21 @@ impl From<SummaryBuilder> for Streaming {
             plan,
             profile,
             notifications,
+            statuses,
         } = value;
 
         if has_more.unwrap_or(false) {
             Streaming::HasMore
         } else {
+            let gql_status_objects = statuses.unwrap_or_default();
+            let notifications = notifications.unwrap_or_else(|| {
+                gql_status_objects
+                    .iter()
+                    .filter(|s| s.is_notification())
+                    .map(Notification::from)
+                    .collect()
+            });
+
             Streaming::Done(Box::new(ResultSummary {
                 bookmark,
                 t_first: None,
@@ -269,7 +373,8 @@ impl From<SummaryBuilder> for Streaming {
                 stats: stats.unwrap_or_default(),
                 plan,
                 profile,
-                notifications: notifications.unwrap_or_default(),
+                notifications,
+                gql_status_objects,
             }))
         }
     }
@@ -547,6 +652,7 @@ impl<'de> Deserialize<'de> for SummaryBuilder {
                     plan,
                     profile,
                     notifications,
+                    statuses,
                 );
 
                 Ok(SummaryBuilder {
@@ -559,6 +665,7 @@ impl<'de> Deserialize<'de> for SummaryBuilder {
                     plan,
                     profile,
                     notifications,
+                    statuses,
                 })
             }
         }
@@ -575,6 +682,7 @@ impl<'de> Deserialize<'de> for SummaryBuilder {
                 "plan",
                 "profile",
                 "notifications",
+                "statuses",
             ],
             Visit,
         )
@@ -636,6 +744,7 @@ mod tests {
             plan: None,
             profile: None,
             notifications: Vec::new(),
+            gql_status_objects: Vec::new(),
         };
 
         let actual = from_bytes::<Streaming>(data).unwrap();
@@ -647,6 +756,89 @@ mod tests {
         assert_eq!(*actual, expected);
     }
 
+    #[test]
+    fn parse_gql_status_objects() {
+        let data = bolt()
+            .tiny_map(1)
+            .tiny_string("statuses")
+            .tiny_list(2)
+            .tiny_map(3)
+            .tiny_string("gql_status")
+            .tiny_string("00000")
+            .string8("status_description")
+            .string8("note: successful completion")
+            .string8("diagnostic_record")
+            .tiny_map(3)
+            .tiny_string("OPERATION")
+            .tiny_string("")
+            .string8("OPERATION_CODE")
+            .tiny_string("0")
+            .string8("CURRENT_SCHEMA")
+            .tiny_string("/")
+            .tiny_map(6)
+            .tiny_string("gql_status")
+            .tiny_string("01N42")
+            .string8("status_description")
+            .string8("warn: feature deprecated.")
+            .tiny_string("neo4j_code")
+            .string8("Neo.ClientNotification.Statement.FeatureDeprecationWarning")
+            .tiny_string("title")
+            .string8("This feature is deprecated.")
+            .tiny_string("description")
+            .string8("The query used a deprecated feature.")
+            .string8("diagnostic_record")
+            .tiny_map(3)
+            .tiny_string("_severity")
+            .tiny_string("WARNING")
+            .string8("_classification")
+            .tiny_string("DEPRECATION")
+            .tiny_string("_position")
+            .tiny_map(3)
+            .tiny_string("offset")
+            .tiny_int(0)
+            .tiny_string("line")
+            .tiny_int(1)
+            .tiny_string("column")
+            .tiny_int(1)
+            .build();
+
+        let actual = from_bytes::<Streaming>(data).unwrap();
+        let actual = match actual {
+            Streaming::Done(actual) => actual,
+            _ => panic!("Expected done"),
+        };
+
+        let [success, deprecation] = actual.gql_status_objects() else {
+            panic!("Expected two status objects");
+        };
+
+        assert_eq!(success.gql_status, "00000");
+        assert_eq!(success.current_schema.as_deref(), Some("/"));
+        assert!(!success.is_notification());
+
+        assert_eq!(deprecation.gql_status, "01N42");
+        assert_eq!(deprecation.severity, Some(NotificationSeverity::Warning));
+        assert_eq!(
+            deprecation.classification,
+            Some(NotificationClassification::Deprecation)
+        );
+        assert!(deprecation.is_notification());
+
+        let expected = Notification {
+            code: Some("Neo.ClientNotification.Statement.FeatureDeprecationWarning".to_owned()),
+            title: Some("This feature is deprecated.".to_owned()),
+            description: Some("The query used a deprecated feature.".to_owned()),
+            severity: Some(NotificationSeverity::Warning),
+            category: Some(NotificationClassification::Deprecation),
+            position: Some(InputPosition {
+                offset: 0,
+                line: 1,
+                column: 1,
+            }),
+        };
+        assert_eq!(actual.notifications(), [expected]);
+    }
+
     #[test]
     fn parse_severity() {
         let data = bolt().tiny_string("WARNING").build();
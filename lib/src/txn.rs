@@ -1,43 +1,208 @@
 #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
-use crate::bolt::{Commit, Rollback, Summary};
+use crate::bolt::{Begin, Commit, ExpectedResponse, Rollback, Summary, TxExtra};
 use crate::{
-    config::Database,
+    auth::AuthToken,
+    config::{Database, NotificationFilter},
     errors::Result,
-    messages::{BoltRequest, BoltResponse},
+    messages::TelemetryApi,
     pool::ManagedConnection,
-    query::Query,
+    query::{BeginRequest, Query},
     stream::RowStream,
+    version::Encoding,
     Operation, RunResult,
 };
+#[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
+use crate::{
+    messages::{BoltRequest, BoltResponse},
+    Version,
+};
 
 /// A handle which is used to control a transaction, created as a result of [`crate::Graph::start_txn`]
 ///
 /// When a transation is started, a dedicated connection is resered and moved into the handle which
 /// will be released to the connection pool when the [`Txn`] handle is dropped.
+///
+/// The transaction is only started on the server together with the first query,
+/// so that BEGIN does not cost an extra round trip.
+/// The records of [`Txn::execute`] are pulled once the stream is read,
+/// so several streams of one transaction can be open at the same time.
+/// Bolt 3 cannot tell the streams apart, so there all records are read by [`Txn::execute`] already.
 pub struct Txn {
-    db: Option<Database>,
     fetch_size: usize,
     connection: ManagedConnection,
+    config: TxConfig,
+    started: bool,
+}
+
+/// The options to start a transaction with, see [`crate::Graph::start_txn_with`].
+///
+/// Every option that is not set uses the value of [`crate::Config`].
+///
+/// # Example
+///
+/// ```
+/// use neo4rs::{AuthToken, NotificationFilter, TxnOptions};
+///
+/// let options = TxnOptions::new()
+///     .with_db("movies")
+///     .with_impersonated_user("alice")
+///     .with_auth(AuthToken::bearer("eyJhbGciOiJIUzI1NiJ9..."))
+///     .with_notification_filter(NotificationFilter::disable_all());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct TxnOptions {
+    pub(crate) operation: Option<Operation>,
+    pub(crate) db: Option<Database>,
+    pub(crate) imp_user: Option<String>,
+    pub(crate) auth: Option<AuthToken>,
+    pub(crate) notifications: Option<NotificationFilter>,
+}
+
+impl TxnOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the transaction reads or writes, which decides the server it runs on in a cluster.
+    /// A transaction writes by default.
+    pub fn with_operation(mut self, operation: Operation) -> Self {
+        self.operation = Some(operation);
+        self
+    }
+
+    /// Run the transaction on the given database instead of the configured one.
+    pub fn with_db(mut self, db: impl Into<Database>) -> Self {
+        self.db = Some(db.into());
+        self
+    }
+
+    /// Run the transaction on behalf of another user,
+    /// with the privileges and, unless a database is set, the home database of that user.
+    ///
+    /// The authenticated user needs the `IMPERSONATE` privilege, and the server at least Neo4j 4.4.
+    pub fn with_impersonated_user(mut self, user: impl Into<String>) -> Self {
+        self.imp_user = Some(user.into());
+        self
+    }
+
+    /// Run the transaction with the credentials of another user, instead of the ones in [`crate::Config`].
+    ///
+    /// Since Neo4j 5.5 (Bolt 5.1), a pooled connection authenticates again with the token,
+    /// older servers get a separate pool of connections for every token.
+    pub fn with_auth(mut self, auth: AuthToken) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    /// Only receive the notifications allowed by the given filter, instead of the one in [`crate::Config`].
+    pub fn with_notification_filter(mut self, notifications: NotificationFilter) -> Self {
+        self.notifications = Some(notifications);
+        self
+    }
+}
+
+/// What the transaction is started with, sent as BEGIN along with the first query.
+struct TxConfig {
+    db: Option<Database>,
+    imp_user: Option<String>,
     operation: Operation,
+    notifications: Option<NotificationFilter>,
+}
+
+impl TxConfig {
+    /// The BEGIN to send along with the next query, if the transaction is not started yet.
+    ///
+    /// The TELEMETRY of the transaction is written right before, so that it is sent along as well.
+    async fn begin_once(
+        &self,
+        started: &mut bool,
+        connection: &mut ManagedConnection,
+    ) -> Result<Option<BeginRequest<'_>>> {
+        if std::mem::replace(started, true) {
+            return Ok(None);
+        }
+        let begin = self.begin(connection.encoding())?;
+        connection
+            .write_telemetry(TelemetryApi::UnmanagedTransaction)
+            .await?;
+        Ok(Some(begin))
+    }
+
+    #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
+    fn begin(&self, encoding: Encoding) -> Result<BeginRequest<'_>> {
+        let version = encoding.version;
+        let notifications = self
+            .notifications
+            .as_ref()
+            .filter(|_| version >= Version::V5_2);
+        BoltRequest::begin(
+            self.db.as_deref(),
+            self.imp_user.as_deref(),
+            notifications,
+            version,
+        )
+    }
+
+    #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+    fn begin(&self, encoding: Encoding) -> Result<BeginRequest<'_>> {
+        let mut extra = TxExtra::new().with_mode(self.operation.clone());
+        if let Some(db) = self.db.as_deref() {
+            extra = extra.with_db(db);
+        }
+        if let Some(imp_user) = self.imp_user.as_deref() {
+            extra = extra.with_imp_user(imp_user);
+        }
+        if let Some(notifications) = self.notifications.as_ref() {
+            extra = extra.with_notification_filter(notifications);
+        }
+        Begin::with_encoding(extra, encoding)
+    }
+
+    #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
+    fn query(&self, query: Query) -> Query {
+        let query = match self.db.as_ref() {
+            Some(db) => query.extra("db", db.to_string()),
+            None => query,
+        };
+        query.extra(
+            "mode",
+            match self.operation {
+                Operation::Read => "r",
+                Operation::Write => "w",
+            },
+        )
+    }
+
+    /// The database and access mode are part of the BEGIN and don't need to be repeated.
+    #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+    fn query(&self, query: Query) -> Query {
+        query
+    }
 }
 
 impl Txn {
     pub(crate) async fn new(
         db: Option<Database>,
+        imp_user: Option<String>,
         fetch_size: usize,
-        mut connection: ManagedConnection,
+        connection: ManagedConnection,
         operation: Operation,
+        notifications: Option<&NotificationFilter>,
     ) -> Result<Self> {
-        let begin = BoltRequest::begin(db.as_deref());
-        match connection.send_recv(begin).await? {
-            BoltResponse::Success(_) => Ok(Txn {
-                db,
-                fetch_size,
-                connection,
-                operation,
-            }),
-            msg => Err(msg.into_error("BEGIN")),
-        }
+        let config = TxConfig {
+            db,
+            imp_user,
+            operation,
+            notifications: notifications.cloned(),
+        };
+        // fail early if the server cannot start such a transaction
+        config.begin(connection.encoding())?;
+        Ok(Txn {
+            fetch_size,
+            connection,
+            config,
+            started: false,
+        })
     }
 
     #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
@@ -69,44 +234,56 @@ impl Txn {
 
     /// Runs a single query and discards the stream.
     pub async fn run(&mut self, q: impl Into<Query>) -> Result<RunResult> {
-        let mut query = q.into();
-        if let Some(db) = self.db.as_ref() {
-            query = query.extra("db", db.to_string());
-        }
-        query = query.extra(
-            "mode",
-            match self.operation {
-                Operation::Read => "r",
-                Operation::Write => "w",
-            },
-        );
-        query.run(&mut self.connection).await
+        self.connection.skip_pending().await?;
+        let query = self.config.query(q.into());
+        let begin = self
+            .config
+            .begin_once(&mut self.started, &mut self.connection)
+            .await?;
+        query.run(&mut self.connection, begin).await
     }
 
     /// Executes a query and returns a [`RowStream`]
     pub async fn execute(&mut self, q: impl Into<Query>) -> Result<RowStream> {
-        let mut query = q.into();
-        if let Some(db) = self.db.as_ref() {
-            query = query.extra("db", db.to_string());
-        }
-        query = query.extra(
-            "mode",
-            match self.operation {
-                Operation::Read => "r",
-                Operation::Write => "w",
-            },
-        );
+        self.connection.skip_pending().await?;
+        let query = self.config.query(q.into());
+        let begin = self
+            .config
+            .begin_once(&mut self.started, &mut self.connection)
+            .await?;
         query
-            .execute_mut(self.fetch_size, &mut self.connection)
+            .execute_mut(self.fetch_size, &mut self.connection, begin)
             .await
     }
 
     /// Commits the transaction in progress
+    ///
+    /// If no query was run, the transaction is started and committed right away,
+    /// so that a database or user that is not valid is still reported.
     pub async fn commit(mut self) -> Result<()> {
+        self.connection.skip_pending().await?;
+        let begin = self
+            .config
+            .begin_once(&mut self.started, &mut self.connection)
+            .await?;
+        let has_begin = begin.is_some();
+
         #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
         {
-            let commit = BoltRequest::commit();
-            match self.connection.send_recv(commit).await? {
+            if let Some(begin) = begin {
+                self.connection.write(begin).await?;
+            }
+            self.connection.send(BoltRequest::commit()).await?;
+            if has_begin {
+                match self.connection.recv().await? {
+                    BoltResponse::Success(_) => {}
+                    msg => {
+                        self.connection.skip_pending().await?;
+                        return Err(msg.into_error("BEGIN"));
+                    }
+                }
+            }
+            match self.connection.recv().await? {
                 BoltResponse::Success(_) => Ok(()),
                 msg => Err(msg.into_error("COMMIT")),
             }
@@ -114,7 +291,28 @@ impl Txn {
 
         #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
         {
-            match self.connection.send_recv_as(Commit).await? {
+            if let Some(begin) = begin {
+                self.connection.write_as(begin).await?;
+            }
+            self.connection.send_as(Commit).await?;
+            if has_begin {
+                match self
+                    .connection
+                    .recv_as::<<Begin as ExpectedResponse>::Response>()
+                    .await?
+                {
+                    Summary::Success(_) => {}
+                    msg => {
+                        self.connection.skip_pending().await?;
+                        return Err(msg.into_error("BEGIN"));
+                    }
+                }
+            }
+            match self
+                .connection
+                .recv_as::<<Commit as ExpectedResponse>::Response>()
+                .await?
+            {
                 Summary::Success(_) => Ok(()),
                 msg => Err(msg.into_error("COMMIT")),
             }
@@ -122,7 +320,14 @@ impl Txn {
     }
 
     /// rollback/abort the current transaction
+    ///
+    /// If no query was run, the transaction was never started on the server and nothing is sent.
     pub async fn rollback(mut self) -> Result<()> {
+        if !self.started {
+            return Ok(());
+        }
+        self.connection.skip_pending().await?;
+
         #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
         {
             let rollback = BoltRequest::rollback();
@@ -182,3 +387,166 @@ pub(crate) mod private {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use tokio::{
+        io::{duplex, AsyncReadExt, AsyncWriteExt, DuplexStream},
+        task::JoinHandle,
+    };
+
+    use super::*;
+    use crate::{pool::create_pool, ConfigBuilder, ConnectFuture, Connector, Transport, Version};
+
+    struct InMemory(Mutex<Option<DuplexStream>>);
+
+    impl Connector for InMemory {
+        fn connect<'a>(&'a self, _host: &'a str, _port: u16) -> ConnectFuture<'a> {
+            let client = self.0.lock().unwrap().take().unwrap();
+            Box::pin(async move { Ok(Box::new(client) as Box<dyn Transport>) })
+        }
+    }
+
+    const SUCCESS: &[u8] = &[0xB1, 0x70, 0xA0];
+
+    const HELLO: &[u8] = &[
+        0xB1, 0x70, 0xA2, 0x86, b's', b'e', b'r', b'v', b'e', b'r', 0x81, b'N', 0x8D, b'c', b'o',
+        b'n', b'n', b'e', b'c', b't', b'i', b'o', b'n', b'_', b'i', b'd', 0x81, b'1',
+    ];
+
+    /// Speaks the given version and answers every request after the HELLO with the next responses of the script.
+    /// Returns the signatures of the requests that were received.
+    async fn serve(
+        mut server: DuplexStream,
+        version: Version,
+        script: Vec<Vec<Vec<u8>>>,
+    ) -> Vec<u8> {
+        let mut handshake = [0; 20];
+        server.read_exact(&mut handshake).await.unwrap();
+        server.write_all(&version.to_bytes()).await.unwrap();
+
+        let mut signatures = Vec::new();
+        for responses in [vec![HELLO.to_vec()]].into_iter().chain(script) {
+            let mut message = Vec::new();
+            loop {
+                let size = usize::from(server.read_u16().await.unwrap());
+                if size == 0 {
+                    break;
+                }
+                let mut chunk = vec![0; size];
+                server.read_exact(&mut chunk).await.unwrap();
+                message.extend(chunk);
+            }
+            signatures.push(message[1]);
+            for response in responses {
+                server.write_u16(response.len() as u16).await.unwrap();
+                server.write_all(&response).await.unwrap();
+                server.write_u16(0).await.unwrap();
+            }
+        }
+        signatures
+    }
+
+    /// Starts a transaction against a server of the given version that follows the script.
+    async fn start_txn(version: Version, script: Vec<Vec<Vec<u8>>>) -> (Txn, JoinHandle<Vec<u8>>) {
+        let (client, server) = duplex(1024);
+        let config = ConfigBuilder::default()
+            .uri("bolt://localhost:7687")
+            .user("user")
+            .password("pass")
+            .min_bolt_version(Version::V3)
+            .with_connector(InMemory(Mutex::new(Some(client))))
+            .build()
+            .unwrap();
+        let server = tokio::spawn(serve(server, version, script));
+
+        let connection = create_pool(&config).unwrap().get().await.unwrap();
+        let txn = Txn::new(None, None, 200, connection, Operation::Write, None)
+            .await
+            .unwrap();
+        (txn, server)
+    }
+
+    fn run_success(qid: u8) -> Vec<u8> {
+        let fields = [0x86, b'f', b'i', b'e', b'l', b'd', b's', 0x91, 0x81, b'n'];
+        [
+            &[0xB1, 0x70, 0xA2][..],
+            &fields,
+            &[0x83, b'q', b'i', b'd', qid],
+        ]
+        .concat()
+    }
+
+    #[tokio::test]
+    async fn should_read_overlapping_streams_and_commit() {
+        let record = vec![0xB1, 0x71, 0x91, 0x01];
+        let (mut txn, server) = start_txn(
+            Version::V4_4,
+            vec![
+                vec![SUCCESS.to_vec()],         // BEGIN
+                vec![run_success(0)],           // RUN
+                vec![run_success(1)],           // RUN
+                vec![record, SUCCESS.to_vec()], // PULL of the first query
+                vec![SUCCESS.to_vec()],         // COMMIT
+            ],
+        )
+        .await;
+
+        let mut first = txn.execute("RETURN 1 AS n").await.unwrap();
+        let _second = txn.execute("RETURN 2 AS n").await.unwrap();
+        let row = first.next(txn.handle()).await.unwrap().unwrap();
+        assert_eq!(row.get::<i64>("n").unwrap(), 1);
+        assert!(first.next(txn.handle()).await.unwrap().is_none());
+        txn.commit().await.unwrap();
+
+        assert_eq!(server.await.unwrap(), [0x01, 0x11, 0x10, 0x10, 0x3F, 0x12]);
+    }
+
+    #[tokio::test]
+    async fn should_begin_and_commit_an_empty_transaction() {
+        let (txn, server) = start_txn(
+            Version::V4_4,
+            vec![vec![SUCCESS.to_vec()], vec![SUCCESS.to_vec()]],
+        )
+        .await;
+
+        txn.commit().await.unwrap();
+
+        assert_eq!(server.await.unwrap(), [0x01, 0x11, 0x12]);
+    }
+
+    #[tokio::test]
+    async fn should_read_overlapping_streams_on_bolt_3() {
+        let run_success = vec![
+            0xB1, 0x70, 0xA1, 0x86, b'f', b'i', b'e', b'l', b'd', b's', 0x91, 0x81, b'n',
+        ];
+        let (mut txn, server) = start_txn(
+            Version::V3,
+            vec![
+                vec![SUCCESS.to_vec()],                               // BEGIN
+                vec![run_success.clone()],                            // RUN
+                vec![vec![0xB1, 0x71, 0x91, 0x01], SUCCESS.to_vec()], // PULL_ALL
+                vec![run_success],                                    // RUN
+                vec![vec![0xB1, 0x71, 0x91, 0x02], SUCCESS.to_vec()], // PULL_ALL
+                vec![SUCCESS.to_vec()],                               // COMMIT
+            ],
+        )
+        .await;
+
+        let mut first = txn.execute("RETURN 1 AS n").await.unwrap();
+        let mut second = txn.execute("RETURN 2 AS n").await.unwrap();
+        for (stream, n) in [(&mut first, 1), (&mut second, 2)] {
+            let row = stream.next(txn.handle()).await.unwrap().unwrap();
+            assert_eq!(row.get::<i64>("n").unwrap(), n);
+            assert!(stream.next(txn.handle()).await.unwrap().is_none());
+        }
+        txn.commit().await.unwrap();
+
+        assert_eq!(
+            server.await.unwrap(),
+            [0x01, 0x11, 0x10, 0x3F, 0x10, 0x3F, 0x12]
+        );
+    }
+}
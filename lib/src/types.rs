@@ -15,6 +15,7 @@ pub mod relation;
 pub(crate) mod serde;
 pub mod string;
 pub mod time;
+pub mod vector;
 pub use self::time::{BoltLocalTime, BoltTime};
 mod wire;
 pub use binary::BoltBytes;
@@ -32,11 +33,12 @@ pub use path::BoltPath;
 pub use point::{BoltPoint2D, BoltPoint3D};
 pub use relation::{BoltRelation, BoltUnboundedRelation};
 pub use string::BoltString;
+pub use vector::BoltVector;
 pub(crate) use wire::BoltWireFormat;
 
 use crate::{
     errors::{Error, Result},
-    version::Version,
+    version::{Encoding, Version},
 };
 use bytes::{Bytes, BytesMut};
 use std::fmt::Display;
@@ -64,6 +66,7 @@ pub enum BoltType {
     DateTime(BoltDateTime),
     LocalDateTime(BoltLocalDateTime),
     DateTimeZoneId(BoltDateTimeZoneId),
+    Vector(BoltVector),
 }
 
 impl Display for BoltType {
@@ -76,29 +79,30 @@ impl Display for BoltType {
 }
 
 impl BoltType {
-    fn write_into(&self, version: Version, bytes: &mut BytesMut) -> Result<()> {
+    fn write_into(&self, encoding: Encoding, bytes: &mut BytesMut) -> Result<()> {
         match self {
-            BoltType::Null(t) => t.write_into(version, bytes),
-            BoltType::Boolean(t) => t.write_into(version, bytes),
-            BoltType::Integer(t) => t.write_into(version, bytes),
-            BoltType::Float(t) => t.write_into(version, bytes),
-            BoltType::String(t) => t.write_into(version, bytes),
-            BoltType::List(t) => t.write_into(version, bytes),
-            BoltType::Point2D(t) => t.write_into(version, bytes),
-            BoltType::Point3D(t) => t.write_into(version, bytes),
-            BoltType::Map(t) => t.write_into(version, bytes),
-            BoltType::Node(t) => t.write_into(version, bytes),
-            BoltType::Path(t) => t.write_into(version, bytes),
-            BoltType::Relation(t) => t.write_into(version, bytes),
-            BoltType::UnboundedRelation(t) => t.write_into(version, bytes),
-            BoltType::Bytes(t) => t.write_into(version, bytes),
-            BoltType::Duration(t) => t.write_into(version, bytes),
-            BoltType::Date(t) => t.write_into(version, bytes),
-            BoltType::Time(t) => t.write_into(version, bytes),
-            BoltType::LocalTime(t) => t.write_into(version, bytes),
-            BoltType::DateTime(t) => t.write_into(version, bytes),
-            BoltType::LocalDateTime(t) => t.write_into(version, bytes),
-            BoltType::DateTimeZoneId(t) => t.write_into(version, bytes),
+            BoltType::Null(t) => t.write_into(encoding, bytes),
+            BoltType::Boolean(t) => t.write_into(encoding, bytes),
+            BoltType::Integer(t) => t.write_into(encoding, bytes),
+            BoltType::Float(t) => t.write_into(encoding, bytes),
+            BoltType::String(t) => t.write_into(encoding, bytes),
+            BoltType::List(t) => t.write_into(encoding, bytes),
+            BoltType::Point2D(t) => t.write_into(encoding, bytes),
+            BoltType::Point3D(t) => t.write_into(encoding, bytes),
+            BoltType::Map(t) => t.write_into(encoding, bytes),
+            BoltType::Node(t) => t.write_into(encoding, bytes),
+            BoltType::Path(t) => t.write_into(encoding, bytes),
+            BoltType::Relation(t) => t.write_into(encoding, bytes),
+            BoltType::UnboundedRelation(t) => t.write_into(encoding, bytes),
+            BoltType::Bytes(t) => t.write_into(encoding, bytes),
+            BoltType::Duration(t) => t.write_into(encoding, bytes),
+            BoltType::Date(t) => t.write_into(encoding, bytes),
+            BoltType::Time(t) => t.write_into(encoding, bytes),
+            BoltType::LocalTime(t) => t.write_into(encoding, bytes),
+            BoltType::DateTime(t) => t.write_into(encoding, bytes),
+            BoltType::LocalDateTime(t) => t.write_into(encoding, bytes),
+            BoltType::DateTimeZoneId(t) => t.write_into(encoding, bytes),
+            BoltType::Vector(t) => t.write_into(encoding, bytes),
         }
     }
 
@@ -167,6 +171,9 @@ impl BoltType {
             input if BoltRelation::can_parse(version, input) => {
                 BoltType::Relation(BoltRelation::parse(version, input)?)
             }
+            input if BoltVector::can_parse(version, input) => {
+                BoltType::Vector(BoltVector::parse(version, input)?)
+            }
             _ => return Err(Error::UnknownType(format!("{:#04X?}", input))),
         };
         Ok(bolt_type)
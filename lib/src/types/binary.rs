@@ -1,7 +1,7 @@
 use crate::{
     errors::{Error, Result},
     types::BoltWireFormat,
-    version::Version,
+    version::{Encoding, Version},
 };
 use bytes::{Buf, BufMut, Bytes, BytesMut};
 use std::mem;
@@ -54,7 +54,7 @@ impl BoltWireFormat for BoltBytes {
         Ok(BoltBytes::new(bytes))
     }
 
-    fn write_into(&self, _version: Version, bytes: &mut BytesMut) -> Result<()> {
+    fn write_into(&self, _encoding: Encoding, bytes: &mut BytesMut) -> Result<()> {
         match &self.value {
             value if value.len() <= 255 => {
                 bytes.reserve(2 + self.value.len());
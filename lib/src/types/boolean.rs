@@ -1,7 +1,7 @@
 use crate::{
     errors::{Error, Result},
     types::BoltWireFormat,
-    version::Version,
+    version::{Encoding, Version},
 };
 use bytes::{Buf, BufMut, Bytes, BytesMut};
 
@@ -34,7 +34,7 @@ impl BoltWireFormat for BoltBoolean {
         }
     }
 
-    fn write_into(&self, _version: Version, bytes: &mut BytesMut) -> Result<()> {
+    fn write_into(&self, _encoding: Encoding, bytes: &mut BytesMut) -> Result<()> {
         let value = if self.value { TRUE } else { FALSE };
         bytes.reserve(1);
         bytes.put_u8(value);
@@ -1,11 +1,19 @@
 use crate::errors::Error;
 use crate::types::*;
-use chrono::{DateTime, FixedOffset, NaiveDateTime, Offset, Timelike};
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use chrono::{DateTime, FixedOffset, NaiveDateTime, Offset, TimeZone, Timelike};
 use neo4rs_macros::BoltStruct;
 use std::convert::TryInto;
 
-#[derive(Debug, PartialEq, Eq, Clone, BoltStruct)]
-#[signature(0xB3, 0x46)]
+const MARKER: u8 = 0xB3;
+const DATE_TIME_SIGNATURE: u8 = 0x46;
+const DATE_TIME_UTC_SIGNATURE: u8 = 0x49;
+const DATE_TIME_ZONE_ID_SIGNATURE: u8 = 0x66;
+const DATE_TIME_ZONE_ID_UTC_SIGNATURE: u8 = 0x69;
+
+/// Holds the seconds since the epoch in local time, as sent by the legacy
+/// signature. The UTC based signature is converted on the wire.
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct BoltDateTime {
     pub(crate) seconds: BoltInteger,
     pub(crate) nanoseconds: BoltInteger,
@@ -19,12 +27,112 @@ pub struct BoltLocalDateTime {
     pub(crate) nanoseconds: BoltInteger,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, BoltStruct)]
-#[signature(0xB3, 0x66)]
+/// Holds the seconds since the epoch in local time, as sent by the legacy
+/// signature. The UTC based signature is converted on the wire.
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct BoltDateTimeZoneId {
     pub(crate) seconds: BoltInteger,
     pub(crate) nanoseconds: BoltInteger,
     pub(crate) tz_id: BoltString,
+    /// The offset from UTC, if it is known.
+    /// It tells apart the two instants of a local time that occurs twice when DST ends.
+    pub(crate) offset_seconds: Option<i32>,
+}
+
+impl BoltWireFormat for BoltDateTime {
+    fn can_parse(_version: Version, input: &[u8]) -> bool {
+        input.len() >= 2
+            && input[0] == MARKER
+            && (input[1] == DATE_TIME_SIGNATURE || input[1] == DATE_TIME_UTC_SIGNATURE)
+    }
+
+    fn parse(version: Version, input: &mut Bytes) -> Result<Self> {
+        input.get_u8();
+        let signature = input.get_u8();
+        let seconds = BoltInteger::parse(version, input)?;
+        let nanoseconds = BoltInteger::parse(version, input)?;
+        let tz_offset_seconds = BoltInteger::parse(version, input)?;
+        let seconds = if signature == DATE_TIME_UTC_SIGNATURE {
+            BoltInteger::new(seconds.value + tz_offset_seconds.value)
+        } else {
+            seconds
+        };
+        Ok(BoltDateTime {
+            seconds,
+            nanoseconds,
+            tz_offset_seconds,
+        })
+    }
+
+    fn write_into(&self, encoding: Encoding, bytes: &mut BytesMut) -> Result<()> {
+        bytes.reserve(2);
+        bytes.put_u8(MARKER);
+        if encoding.utc {
+            bytes.put_u8(DATE_TIME_UTC_SIGNATURE);
+            BoltInteger::new(self.seconds.value - self.tz_offset_seconds.value)
+                .write_into(encoding, bytes)?;
+        } else {
+            bytes.put_u8(DATE_TIME_SIGNATURE);
+            self.seconds.write_into(encoding, bytes)?;
+        }
+        self.nanoseconds.write_into(encoding, bytes)?;
+        self.tz_offset_seconds.write_into(encoding, bytes)?;
+        Ok(())
+    }
+}
+
+impl BoltWireFormat for BoltDateTimeZoneId {
+    fn can_parse(_version: Version, input: &[u8]) -> bool {
+        input.len() >= 2
+            && input[0] == MARKER
+            && (input[1] == DATE_TIME_ZONE_ID_SIGNATURE
+                || input[1] == DATE_TIME_ZONE_ID_UTC_SIGNATURE)
+    }
+
+    fn parse(version: Version, input: &mut Bytes) -> Result<Self> {
+        input.get_u8();
+        let signature = input.get_u8();
+        let seconds = BoltInteger::parse(version, input)?;
+        let nanoseconds = BoltInteger::parse(version, input)?;
+        let tz_id = BoltString::parse(version, input)?;
+        if signature == DATE_TIME_ZONE_ID_UTC_SIGNATURE {
+            Self::from_utc(seconds, nanoseconds, tz_id)
+        } else {
+            Ok(Self::from_local(seconds, nanoseconds, tz_id))
+        }
+    }
+
+    fn write_into(&self, encoding: Encoding, bytes: &mut BytesMut) -> Result<()> {
+        bytes.reserve(2);
+        bytes.put_u8(MARKER);
+        if encoding.utc {
+            bytes.put_u8(DATE_TIME_ZONE_ID_UTC_SIGNATURE);
+            BoltInteger::new(self.seconds.value - i64::from(self.utc_offset()?))
+                .write_into(encoding, bytes)?;
+        } else {
+            bytes.put_u8(DATE_TIME_ZONE_ID_SIGNATURE);
+            self.seconds.write_into(encoding, bytes)?;
+        }
+        self.nanoseconds.write_into(encoding, bytes)?;
+        self.tz_id.write_into(encoding, bytes)?;
+        Ok(())
+    }
+}
+
+fn parse_tz(tz_id: &BoltString) -> Result<chrono_tz::Tz> {
+    tz_id
+        .value
+        .parse()
+        .map_err(|_| Error::UnknownType(format!("unknown time zone {}", tz_id.value)))
+}
+
+/// The offset from UTC that applies to the given local time,
+/// the earlier one if the local time occurs twice.
+/// There is none for an unknown zone or a local time that is skipped when DST starts.
+fn offset_from_local(tz_id: &BoltString, local: &NaiveDateTime) -> Option<i32> {
+    let tz = parse_tz(tz_id).ok()?;
+    let offset = tz.offset_from_local_datetime(local).earliest()?;
+    Some(offset.fix().local_minus_utc())
 }
 
 impl BoltDateTime {
@@ -40,6 +148,51 @@ impl BoltLocalDateTime {
 }
 
 impl BoltDateTimeZoneId {
+    /// Creates the value from the seconds since the epoch in local time,
+    /// as sent by the legacy signature.
+    pub(crate) fn from_local(
+        seconds: BoltInteger,
+        nanoseconds: BoltInteger,
+        tz_id: BoltString,
+    ) -> Self {
+        let offset_seconds = DateTime::from_timestamp(seconds.value, 0)
+            .and_then(|local| offset_from_local(&tz_id, &local.naive_utc()));
+        BoltDateTimeZoneId {
+            seconds,
+            nanoseconds,
+            tz_id,
+            offset_seconds,
+        }
+    }
+
+    /// Creates the value from the seconds since the epoch in UTC,
+    /// as sent by the UTC based signature.
+    pub(crate) fn from_utc(
+        seconds: BoltInteger,
+        nanoseconds: BoltInteger,
+        tz_id: BoltString,
+    ) -> Result<Self> {
+        let tz = parse_tz(&tz_id)?;
+        let utc = DateTime::from_timestamp(seconds.value, 0).ok_or(Error::ConversionError)?;
+        let offset = tz.offset_from_utc_datetime(&utc.naive_utc()).fix();
+        let offset_seconds = offset.local_minus_utc();
+        Ok(BoltDateTimeZoneId {
+            seconds: BoltInteger::new(seconds.value + i64::from(offset_seconds)),
+            nanoseconds,
+            tz_id,
+            offset_seconds: Some(offset_seconds),
+        })
+    }
+
+    /// The offset from UTC, which fails for an unknown zone
+    /// or a local time that is skipped when DST starts.
+    fn utc_offset(&self) -> Result<i32> {
+        match self.offset_seconds {
+            Some(offset) => Ok(offset),
+            None => parse_tz(&self.tz_id).and(Err(Error::ConversionError)),
+        }
+    }
+
     pub(crate) fn try_to_chrono(&self) -> Result<DateTime<FixedOffset>> {
         self.try_into()
     }
@@ -49,14 +202,30 @@ impl BoltDateTimeZoneId {
     }
 }
 
+/// Local times that occur twice when DST ends resolve to the earlier instant.
+/// Use a `DateTime<chrono_tz::Tz>` to refer to a specific instant.
 impl From<(NaiveDateTime, &str)> for BoltDateTimeZoneId {
-    fn from(value: (NaiveDateTime, &str)) -> Self {
-        let seconds = value.0.and_utc().timestamp().into();
-        let nanoseconds = (value.0.and_utc().timestamp_subsec_nanos() as i64).into();
+    fn from((local, tz_id): (NaiveDateTime, &str)) -> Self {
+        let seconds = local.and_utc().timestamp().into();
+        let nanoseconds = (local.and_utc().timestamp_subsec_nanos() as i64).into();
+        let tz_id = BoltString::from(tz_id);
+        let offset_seconds = offset_from_local(&tz_id, &local);
         BoltDateTimeZoneId {
             seconds,
             nanoseconds,
-            tz_id: value.1.into(),
+            tz_id,
+            offset_seconds,
+        }
+    }
+}
+
+impl From<DateTime<chrono_tz::Tz>> for BoltDateTimeZoneId {
+    fn from(value: DateTime<chrono_tz::Tz>) -> Self {
+        BoltDateTimeZoneId {
+            seconds: value.naive_local().and_utc().timestamp().into(),
+            nanoseconds: (value.timestamp_subsec_nanos() as i64).into(),
+            tz_id: value.timezone().name().into(),
+            offset_seconds: Some(value.offset().fix().local_minus_utc()),
         }
     }
 }
@@ -85,20 +254,14 @@ impl TryFrom<&BoltDateTimeZoneId> for DateTime<FixedOffset> {
     type Error = Error;
 
     fn try_from(value: &BoltDateTimeZoneId) -> std::result::Result<Self, Self::Error> {
-        let tz: chrono_tz::Tz = value
-            .tz_id
-            .value
-            .parse()
-            .map_err(|_| Error::ConversionError)?;
+        let offset = value.utc_offset()?;
+        let offset = FixedOffset::east_opt(offset).ok_or(Error::ConversionError)?;
 
-        let seconds = value.seconds.value;
+        let seconds = value.seconds.value - i64::from(offset.local_minus_utc());
         let nanoseconds = value.nanoseconds.value as u32;
 
         let dt = DateTime::from_timestamp(seconds, nanoseconds).ok_or(Error::ConversionError)?;
-        let dt = chrono::TimeZone::from_local_datetime(&tz, &dt.naive_utc())
-            .single()
-            .ok_or(Error::ConversionError)?;
-        Ok(dt.fixed_offset())
+        Ok(dt.with_timezone(&offset))
     }
 }
 
@@ -269,4 +432,109 @@ mod tests {
         assert_eq!(datetime.to_string(), "2015-07-01 08:59:60.123");
         assert_eq!(zone_id, "Europe/Paris");
     }
+
+    #[test]
+    fn should_serialize_a_utc_datetime() {
+        let date: BoltDateTime = DateTime::parse_from_rfc2822("Wed, 24 Jun 2015 12:50:35 +0100")
+            .unwrap()
+            .into();
+
+        assert_eq!(
+            date.into_bytes(Version::V5_0).unwrap(),
+            Bytes::from_static(&[
+                0xB3, 0x49, 0xCA, 0x55, 0x8A, 0x99, 0x8B, 0x00, 0xC9, 0x0E, 0x10,
+            ])
+        );
+    }
+
+    #[test]
+    fn should_deserialize_a_utc_datetime() {
+        let mut bytes = Bytes::from_static(&[
+            0xB3, 0x49, 0xCA, 0x55, 0x8A, 0x99, 0x8B, 0x00, 0xC9, 0x0E, 0x10,
+        ]);
+
+        let datetime: DateTime<FixedOffset> = BoltDateTime::parse(Version::V5_0, &mut bytes)
+            .unwrap()
+            .try_into()
+            .unwrap();
+
+        assert_eq!(datetime.to_rfc2822(), "Wed, 24 Jun 2015 12:50:35 +0100");
+    }
+
+    #[test]
+    fn should_roundtrip_a_utc_datetime_with_zoneid() {
+        let datetime =
+            NaiveDateTime::parse_from_str("2015-07-01 08:59:59.123", "%Y-%m-%d %H:%M:%S%.f")
+                .unwrap();
+
+        let date: BoltDateTimeZoneId = (datetime, "Europe/Paris").into();
+        let mut bytes = date.clone().into_bytes(Version::V5_0).unwrap();
+
+        assert_eq!(&bytes[..2], &[0xB3, 0x69]);
+        // 2015-07-01 06:59:59 UTC
+        assert_eq!(&bytes[2..7], &[0xCA, 0x55, 0x93, 0x8F, 0xEF]);
+
+        let parsed = BoltDateTimeZoneId::parse(Version::V5_0, &mut bytes).unwrap();
+        assert_eq!(parsed, date);
+    }
+
+    #[test]
+    fn should_use_utc_signatures_with_utc_patch() {
+        let datetime =
+            NaiveDateTime::parse_from_str("2015-07-01 08:59:59", "%Y-%m-%d %H:%M:%S").unwrap();
+        let date: BoltDateTimeZoneId = (datetime, "Europe/Paris").into();
+        let bytes = date.clone().into_bytes(Version::V4_4).unwrap();
+        assert_eq!(&bytes[..2], &[0xB3, 0x66]);
+        let bytes = date.into_bytes(Encoding::new(Version::V4_4, true)).unwrap();
+        assert_eq!(&bytes[..2], &[0xB3, 0x69]);
+
+        let date: BoltDateTime = DateTime::parse_from_rfc2822("Wed, 24 Jun 2015 12:50:35 +0100")
+            .unwrap()
+            .into();
+        let bytes = date.clone().into_bytes(Version::V4_4).unwrap();
+        assert_eq!(&bytes[..2], &[0xB3, 0x46]);
+        let bytes = date.into_bytes(Encoding::new(Version::V4_4, true)).unwrap();
+        assert_eq!(&bytes[..2], &[0xB3, 0x49]);
+    }
+
+    #[test]
+    fn should_fail_on_unknown_zone_ids() {
+        let datetime =
+            NaiveDateTime::parse_from_str("2015-07-01 08:59:59", "%Y-%m-%d %H:%M:%S").unwrap();
+        let date: BoltDateTimeZoneId = (datetime, "Mars/Olympus_Mons").into();
+
+        assert!(matches!(
+            date.clone().into_bytes(Version::V5_0),
+            Err(Error::UnknownType(_))
+        ));
+        assert!(matches!(date.try_to_chrono(), Err(Error::UnknownType(_))));
+
+        let mut bytes =
+            Bytes::from_static(&[0xB3, 0x69, 0xCA, 0x55, 0x93, 0x8F, 0xEF, 0x00, 0x81, 0x58]);
+        assert!(matches!(
+            BoltDateTimeZoneId::parse(Version::V5_0, &mut bytes),
+            Err(Error::UnknownType(_))
+        ));
+    }
+
+    #[test]
+    fn should_keep_ambiguous_instants_with_zoneid() {
+        // 02:30 happens twice in Paris when DST ends
+        let later = chrono_tz::Europe::Paris
+            .with_ymd_and_hms(2023, 10, 29, 2, 30, 0)
+            .latest()
+            .unwrap();
+
+        let date = BoltDateTimeZoneId::from(later);
+        let mut bytes = date
+            .clone()
+            .into_bytes(Encoding::new(Version::V4_4, true))
+            .unwrap();
+        let parsed = BoltDateTimeZoneId::parse(Version::V4_4, &mut bytes).unwrap();
+        assert_eq!(parsed, date);
+
+        let datetime: DateTime<FixedOffset> = parsed.try_to_chrono().unwrap();
+        assert_eq!(datetime, later.fixed_offset());
+        assert_eq!(datetime.to_rfc3339(), "2023-10-29T02:30:00+01:00");
+    }
 }
@@ -1,4 +1,8 @@
-use crate::{errors::Result, types::BoltWireFormat, version::Version};
+use crate::{
+    errors::Result,
+    types::BoltWireFormat,
+    version::{Encoding, Version},
+};
 use bytes::{Buf, BufMut, Bytes, BytesMut};
 use std::mem;
 
@@ -26,7 +30,7 @@ impl BoltWireFormat for BoltFloat {
         Ok(BoltFloat::new(value))
     }
 
-    fn write_into(&self, _version: Version, bytes: &mut BytesMut) -> Result<()> {
+    fn write_into(&self, _encoding: Encoding, bytes: &mut BytesMut) -> Result<()> {
         bytes.reserve(mem::size_of::<u8>() + mem::size_of::<f64>());
         bytes.put_u8(MARKER);
         bytes.put_f64(self.value);
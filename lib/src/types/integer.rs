@@ -1,7 +1,7 @@
 use crate::{
     errors::{Error, Result},
     types::BoltWireFormat,
-    version::Version,
+    version::{Encoding, Version},
 };
 use bytes::{Buf, BufMut, Bytes, BytesMut};
 use std::mem;
@@ -80,7 +80,7 @@ impl BoltWireFormat for BoltInteger {
         Ok(BoltInteger::new(value))
     }
 
-    fn write_into(&self, _version: Version, bytes: &mut BytesMut) -> Result<()> {
+    fn write_into(&self, _encoding: Encoding, bytes: &mut BytesMut) -> Result<()> {
         match self.value {
             -16..=127 => {
                 bytes.reserve(mem::size_of::<u8>());
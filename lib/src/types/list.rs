@@ -1,7 +1,7 @@
 use crate::{
     errors::{Error, Result},
     types::{BoltType, BoltWireFormat, BytesMut},
-    version::Version,
+    version::{Encoding, Version},
 };
 use bytes::{Buf, BufMut, Bytes};
 use std::mem;
@@ -110,7 +110,7 @@ impl BoltWireFormat for BoltList {
         Ok(list)
     }
 
-    fn write_into(&self, version: Version, bytes: &mut BytesMut) -> Result<()> {
+    fn write_into(&self, encoding: Encoding, bytes: &mut BytesMut) -> Result<()> {
         let length = self.value.len();
 
         match length {
@@ -136,7 +136,7 @@ impl BoltWireFormat for BoltList {
             _ => return Err(Error::ListTooLong),
         }
         for elem in &self.value {
-            elem.write_into(version, bytes)?;
+            elem.write_into(encoding, bytes)?;
         }
 
         Ok(())
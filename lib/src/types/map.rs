@@ -1,7 +1,7 @@
 use crate::{
     errors::{Error, Result},
     types::{serde::DeError, BoltString, BoltType, BoltWireFormat},
-    version::Version,
+    version::{Encoding, Version},
 };
 use ::serde::Deserialize;
 use bytes::{Buf, BufMut, Bytes, BytesMut};
@@ -101,7 +101,7 @@ impl BoltWireFormat for BoltMap {
         Ok(map)
     }
 
-    fn write_into(&self, version: Version, bytes: &mut BytesMut) -> Result<()> {
+    fn write_into(&self, encoding: Encoding, bytes: &mut BytesMut) -> Result<()> {
         let length = self.value.len();
         match length {
             0..=15 => {
@@ -127,8 +127,8 @@ impl BoltWireFormat for BoltMap {
         }
 
         for (key, value) in &self.value {
-            key.write_into(version, bytes)?;
-            value.write_into(version, bytes)?;
+            key.write_into(encoding, bytes)?;
+            value.write_into(encoding, bytes)?;
         }
 
         Ok(())
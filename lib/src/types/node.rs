@@ -1,13 +1,22 @@
-use crate::types::{serde::DeError, BoltInteger, BoltList, BoltMap, BoltType, Result};
+use crate::{
+    errors::{Error, Result},
+    types::{serde::DeError, BoltInteger, BoltList, BoltMap, BoltString, BoltType, BoltWireFormat},
+    version::{Encoding, Version},
+};
 use ::serde::Deserialize;
-use neo4rs_macros::BoltStruct;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
 
-#[derive(Debug, PartialEq, Clone, BoltStruct)]
-#[signature(0xB3, 0x4E)]
+const MARKER: u8 = 0xB3;
+const MARKER_V5: u8 = 0xB4;
+const SIGNATURE: u8 = 0x4E;
+
+#[derive(Debug, PartialEq, Clone)]
 pub struct BoltNode {
     pub id: BoltInteger,
     pub labels: BoltList,
     pub properties: BoltMap,
+    /// The element id of the node, only sent by servers speaking Bolt 5.0 or newer.
+    pub element_id: Option<BoltString>,
 }
 
 impl BoltNode {
@@ -16,8 +25,60 @@ impl BoltNode {
             id,
             labels,
             properties,
+            element_id: None,
         }
     }
+
+    pub fn with_element_id(mut self, element_id: impl Into<BoltString>) -> Self {
+        self.element_id = Some(element_id.into());
+        self
+    }
+}
+
+impl BoltWireFormat for BoltNode {
+    fn can_parse(_version: Version, input: &[u8]) -> bool {
+        input.len() >= 2 && (input[0] == MARKER || input[0] == MARKER_V5) && input[1] == SIGNATURE
+    }
+
+    fn parse(version: Version, input: &mut Bytes) -> Result<Self> {
+        let marker = input.get_u8();
+        input.get_u8();
+        let id = BoltInteger::parse(version, input)?;
+        let labels = BoltList::parse(version, input)?;
+        let properties = BoltMap::parse(version, input)?;
+        let element_id = match marker {
+            MARKER => None,
+            MARKER_V5 => Some(BoltString::parse(version, input)?),
+            _ => {
+                return Err(Error::InvalidTypeMarker(format!(
+                    "invalid node marker {marker}"
+                )))
+            }
+        };
+        Ok(BoltNode {
+            id,
+            labels,
+            properties,
+            element_id,
+        })
+    }
+
+    fn write_into(&self, encoding: Encoding, bytes: &mut BytesMut) -> Result<()> {
+        let v5 = encoding.version >= Version::V5_0;
+        bytes.reserve(2);
+        bytes.put_u8(if v5 { MARKER_V5 } else { MARKER });
+        bytes.put_u8(SIGNATURE);
+        self.id.write_into(encoding, bytes)?;
+        self.labels.write_into(encoding, bytes)?;
+        self.properties.write_into(encoding, bytes)?;
+        if v5 {
+            match &self.element_id {
+                Some(element_id) => element_id.write_into(encoding, bytes)?,
+                None => BoltString::from(self.id.value.to_string()).write_into(encoding, bytes)?,
+            }
+        }
+        Ok(())
+    }
 }
 
 impl BoltNode {
@@ -63,11 +124,7 @@ mod tests {
         let id = BoltInteger::new(19);
         let labels = vec!["Person".into()].into();
         let properties = vec![("name".into(), "Mark".into())].into_iter().collect();
-        let node = BoltNode {
-            id,
-            labels,
-            properties,
-        };
+        let node = BoltNode::new(id, labels, properties);
 
         let bytes: Bytes = node.into_bytes(Version::V4_1).unwrap();
 
@@ -79,4 +136,40 @@ mod tests {
             ])
         );
     }
+
+    #[test]
+    fn should_deserialize_a_v5_node() {
+        let mut input = Bytes::from_static(&[
+            0xB4, 0x4E, 0x13, 0x91, 0x86, 0x50, 0x65, 0x72, 0x73, 0x6F, 0x6E, 0xA1, 0x84, 0x6E,
+            0x61, 0x6D, 0x65, 0x84, 0x4D, 0x61, 0x72, 0x6B, 0x84, 0x34, 0x3A, 0x78, 0x79,
+        ]);
+
+        let node: BoltNode = BoltNode::parse(Version::V5_0, &mut input).unwrap();
+
+        assert_eq!(node.id, BoltInteger::new(19));
+        assert_eq!(node.labels, vec!["Person".into()].into());
+        assert_eq!(
+            node.properties,
+            vec![("name".into(), "Mark".into())].into_iter().collect()
+        );
+        assert_eq!(node.element_id, Some("4:xy".into()));
+    }
+
+    #[test]
+    fn should_serialize_a_v5_node() {
+        let id = BoltInteger::new(19);
+        let labels = vec!["Person".into()].into();
+        let properties = vec![("name".into(), "Mark".into())].into_iter().collect();
+        let node = BoltNode::new(id, labels, properties).with_element_id("4:xy");
+
+        let bytes: Bytes = node.into_bytes(Version::V5_0).unwrap();
+
+        assert_eq!(
+            bytes,
+            Bytes::from_static(&[
+                0xB4, 0x4E, 0x13, 0x91, 0x86, 0x50, 0x65, 0x72, 0x73, 0x6F, 0x6E, 0xA1, 0x84, 0x6E,
+                0x61, 0x6D, 0x65, 0x84, 0x4D, 0x61, 0x72, 0x6B, 0x84, 0x34, 0x3A, 0x78, 0x79,
+            ])
+        );
+    }
 }
@@ -1,23 +1,71 @@
-use crate::types::{serde::DeError, BoltInteger, BoltMap, BoltString, BoltType, Result};
+use crate::{
+    errors::{Error, Result},
+    types::{serde::DeError, BoltInteger, BoltMap, BoltString, BoltType, BoltWireFormat},
+    version::{Encoding, Version},
+};
 use ::serde::Deserialize;
-use neo4rs_macros::BoltStruct;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
 
-#[derive(Debug, PartialEq, Clone, BoltStruct)]
-#[signature(0xB5, 0x52)]
+const RELATION_MARKER: u8 = 0xB5;
+const RELATION_MARKER_V5: u8 = 0xB8;
+const RELATION_SIGNATURE: u8 = 0x52;
+const UNBOUNDED_RELATION_MARKER: u8 = 0xB3;
+const UNBOUNDED_RELATION_MARKER_V5: u8 = 0xB4;
+const UNBOUNDED_RELATION_SIGNATURE: u8 = 0x72;
+
+#[derive(Debug, PartialEq, Clone)]
 pub struct BoltRelation {
     pub id: BoltInteger,
     pub start_node_id: BoltInteger,
     pub end_node_id: BoltInteger,
     pub typ: BoltString,
     pub properties: BoltMap,
+    /// The element ids are only sent by servers speaking Bolt 5.0 or newer.
+    pub element_id: Option<BoltString>,
+    pub start_node_element_id: Option<BoltString>,
+    pub end_node_element_id: Option<BoltString>,
 }
 
-#[derive(Debug, PartialEq, Clone, BoltStruct)]
-#[signature(0xB3, 0x72)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct BoltUnboundedRelation {
     pub id: BoltInteger,
     pub typ: BoltString,
     pub properties: BoltMap,
+    /// The element id is only sent by servers speaking Bolt 5.0 or newer.
+    pub element_id: Option<BoltString>,
+}
+
+impl BoltRelation {
+    pub fn new(
+        id: BoltInteger,
+        start_node_id: BoltInteger,
+        end_node_id: BoltInteger,
+        typ: BoltString,
+        properties: BoltMap,
+    ) -> Self {
+        BoltRelation {
+            id,
+            start_node_id,
+            end_node_id,
+            typ,
+            properties,
+            element_id: None,
+            start_node_element_id: None,
+            end_node_element_id: None,
+        }
+    }
+
+    pub fn with_element_ids(
+        mut self,
+        element_id: impl Into<BoltString>,
+        start_node_element_id: impl Into<BoltString>,
+        end_node_element_id: impl Into<BoltString>,
+    ) -> Self {
+        self.element_id = Some(element_id.into());
+        self.start_node_element_id = Some(start_node_element_id.into());
+        self.end_node_element_id = Some(end_node_element_id.into());
+        self
+    }
 }
 
 impl BoltUnboundedRelation {
@@ -26,7 +74,141 @@ impl BoltUnboundedRelation {
             id,
             typ,
             properties,
+            element_id: None,
+        }
+    }
+
+    pub fn with_element_id(mut self, element_id: impl Into<BoltString>) -> Self {
+        self.element_id = Some(element_id.into());
+        self
+    }
+}
+
+/// Writes the element id if present, or falls back to the legacy id,
+/// which is what the server would use for entities without an element id.
+fn write_element_id(
+    element_id: Option<&BoltString>,
+    id: &BoltInteger,
+    encoding: Encoding,
+    bytes: &mut BytesMut,
+) -> Result<()> {
+    match element_id {
+        Some(element_id) => element_id.write_into(encoding, bytes),
+        None => BoltString::from(id.value.to_string()).write_into(encoding, bytes),
+    }
+}
+
+impl BoltWireFormat for BoltRelation {
+    fn can_parse(_version: Version, input: &[u8]) -> bool {
+        input.len() >= 2
+            && (input[0] == RELATION_MARKER || input[0] == RELATION_MARKER_V5)
+            && input[1] == RELATION_SIGNATURE
+    }
+
+    fn parse(version: Version, input: &mut Bytes) -> Result<Self> {
+        let marker = input.get_u8();
+        input.get_u8();
+        let mut relation = BoltRelation::new(
+            BoltInteger::parse(version, input)?,
+            BoltInteger::parse(version, input)?,
+            BoltInteger::parse(version, input)?,
+            BoltString::parse(version, input)?,
+            BoltMap::parse(version, input)?,
+        );
+        match marker {
+            RELATION_MARKER => {}
+            RELATION_MARKER_V5 => {
+                relation.element_id = Some(BoltString::parse(version, input)?);
+                relation.start_node_element_id = Some(BoltString::parse(version, input)?);
+                relation.end_node_element_id = Some(BoltString::parse(version, input)?);
+            }
+            _ => {
+                return Err(Error::InvalidTypeMarker(format!(
+                    "invalid relationship marker {marker}"
+                )))
+            }
+        }
+        Ok(relation)
+    }
+
+    fn write_into(&self, encoding: Encoding, bytes: &mut BytesMut) -> Result<()> {
+        let v5 = encoding.version >= Version::V5_0;
+        bytes.reserve(2);
+        bytes.put_u8(if v5 {
+            RELATION_MARKER_V5
+        } else {
+            RELATION_MARKER
+        });
+        bytes.put_u8(RELATION_SIGNATURE);
+        self.id.write_into(encoding, bytes)?;
+        self.start_node_id.write_into(encoding, bytes)?;
+        self.end_node_id.write_into(encoding, bytes)?;
+        self.typ.write_into(encoding, bytes)?;
+        self.properties.write_into(encoding, bytes)?;
+        if v5 {
+            write_element_id(self.element_id.as_ref(), &self.id, encoding, bytes)?;
+            write_element_id(
+                self.start_node_element_id.as_ref(),
+                &self.start_node_id,
+                encoding,
+                bytes,
+            )?;
+            write_element_id(
+                self.end_node_element_id.as_ref(),
+                &self.end_node_id,
+                encoding,
+                bytes,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl BoltWireFormat for BoltUnboundedRelation {
+    fn can_parse(_version: Version, input: &[u8]) -> bool {
+        input.len() >= 2
+            && (input[0] == UNBOUNDED_RELATION_MARKER || input[0] == UNBOUNDED_RELATION_MARKER_V5)
+            && input[1] == UNBOUNDED_RELATION_SIGNATURE
+    }
+
+    fn parse(version: Version, input: &mut Bytes) -> Result<Self> {
+        let marker = input.get_u8();
+        input.get_u8();
+        let mut relation = BoltUnboundedRelation::new(
+            BoltInteger::parse(version, input)?,
+            BoltString::parse(version, input)?,
+            BoltMap::parse(version, input)?,
+        );
+        match marker {
+            UNBOUNDED_RELATION_MARKER => {}
+            UNBOUNDED_RELATION_MARKER_V5 => {
+                relation.element_id = Some(BoltString::parse(version, input)?);
+            }
+            _ => {
+                return Err(Error::InvalidTypeMarker(format!(
+                    "invalid unbounded relationship marker {marker}"
+                )))
+            }
+        }
+        Ok(relation)
+    }
+
+    fn write_into(&self, encoding: Encoding, bytes: &mut BytesMut) -> Result<()> {
+        let v5 = encoding.version >= Version::V5_0;
+        bytes.reserve(2);
+        bytes.put_u8(if v5 {
+            UNBOUNDED_RELATION_MARKER_V5
+        } else {
+            UNBOUNDED_RELATION_MARKER
+        });
+        bytes.put_u8(UNBOUNDED_RELATION_SIGNATURE);
+        self.id.write_into(encoding, bytes)?;
+        self.typ.write_into(encoding, bytes)?;
+        self.properties.write_into(encoding, bytes)?;
+        if v5 {
+            write_element_id(self.element_id.as_ref(), &self.id, encoding, bytes)?;
         }
+        Ok(())
     }
 }
 
@@ -74,13 +256,7 @@ mod tests {
         let typ = BoltString::new("rel");
         let properties = vec![("name".into(), "Mark".into())].into_iter().collect();
 
-        let relation = BoltRelation {
-            id,
-            start_node_id,
-            end_node_id,
-            typ,
-            properties,
-        };
+        let relation = BoltRelation::new(id, start_node_id, end_node_id, typ, properties);
 
         let bytes: Bytes = relation.into_bytes(Version::V4_1).unwrap();
 
@@ -147,4 +323,61 @@ mod tests {
             vec![("name".into(), "Mark".into())].into_iter().collect()
         );
     }
+
+    #[test]
+    fn should_deserialize_a_v5_relation() {
+        let mut input = Bytes::from_static(&[
+            0xB8, 0x52, 0x2A, 0x01, 0x02, 0x83, 0x72, 0x65, 0x6C, 0xA1, 0x84, 0x6E, 0x61, 0x6D,
+            0x65, 0x84, 0x4D, 0x61, 0x72, 0x6B, 0x84, 0x35, 0x3A, 0x34, 0x32, 0x83, 0x34, 0x3A,
+            0x31, 0x83, 0x34, 0x3A, 0x32,
+        ]);
+
+        let relation: BoltRelation = BoltRelation::parse(Version::V5_0, &mut input).unwrap();
+
+        assert_eq!(relation.id, BoltInteger::new(42));
+        assert_eq!(relation.start_node_id, BoltInteger::new(1));
+        assert_eq!(relation.end_node_id, BoltInteger::new(2));
+        assert_eq!(relation.typ, BoltString::new("rel"));
+        assert_eq!(relation.element_id, Some("5:42".into()));
+        assert_eq!(relation.start_node_element_id, Some("4:1".into()));
+        assert_eq!(relation.end_node_element_id, Some("4:2".into()));
+    }
+
+    #[test]
+    fn should_serialize_a_v5_relation() {
+        let relation = BoltRelation::new(
+            BoltInteger::new(42),
+            BoltInteger::new(1),
+            BoltInteger::new(2),
+            BoltString::new("rel"),
+            vec![("name".into(), "Mark".into())].into_iter().collect(),
+        )
+        .with_element_ids("5:42", "4:1", "4:2");
+
+        let bytes: Bytes = relation.into_bytes(Version::V5_0).unwrap();
+
+        assert_eq!(
+            bytes,
+            Bytes::from_static(&[
+                0xB8, 0x52, 0x2A, 0x01, 0x02, 0x83, 0x72, 0x65, 0x6C, 0xA1, 0x84, 0x6E, 0x61, 0x6D,
+                0x65, 0x84, 0x4D, 0x61, 0x72, 0x6B, 0x84, 0x35, 0x3A, 0x34, 0x32, 0x83, 0x34, 0x3A,
+                0x31, 0x83, 0x34, 0x3A, 0x32,
+            ])
+        );
+    }
+
+    #[test]
+    fn should_deserialize_a_v5_unbounded_relation() {
+        let mut input = Bytes::from_static(&[
+            0xB4, 0x72, 0x2A, 0x83, 0x72, 0x65, 0x6C, 0xA1, 0x84, 0x6E, 0x61, 0x6D, 0x65, 0x84,
+            0x4D, 0x61, 0x72, 0x6B, 0x84, 0x35, 0x3A, 0x34, 0x32,
+        ]);
+
+        let relation: BoltUnboundedRelation =
+            BoltUnboundedRelation::parse(Version::V5_0, &mut input).unwrap();
+
+        assert_eq!(relation.id, BoltInteger::new(42));
+        assert_eq!(relation.typ, BoltString::new("rel"));
+        assert_eq!(relation.element_id, Some("5:42".into()));
+    }
 }
@@ -3,10 +3,13 @@ use crate::types::{
     BoltUnboundedRelation,
 };
 
-use std::result::Result;
+use std::{fmt, result::Result};
 
 use delegate::delegate;
-use serde::{de::Error, Deserialize};
+use serde::{
+    de::{Error, Visitor},
+    Deserialize, Deserializer,
+};
 
 #[derive(Debug, Clone, Default)]
 pub struct BoltNodeBuilder {
@@ -19,6 +22,7 @@ impl BoltNodeBuilder {
             pub fn id<E: Error>(&mut self, read: impl FnOnce() -> Result<BoltInteger, E>) -> Result<(), E>;
             pub fn labels<E: Error>(&mut self, read: impl FnOnce() -> Result<BoltList, E>) -> Result<(), E>;
             pub fn properties<E: Error>(&mut self, read: impl FnOnce() -> Result<BoltMap, E>) -> Result<(), E>;
+            pub fn element_id<E: Error>(&mut self, read: impl FnOnce() -> Result<Option<BoltString>, E>) -> Result<(), E>;
         }
     }
 
@@ -30,10 +34,13 @@ impl BoltNodeBuilder {
             .ok_or_else(|| Error::missing_field("labels"))?;
         let properties = self.inner.properties.or_else(Default::default);
 
+        let element_id = self.inner.element_id.or_else(Default::default);
+
         Ok(BoltNode {
             id,
             labels,
             properties,
+            element_id,
         })
     }
 }
@@ -51,6 +58,9 @@ impl BoltRelationBuilder {
             pub fn end_node_id<E: Error>(&mut self, read: impl FnOnce() -> Result<BoltInteger, E>) -> Result<(), E>;
             pub fn typ<E: Error>(&mut self, read: impl FnOnce() -> Result<BoltString, E>) -> Result<(), E>;
             pub fn properties<E: Error>(&mut self, read: impl FnOnce() -> Result<BoltMap, E>) -> Result<(), E>;
+            pub fn element_id<E: Error>(&mut self, read: impl FnOnce() -> Result<Option<BoltString>, E>) -> Result<(), E>;
+            pub fn start_node_element_id<E: Error>(&mut self, read: impl FnOnce() -> Result<Option<BoltString>, E>) -> Result<(), E>;
+            pub fn end_node_element_id<E: Error>(&mut self, read: impl FnOnce() -> Result<Option<BoltString>, E>) -> Result<(), E>;
         }
     }
 
@@ -67,12 +77,19 @@ impl BoltRelationBuilder {
         let typ = self.inner.typ.ok_or_else(|| Error::missing_field("type"))?;
         let properties = self.inner.properties.or_else(Default::default);
 
+        let element_id = self.inner.element_id.or_else(Default::default);
+        let start_node_element_id = self.inner.start_node_element_id.or_else(Default::default);
+        let end_node_element_id = self.inner.end_node_element_id.or_else(Default::default);
+
         Ok(BoltRelation {
             id,
             start_node_id,
             end_node_id,
             typ,
             properties,
+            element_id,
+            start_node_element_id,
+            end_node_element_id,
         })
     }
 }
@@ -88,6 +105,7 @@ impl BoltUnboundedRelationBuilder {
             pub fn id<E: Error>(&mut self, read: impl FnOnce() -> Result<BoltInteger, E>) -> Result<(), E>;
             pub fn typ<E: Error>(&mut self, read: impl FnOnce() -> Result<BoltString, E>) -> Result<(), E>;
             pub fn properties<E: Error>(&mut self, read: impl FnOnce() -> Result<BoltMap, E>) -> Result<(), E>;
+            pub fn element_id<E: Error>(&mut self, read: impl FnOnce() -> Result<Option<BoltString>, E>) -> Result<(), E>;
         }
     }
 
@@ -96,10 +114,13 @@ impl BoltUnboundedRelationBuilder {
         let typ = self.inner.typ.ok_or_else(|| Error::missing_field("type"))?;
         let properties = self.inner.properties.or_else(Default::default);
 
+        let element_id = self.inner.element_id.or_else(Default::default);
+
         Ok(BoltUnboundedRelation {
             id,
             typ,
             properties,
+            element_id,
         })
     }
 }
@@ -144,6 +165,51 @@ pub struct Id(pub BoltInteger);
 pub struct StartNodeId(pub BoltInteger);
 pub struct EndNodeId(pub BoltInteger);
 
+/// Element ids are optional, as only servers speaking Bolt 5.0 or newer send them.
+pub struct ElementId(pub Option<BoltString>);
+pub struct StartNodeElementId(pub Option<BoltString>);
+pub struct EndNodeElementId(pub Option<BoltString>);
+
+struct OptionalElementIdVisitor;
+
+impl<'de> Visitor<'de> for OptionalElementIdVisitor {
+    type Value = Option<BoltString>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an optional element id")
+    }
+
+    fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer).map(|id| Some(id.into()))
+    }
+}
+
+macro_rules! optional_element_id {
+    ($($typ:ident),+) => {
+        $(
+            impl<'de> Deserialize<'de> for $typ {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: Deserializer<'de>,
+                {
+                    deserializer
+                        .deserialize_newtype_struct(stringify!($typ), OptionalElementIdVisitor)
+                        .map($typ)
+                }
+            }
+        )+
+    };
+}
+
+optional_element_id!(ElementId, StartNodeElementId, EndNodeElementId);
+
 impl<'de> Deserialize<'de> for Id {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -180,6 +246,9 @@ struct ElementBuilder {
     labels: SetOnce<BoltList>,
     typ: SetOnce<BoltString>,
     properties: SetOnce<BoltMap>,
+    element_id: SetOnce<Option<BoltString>>,
+    start_node_element_id: SetOnce<Option<BoltString>>,
+    end_node_element_id: SetOnce<Option<BoltString>>,
     nodes: SetOnce<BoltList>,
     rels: SetOnce<BoltList>,
     indices: SetOnce<BoltList>,
@@ -234,6 +303,36 @@ impl ElementBuilder {
         }
     }
 
+    fn element_id<E: Error>(
+        &mut self,
+        read: impl FnOnce() -> Result<Option<BoltString>, E>,
+    ) -> Result<(), E> {
+        match self.element_id.try_insert_with(read)? {
+            Ok(_) => Ok(()),
+            Err(_) => Err(Error::duplicate_field("element_id")),
+        }
+    }
+
+    fn start_node_element_id<E: Error>(
+        &mut self,
+        read: impl FnOnce() -> Result<Option<BoltString>, E>,
+    ) -> Result<(), E> {
+        match self.start_node_element_id.try_insert_with(read)? {
+            Ok(_) => Ok(()),
+            Err(_) => Err(Error::duplicate_field("start_node_element_id")),
+        }
+    }
+
+    fn end_node_element_id<E: Error>(
+        &mut self,
+        read: impl FnOnce() -> Result<Option<BoltString>, E>,
+    ) -> Result<(), E> {
+        match self.end_node_element_id.try_insert_with(read)? {
+            Ok(_) => Ok(()),
+            Err(_) => Err(Error::duplicate_field("end_node_element_id")),
+        }
+    }
+
     fn nodes<E: Error>(&mut self, read: impl FnOnce() -> Result<BoltList, E>) -> Result<(), E> {
         match self.nodes.try_insert_with(read)? {
             Ok(_) => Ok(()),
@@ -256,18 +355,13 @@ impl ElementBuilder {
     }
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, Default)]
 pub enum SetOnce<T> {
+    #[default]
     Empty,
     Set(T),
 }
 
-impl<T> Default for SetOnce<T> {
-    fn default() -> Self {
-        Self::Empty
-    }
-}
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct SetOnceError;
 
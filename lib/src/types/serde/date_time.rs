@@ -324,19 +324,19 @@ impl DateTimeIsh for BoltLocalDateTime {
 
 impl DateTimeIsh for BoltDateTimeZoneId {
     fn build<E: Error>(builder: &mut DateTimeIshBuilder) -> Result<Self, E> {
-        Ok(BoltDateTimeZoneId {
-            seconds: builder
+        Ok(BoltDateTimeZoneId::from_local(
+            builder
                 .seconds
                 .take()
                 .ok_or_else(|| Error::missing_field("seconds"))?,
-            nanoseconds: builder
+            builder
                 .nanoseconds
                 .take()
                 .ok_or_else(|| Error::missing_field("nanoseconds"))?,
-            tz_id: builder
+            builder
                 .tz_id
                 .take()
                 .ok_or_else(|| Error::missing_field("tz_id"))?,
-        })
+        ))
     }
 }
@@ -34,6 +34,9 @@ crate::cenum!(ElementDataKey {
     Nodes,
     Relationships,
     Indices,
+    ElementId,
+    StartNodeElementId,
+    EndNodeElementId,
 });
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -128,6 +131,13 @@ impl<'de, T: ElementData<'de>> ElementDataDeserializer<'de, T> {
                 };
                 visitation.visit(id, visitor)
             }
+            "ElementId" => self.visit_element_id(ElementDataKey::ElementId, visitor, visitation),
+            "StartNodeElementId" => {
+                self.visit_element_id(ElementDataKey::StartNodeElementId, visitor, visitation)
+            }
+            "EndNodeElementId" => {
+                self.visit_element_id(ElementDataKey::EndNodeElementId, visitor, visitation)
+            }
             "Labels" => {
                 let labels = match self.data.value(ElementDataKey::Labels) {
                     Some(ElementDataValue::Lst(BoltList { value: labels })) => labels,
@@ -183,12 +193,33 @@ impl<'de, T: ElementData<'de>> ElementDataDeserializer<'de, T> {
             _ => Err(DeError::invalid_type(
                 Unexpected::Other(&format!("struct `{}`", name)),
                 &concat!(
-                    "one of `Id`, `Labels`, `Type`, `StartNodeId`, ",
-                    "`EndNodeId`, `Keys`, `Nodes`, `Relationships`, or `Indices`"
+                    "one of `Id`, `ElementId`, `Labels`, `Type`, `StartNodeId`, ",
+                    "`EndNodeId`, `StartNodeElementId`, `EndNodeElementId`, ",
+                    "`Keys`, `Nodes`, `Relationships`, or `Indices`"
                 ),
             )),
         }
     }
+
+    /// Element ids are only sent by servers speaking Bolt 5.0 or newer,
+    /// their absence is reported as `none` to the visitor.
+    fn visit_element_id<V, A>(
+        self,
+        key: ElementDataKey,
+        visitor: V,
+        visitation: A,
+    ) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+        A: Visitation,
+    {
+        match self.data.value(key) {
+            Some(ElementDataValue::Str(BoltString { value: id })) => {
+                visitation.visit(BorrowedStr(id), visitor)
+            }
+            _ => visitor.visit_none(),
+        }
+    }
 }
 
 impl<'de, T: ElementData<'de>> Deserializer<'de> for ElementDataDeserializer<'de, T> {
@@ -561,17 +592,34 @@ impl<'de, T: ElementData<'de>> IntoDeserializer<'de, DeError> for AdditionalData
     }
 }
 
+type ElementItem<'de> = (ElementDataKey, ElementDataValue<'de>);
+
+/// The fixed items of an element, followed by the element ids that are present.
+type WithElementIds<'de, const N: usize, const M: usize> = iter::Chain<
+    std::array::IntoIter<ElementItem<'de>, N>,
+    iter::Flatten<std::array::IntoIter<Option<ElementItem<'de>>, M>>,
+>;
+
+fn element_ids<'de, const M: usize>(
+    ids: [(ElementDataKey, &'de Option<BoltString>); M],
+) -> iter::Flatten<std::array::IntoIter<Option<ElementItem<'de>>, M>> {
+    ids.map(|(key, id)| id.as_ref().map(|id| (key, ElementDataValue::Str(id))))
+        .into_iter()
+        .flatten()
+}
+
 impl<'de> ElementData<'de> for &'de BoltNode {
     fn value(self, key: ElementDataKey) -> Option<ElementDataValue<'de>> {
         match key {
             ElementDataKey::Id => Some(ElementDataValue::Int(&self.id)),
             ElementDataKey::Labels => Some(ElementDataValue::Lst(&self.labels)),
             ElementDataKey::Properties => Some(ElementDataValue::Map(&self.properties)),
+            ElementDataKey::ElementId => self.element_id.as_ref().map(ElementDataValue::Str),
             _ => None,
         }
     }
 
-    type Items = [(ElementDataKey, ElementDataValue<'de>); 3];
+    type Items = WithElementIds<'de, 3, 1>;
 
     fn items(self) -> Self::Items {
         [
@@ -582,6 +630,8 @@ impl<'de> ElementData<'de> for &'de BoltNode {
                 ElementDataValue::Map(&self.properties),
             ),
         ]
+        .into_iter()
+        .chain(element_ids([(ElementDataKey::ElementId, &self.element_id)]))
     }
 }
 
@@ -593,11 +643,19 @@ impl<'de> ElementData<'de> for &'de BoltRelation {
             ElementDataKey::EndNodeId => Some(ElementDataValue::Int(&self.end_node_id)),
             ElementDataKey::Type => Some(ElementDataValue::Str(&self.typ)),
             ElementDataKey::Properties => Some(ElementDataValue::Map(&self.properties)),
+            ElementDataKey::ElementId => self.element_id.as_ref().map(ElementDataValue::Str),
+            ElementDataKey::StartNodeElementId => self
+                .start_node_element_id
+                .as_ref()
+                .map(ElementDataValue::Str),
+            ElementDataKey::EndNodeElementId => {
+                self.end_node_element_id.as_ref().map(ElementDataValue::Str)
+            }
             _ => None,
         }
     }
 
-    type Items = [(ElementDataKey, ElementDataValue<'de>); 5];
+    type Items = WithElementIds<'de, 5, 3>;
 
     fn items(self) -> Self::Items {
         [
@@ -616,6 +674,15 @@ impl<'de> ElementData<'de> for &'de BoltRelation {
                 ElementDataValue::Map(&self.properties),
             ),
         ]
+        .into_iter()
+        .chain(element_ids([
+            (ElementDataKey::ElementId, &self.element_id),
+            (
+                ElementDataKey::StartNodeElementId,
+                &self.start_node_element_id,
+            ),
+            (ElementDataKey::EndNodeElementId, &self.end_node_element_id),
+        ]))
     }
 }
 
@@ -625,11 +692,12 @@ impl<'de> ElementData<'de> for &'de BoltUnboundedRelation {
             ElementDataKey::Id => Some(ElementDataValue::Int(&self.id)),
             ElementDataKey::Type => Some(ElementDataValue::Str(&self.typ)),
             ElementDataKey::Properties => Some(ElementDataValue::Map(&self.properties)),
+            ElementDataKey::ElementId => self.element_id.as_ref().map(ElementDataValue::Str),
             _ => None,
         }
     }
 
-    type Items = [(ElementDataKey, ElementDataValue<'de>); 3];
+    type Items = WithElementIds<'de, 3, 1>;
 
     fn items(self) -> Self::Items {
         [
@@ -640,6 +708,8 @@ impl<'de> ElementData<'de> for &'de BoltUnboundedRelation {
                 ElementDataValue::Map(&self.properties),
             ),
         ]
+        .into_iter()
+        .chain(element_ids([(ElementDataKey::ElementId, &self.element_id)]))
     }
 }
 
@@ -711,7 +781,7 @@ mod tests {
         assert_eq!(node.value(ElementDataKey::Relationships), None);
         assert_eq!(node.value(ElementDataKey::Indices), None);
 
-        let mut items = node.items().into_iter();
+        let mut items = node.items();
         assert_eq!(
             items.next(),
             Some((
@@ -738,13 +808,13 @@ mod tests {
 
     #[test]
     fn rel_impl() {
-        let rel = BoltRelation {
-            id: BoltInteger::new(42),
-            start_node_id: BoltInteger::new(1),
-            end_node_id: BoltInteger::new(2),
-            typ: BoltString::from("KNOWS"),
-            properties: [("since".into(), 2017.into())].into_iter().collect(),
-        };
+        let rel = BoltRelation::new(
+            BoltInteger::new(42),
+            BoltInteger::new(1),
+            BoltInteger::new(2),
+            BoltString::from("KNOWS"),
+            [("since".into(), 2017.into())].into_iter().collect(),
+        );
 
         assert_eq!(
             rel.value(ElementDataKey::Id),
@@ -773,7 +843,7 @@ mod tests {
         assert_eq!(rel.value(ElementDataKey::Relationships), None);
         assert_eq!(rel.value(ElementDataKey::Indices), None);
 
-        let mut items = rel.items().into_iter();
+        let mut items = rel.items();
         assert_eq!(
             items.next(),
             Some((
@@ -838,7 +908,7 @@ mod tests {
         assert_eq!(unbounded_rel.value(ElementDataKey::EndNodeId), None);
         assert_eq!(unbounded_rel.value(ElementDataKey::Labels), None);
 
-        let mut items = unbounded_rel.items().into_iter();
+        let mut items = unbounded_rel.items();
         assert_eq!(
             items.next(),
             Some((
@@ -967,13 +1037,13 @@ mod tests {
 
     #[test]
     fn rel_deser() {
-        let rel = BoltRelation {
-            id: BoltInteger::new(42),
-            start_node_id: BoltInteger::new(1),
-            end_node_id: BoltInteger::new(2),
-            typ: BoltString::from("KNOWS"),
-            properties: [("since".into(), 2017.into())].into_iter().collect(),
-        };
+        let rel = BoltRelation::new(
+            BoltInteger::new(42),
+            BoltInteger::new(1),
+            BoltInteger::new(2),
+            BoltString::from("KNOWS"),
+            [("since".into(), 2017.into())].into_iter().collect(),
+        );
 
         let id = Id::deserialize(ElementDataDeserializer::new(&rel)).unwrap();
         assert_eq!(id, Id(42));
@@ -1068,13 +1138,13 @@ mod tests {
 
     #[test]
     fn rel_deser_map() {
-        let rel = BoltRelation {
-            id: BoltInteger::new(42),
-            start_node_id: BoltInteger::new(1),
-            end_node_id: BoltInteger::new(2),
-            typ: BoltString::from("KNOWS"),
-            properties: [("since".into(), 2017.into())].into_iter().collect(),
-        };
+        let rel = BoltRelation::new(
+            BoltInteger::new(42),
+            BoltInteger::new(1),
+            BoltInteger::new(2),
+            BoltString::from("KNOWS"),
+            [("since".into(), 2017.into())].into_iter().collect(),
+        );
 
         let knows = HashMap::<ElementDataKey, BoltType>::deserialize(MapAccessDeserializer::new(
             ElementMapAccess::new(rel.items()),
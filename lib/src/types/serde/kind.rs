@@ -20,4 +20,5 @@ crate::cenum!(BoltKind {
     DateTime,
     LocalDateTime,
     DateTimeZoneId,
+    Vector,
 });
@@ -3,6 +3,8 @@ use std::collections::{HashMap, HashSet};
 use chrono::FixedOffset;
 pub use error::DeError;
 pub use kind::BoltKind;
+#[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+pub(crate) use vector::BoltVectorVisitor;
 
 use crate::BoltType;
 
@@ -20,6 +22,7 @@ mod rel;
 mod time;
 mod typ;
 mod urel;
+mod vector;
 
 /// Newtype to extract the node id or relationship id during deserialization.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
@@ -33,6 +36,20 @@ pub struct StartNodeId(pub u64);
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct EndNodeId(pub u64);
 
+/// Newtype to extract the node element id or relationship element id during deserialization.
+///
+/// Element ids are only available when the server speaks Bolt 5.0 or newer.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ElementId<T = String>(pub T);
+
+/// Newtype to extract the start node element id of a relationship during deserialization.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct StartNodeElementId<T = String>(pub T);
+
+/// Newtype to extract the end node element id of a relationship during deserialization.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct EndNodeElementId<T = String>(pub T);
+
 /// Newtype to extract the node labels during deserialization.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Labels<Coll = Vec<String>>(pub Coll);
@@ -1,11 +1,11 @@
 use crate::{
     types::{
         serde::{
-            builder::{BoltNodeBuilder, Id},
+            builder::{BoltNodeBuilder, ElementId, Id},
             element::{ElementDataDeserializer, ElementDataKey},
             BoltKind, Properties,
         },
-        BoltList, BoltNode,
+        BoltList, BoltNode, BoltString,
     },
     BoltMap, DeError, Labels, Node,
 };
@@ -46,8 +46,9 @@ impl<'de> Deserialize<'de> for BoltNode {
         const ID: &str = "42.<id>";
         const LABELS: &str = "42.<labels>";
         const PROPERTIES: &str = "42.<properties>";
+        const ELEMENT_ID: &str = "42.<element_id>";
 
-        const FIELDS: &[&str] = &[ID, LABELS, PROPERTIES];
+        const FIELDS: &[&str] = &[ID, LABELS, PROPERTIES, ELEMENT_ID];
 
         struct BoltNodeVisitor;
 
@@ -72,6 +73,9 @@ impl<'de> Deserialize<'de> for BoltNode {
                         }
                         PROPERTIES => builder
                             .properties(|| map.next_value::<Properties<BoltMap>>().map(|l| l.0))?,
+                        ELEMENT_ID => {
+                            builder.element_id(|| map.next_value::<ElementId>().map(|i| i.0))?
+                        }
                         otherwise => Err(Error::unknown_field(otherwise, FIELDS))?,
                     }
                 }
@@ -105,10 +109,13 @@ impl<'de> Visitor<'de> for BoltNodeVisitor {
                 ElementDataKey::Id => builder.id(|| map.next_value())?,
                 ElementDataKey::Labels => builder.labels(|| map.next_value())?,
                 ElementDataKey::Properties => builder.properties(|| map.next_value())?,
+                ElementDataKey::ElementId => {
+                    builder.element_id(|| map.next_value::<BoltString>().map(Some))?
+                }
                 otherwise => {
                     return Err(Error::unknown_field(
                         otherwise.name(),
-                        &["Id", "Labels", "Properties"],
+                        &["Id", "Labels", "Properties", "ElementId"],
                     ))
                 }
             }
@@ -243,11 +250,7 @@ mod tests {
         .into_iter()
         .collect();
 
-        BoltNode {
-            id,
-            labels,
-            properties,
-        }
+        BoltNode::new(id, labels, properties)
     }
 
     #[test]
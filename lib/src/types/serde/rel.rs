@@ -1,7 +1,10 @@
 use crate::{
     types::{
         serde::{
-            builder::{BoltRelationBuilder, EndNodeId, Id, StartNodeId},
+            builder::{
+                BoltRelationBuilder, ElementId, EndNodeElementId, EndNodeId, Id,
+                StartNodeElementId, StartNodeId,
+            },
             element::{ElementDataDeserializer, ElementDataKey},
             BoltKind, Properties,
         },
@@ -48,8 +51,20 @@ impl<'de> Deserialize<'de> for BoltRelation {
         const EID: &str = "42.<end_node_id>";
         const TYP: &str = "42.<type>";
         const PROPS: &str = "42.<properties>";
-
-        const FIELDS: &[&str] = &[ID, SID, EID, TYP, PROPS];
+        const ELEMENT_ID: &str = "42.<element_id>";
+        const START_ELEMENT_ID: &str = "42.<start_node_element_id>";
+        const END_ELEMENT_ID: &str = "42.<end_node_element_id>";
+
+        const FIELDS: &[&str] = &[
+            ID,
+            SID,
+            EID,
+            TYP,
+            PROPS,
+            ELEMENT_ID,
+            START_ELEMENT_ID,
+            END_ELEMENT_ID,
+        ];
 
         struct BoltRelationVisitor;
 
@@ -77,6 +92,15 @@ impl<'de> Deserialize<'de> for BoltRelation {
                         TYP => builder.typ(|| map.next_value::<Type<BoltString>>().map(|t| t.0))?,
                         PROPS => builder
                             .properties(|| map.next_value::<Properties<BoltMap>>().map(|t| t.0))?,
+                        ELEMENT_ID => {
+                            builder.element_id(|| map.next_value::<ElementId>().map(|i| i.0))?
+                        }
+                        START_ELEMENT_ID => builder.start_node_element_id(|| {
+                            map.next_value::<StartNodeElementId>().map(|i| i.0)
+                        })?,
+                        END_ELEMENT_ID => builder.end_node_element_id(|| {
+                            map.next_value::<EndNodeElementId>().map(|i| i.0)
+                        })?,
                         otherwise => Err(Error::unknown_field(otherwise, FIELDS))?,
                     }
                 }
@@ -112,10 +136,28 @@ impl<'de> Visitor<'de> for BoltRelationVisitor {
                 ElementDataKey::EndNodeId => builder.end_node_id(|| map.next_value())?,
                 ElementDataKey::Type => builder.typ(|| map.next_value())?,
                 ElementDataKey::Properties => builder.properties(|| map.next_value())?,
+                ElementDataKey::ElementId => {
+                    builder.element_id(|| map.next_value::<BoltString>().map(Some))?
+                }
+                ElementDataKey::StartNodeElementId => {
+                    builder.start_node_element_id(|| map.next_value::<BoltString>().map(Some))?
+                }
+                ElementDataKey::EndNodeElementId => {
+                    builder.end_node_element_id(|| map.next_value::<BoltString>().map(Some))?
+                }
                 otherwise => {
                     return Err(Error::unknown_field(
                         otherwise.name(),
-                        &["Id", "StartNodeId", "EndNodeId", "Type", "Properties"],
+                        &[
+                            "Id",
+                            "StartNodeId",
+                            "EndNodeId",
+                            "Type",
+                            "Properties",
+                            "ElementId",
+                            "StartNodeElementId",
+                            "EndNodeElementId",
+                        ],
                     ))
                 }
             }
@@ -252,13 +294,7 @@ mod tests {
         .into_iter()
         .collect();
 
-        BoltRelation {
-            id,
-            start_node_id,
-            end_node_id,
-            properties,
-            typ,
-        }
+        BoltRelation::new(id, start_node_id, end_node_id, typ, properties)
     }
 
     #[test]
@@ -8,6 +8,7 @@ use crate::{
             point::{BoltPointDeserializer, BoltPointVisitor},
             rel::BoltRelationVisitor,
             urel::BoltUnboundedRelationVisitor,
+            vector::BoltVectorVisitor,
             BoltKind,
         },
         BoltBoolean, BoltBytes, BoltDate, BoltDateTime, BoltDateTimeZoneId, BoltFloat, BoltInteger,
@@ -259,6 +260,9 @@ impl<'de> Visitor<'de> for BoltTypeVisitor {
             BoltKind::DateTimeZoneId => variant
                 .tuple_variant(1, BoltDateTimeVisitor::<BoltDateTimeZoneId>::new())
                 .map(BoltType::DateTimeZoneId),
+            BoltKind::Vector => variant
+                .tuple_variant(2, BoltVectorVisitor)
+                .map(BoltType::Vector),
         }
     }
 }
@@ -279,6 +283,7 @@ impl<'de> Deserializer<'de> for BoltTypeDeserializer<'de> {
             BoltType::Bytes(v) => visitor.visit_seq(SeqDeserializer::new(v.value.iter().copied())),
             BoltType::Point2D(p) => p.into_deserializer().deserialize_seq(visitor),
             BoltType::Point3D(p) => p.into_deserializer().deserialize_seq(visitor),
+            BoltType::Vector(v) => v.deserialize_seq(visitor),
             _ => self.unexpected(visitor),
         }
     }
@@ -668,7 +673,9 @@ impl<'de> Deserializer<'de> for BoltTypeDeserializer<'de> {
             BoltType::Null(_) => self.deserialize_unit(visitor),
             BoltType::Integer(_) => self.deserialize_i64(visitor),
             BoltType::Float(_) => self.deserialize_f64(visitor),
-            BoltType::List(_) | BoltType::Bytes(_) => self.deserialize_seq(visitor),
+            BoltType::List(_) | BoltType::Bytes(_) | BoltType::Vector(_) => {
+                self.deserialize_seq(visitor)
+            }
             BoltType::Date(_)
             | BoltType::Time(_)
             | BoltType::LocalTime(_)
@@ -794,6 +801,7 @@ impl BoltType {
             BoltType::DateTime(_) => Unexp::Other("DateTime"),
             BoltType::LocalDateTime(_) => Unexp::Other("LocalDateTime"),
             BoltType::DateTimeZoneId(_) => Unexp::Other("DateTimeZoneId"),
+            BoltType::Vector(_) => Unexp::Other("Vector"),
         };
 
         Err(DeError::invalid_type(typ, expected))
@@ -835,6 +843,7 @@ impl<'de> EnumAccess<'de> for BoltEnum<'de> {
             BoltType::DateTime(_) => BoltKind::DateTime,
             BoltType::LocalDateTime(_) => BoltKind::LocalDateTime,
             BoltType::DateTimeZoneId(_) => BoltKind::DateTimeZoneId,
+            BoltType::Vector(_) => BoltKind::Vector,
         };
         let val = seed.deserialize(kind.into_deserializer())?;
         Ok((val, self))
@@ -886,6 +895,7 @@ impl<'de> VariantAccess<'de> for BoltEnum<'de> {
             BoltType::DateTime(dt) => visitor.visit_map(dt.map_access()),
             BoltType::LocalDateTime(dt) => visitor.visit_map(dt.map_access()),
             BoltType::DateTimeZoneId(dt) => visitor.visit_map(dt.map_access()),
+            BoltType::Vector(v) => v.deserialize_fields(visitor),
         }
     }
 
@@ -1173,11 +1183,7 @@ mod tests {
         .into_iter()
         .collect();
 
-        let node = BoltNode {
-            id,
-            labels,
-            properties,
-        };
+        let node = BoltNode::new(id, labels, properties);
         let node = BoltType::Node(node);
 
         let actual = node.to::<Person>().unwrap();
@@ -1215,13 +1221,7 @@ mod tests {
         .into_iter()
         .collect();
 
-        let relation = BoltRelation {
-            id,
-            start_node_id,
-            end_node_id,
-            properties,
-            typ,
-        };
+        let relation = BoltRelation::new(id, start_node_id, end_node_id, typ, properties);
         let relation = BoltType::Relation(relation);
 
         let actual = relation.to::<Person>().unwrap();
@@ -1257,11 +1257,7 @@ mod tests {
         .into_iter()
         .collect();
 
-        let relation = BoltUnboundedRelation {
-            id,
-            properties,
-            typ,
-        };
+        let relation = BoltUnboundedRelation::new(id, typ, properties);
         let relation = BoltType::UnboundedRelation(relation);
 
         let actual = relation.to::<Person>().unwrap();
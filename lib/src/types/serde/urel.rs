@@ -1,7 +1,7 @@
 use crate::{
     types::{
         serde::{
-            builder::{BoltUnboundedRelationBuilder, Id},
+            builder::{BoltUnboundedRelationBuilder, ElementId, Id},
             element::{ElementDataDeserializer, ElementDataKey},
             BoltKind, Properties,
         },
@@ -46,8 +46,9 @@ impl<'de> Deserialize<'de> for BoltUnboundedRelation {
         const ID: &str = "42.<id>";
         const TYP: &str = "42.<type>";
         const PROPS: &str = "42.<properties>";
+        const ELEMENT_ID: &str = "42.<element_id>";
 
-        const FIELDS: &[&str] = &[ID, TYP, PROPS];
+        const FIELDS: &[&str] = &[ID, TYP, PROPS, ELEMENT_ID];
 
         struct BoltUnboundedRelationVisitor;
 
@@ -70,6 +71,9 @@ impl<'de> Deserialize<'de> for BoltUnboundedRelation {
                         TYP => builder.typ(|| map.next_value::<Type<BoltString>>().map(|t| t.0))?,
                         PROPS => builder
                             .properties(|| map.next_value::<Properties<BoltMap>>().map(|o| o.0))?,
+                        ELEMENT_ID => {
+                            builder.element_id(|| map.next_value::<ElementId>().map(|i| i.0))?
+                        }
                         otherwise => Err(Error::unknown_field(otherwise, FIELDS))?,
                     }
                 }
@@ -107,10 +111,13 @@ impl<'de> Visitor<'de> for BoltUnboundedRelationVisitor {
                 ElementDataKey::Id => builder.id(|| map.next_value())?,
                 ElementDataKey::Type => builder.typ(|| map.next_value())?,
                 ElementDataKey::Properties => builder.properties(|| map.next_value())?,
+                ElementDataKey::ElementId => {
+                    builder.element_id(|| map.next_value::<BoltString>().map(Some))?
+                }
                 otherwise => {
                     return Err(Error::unknown_field(
                         otherwise.name(),
-                        &["Id", "Type", "Properties"],
+                        &["Id", "Type", "Properties", "ElementId"],
                     ))
                 }
             }
@@ -245,11 +252,7 @@ mod tests {
         .into_iter()
         .collect();
 
-        BoltUnboundedRelation {
-            id,
-            properties,
-            typ,
-        }
+        BoltUnboundedRelation::new(id, typ, properties)
     }
 
     #[test]
@@ -0,0 +1,96 @@
+use std::fmt;
+
+use bytes::Bytes;
+use serde::de::{value::SeqDeserializer, Error, SeqAccess, Visitor};
+
+use crate::{
+    types::{vector::each_vector, BoltVector},
+    DeError,
+};
+
+impl BoltVector {
+    /// Deserializes the elements, e.g. into a `Vec<f32>` or `Vec<f64>`.
+    pub(crate) fn deserialize_seq<'de, V>(&'de self, visitor: V) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        each_vector!(self, elements => {
+            visitor.visit_seq(SeqDeserializer::new(elements.iter().copied()))
+        })
+    }
+
+    /// Deserializes the struct fields as they are sent on the wire:
+    /// the element type marker and the big endian elements, both as bytes.
+    pub(crate) fn deserialize_fields<'de, V>(&self, visitor: V) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        let type_marker = [self.type_marker()];
+        let data = self.to_be_bytes();
+        let fields = [&type_marker[..], &data[..]];
+        visitor.visit_seq(SeqDeserializer::new(fields.into_iter()))
+    }
+}
+
+/// Reads a [`BoltVector`] from its two struct fields.
+pub(crate) struct BoltVectorVisitor;
+
+impl<'de> Visitor<'de> for BoltVectorVisitor {
+    type Value = BoltVector;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a valid Bolt Vector struct")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let type_marker = seq
+            .next_element::<Bytes>()?
+            .ok_or_else(|| Error::missing_field("type_marker"))?;
+        let data = seq
+            .next_element::<Bytes>()?
+            .ok_or_else(|| Error::missing_field("data"))?;
+
+        BoltVector::from_be_bytes(&type_marker, &data).map_err(Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BoltType, Row};
+
+    #[test]
+    fn vector_to_floats() {
+        let value = BoltType::Vector(BoltVector::F32(vec![1.0, 2.5]));
+
+        assert_eq!(value.to::<Vec<f32>>().unwrap(), vec![1.0, 2.5]);
+        assert_eq!(value.to::<Vec<f64>>().unwrap(), vec![1.0, 2.5]);
+    }
+
+    #[test]
+    fn int_vector_to_floats() {
+        let value = BoltType::Vector(BoltVector::I8(vec![-1, 42]));
+
+        assert_eq!(value.to::<Vec<f32>>().unwrap(), vec![-1.0, 42.0]);
+        assert_eq!(value.to::<Vec<i64>>().unwrap(), vec![-1, 42]);
+    }
+
+    #[test]
+    fn vector_to_bolt_type() {
+        let value = BoltType::Vector(BoltVector::F64(vec![0.5, -0.25]));
+
+        assert_eq!(value.to::<BoltType>().unwrap(), value);
+    }
+
+    #[test]
+    fn vector_from_row() {
+        let fields = vec![BoltType::from("embedding")].into();
+        let data = vec![BoltType::from(BoltVector::from(vec![0.25_f32, 0.5]))].into();
+        let row = Row::new(fields, data);
+
+        assert_eq!(row.get::<Vec<f32>>("embedding").unwrap(), vec![0.25, 0.5]);
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
     errors::{Error, Result},
     types::BoltWireFormat,
-    version::Version,
+    version::{Encoding, Version},
     DeError,
 };
 use bytes::{Buf, BufMut, Bytes, BytesMut};
@@ -86,7 +86,7 @@ impl BoltWireFormat for BoltString {
         }
     }
 
-    fn write_into(&self, _version: Version, bytes: &mut BytesMut) -> Result<()> {
+    fn write_into(&self, _encoding: Encoding, bytes: &mut BytesMut) -> Result<()> {
         let required_bytes = self.value.len();
         match self.value.len() {
             0..=15 => {
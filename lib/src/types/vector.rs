@@ -0,0 +1,207 @@
+use crate::{
+    errors::{Error, Result},
+    types::{BoltBytes, BoltWireFormat},
+    version::{Encoding, Version},
+};
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+const SIGNATURE: [u8; 2] = [0xB2, 0x56];
+
+/// A vector of numbers, as stored in Neo4j's native VECTOR type.
+/// Vectors are only supported by servers speaking Bolt 6.0 or newer.
+/// The driver does not negotiate Bolt 6.0 yet, so vectors cannot be sent as parameters for now.
+///
+/// Each variant holds the elements with the type they are stored as on the server.
+#[derive(Debug, PartialEq, Clone)]
+pub enum BoltVector {
+    I8(Vec<i8>),
+    I16(Vec<i16>),
+    I32(Vec<i32>),
+    I64(Vec<i64>),
+    F32(Vec<f32>),
+    F64(Vec<f64>),
+}
+
+macro_rules! each_vector {
+    ($vector:expr, $elements:ident => $body:expr) => {
+        match $vector {
+            BoltVector::I8($elements) => $body,
+            BoltVector::I16($elements) => $body,
+            BoltVector::I32($elements) => $body,
+            BoltVector::I64($elements) => $body,
+            BoltVector::F32($elements) => $body,
+            BoltVector::F64($elements) => $body,
+        }
+    };
+}
+
+pub(crate) use each_vector;
+
+impl BoltVector {
+    pub fn len(&self) -> usize {
+        each_vector!(self, elements => elements.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The PackStream marker of the element type.
+    pub(crate) fn type_marker(&self) -> u8 {
+        match self {
+            BoltVector::I8(_) => 0xC8,
+            BoltVector::I16(_) => 0xC9,
+            BoltVector::I32(_) => 0xCA,
+            BoltVector::I64(_) => 0xCB,
+            BoltVector::F32(_) => 0xC6,
+            BoltVector::F64(_) => 0xC1,
+        }
+    }
+
+    /// The elements, encoded as big endian values one after the other.
+    pub(crate) fn to_be_bytes(&self) -> Bytes {
+        let mut bytes = BytesMut::new();
+        match self {
+            BoltVector::I8(v) => v.iter().for_each(|e| bytes.put_i8(*e)),
+            BoltVector::I16(v) => v.iter().for_each(|e| bytes.put_i16(*e)),
+            BoltVector::I32(v) => v.iter().for_each(|e| bytes.put_i32(*e)),
+            BoltVector::I64(v) => v.iter().for_each(|e| bytes.put_i64(*e)),
+            BoltVector::F32(v) => v.iter().for_each(|e| bytes.put_f32(*e)),
+            BoltVector::F64(v) => v.iter().for_each(|e| bytes.put_f64(*e)),
+        }
+        bytes.freeze()
+    }
+
+    pub(crate) fn from_be_bytes(type_marker: &[u8], mut data: &[u8]) -> Result<Self> {
+        fn read<T>(data: &mut &[u8], size: usize, get: impl Fn(&mut &[u8]) -> T) -> Result<Vec<T>> {
+            if data.len() % size != 0 {
+                return Err(Error::InvalidTypeMarker(format!(
+                    "vector data of {} bytes does not fit elements of {} bytes",
+                    data.len(),
+                    size
+                )));
+            }
+            Ok((0..data.len() / size).map(|_| get(data)).collect())
+        }
+
+        let vector = match type_marker {
+            [0xC8] => BoltVector::I8(read(&mut data, 1, |b| b.get_i8())?),
+            [0xC9] => BoltVector::I16(read(&mut data, 2, |b| b.get_i16())?),
+            [0xCA] => BoltVector::I32(read(&mut data, 4, |b| b.get_i32())?),
+            [0xCB] => BoltVector::I64(read(&mut data, 8, |b| b.get_i64())?),
+            [0xC6] => BoltVector::F32(read(&mut data, 4, |b| b.get_f32())?),
+            [0xC1] => BoltVector::F64(read(&mut data, 8, |b| b.get_f64())?),
+            otherwise => {
+                return Err(Error::InvalidTypeMarker(format!(
+                    "invalid vector type marker {:02X?}",
+                    otherwise
+                )))
+            }
+        };
+        Ok(vector)
+    }
+}
+
+macro_rules! impl_from_vec {
+    ($($case:ident($t:ty)),+ $(,)?) => {
+        $(
+            impl From<Vec<$t>> for BoltVector {
+                fn from(value: Vec<$t>) -> Self {
+                    BoltVector::$case(value)
+                }
+            }
+
+            impl From<&[$t]> for BoltVector {
+                fn from(value: &[$t]) -> Self {
+                    BoltVector::$case(value.to_vec())
+                }
+            }
+        )+
+    };
+}
+
+impl_from_vec!(I8(i8), I16(i16), I32(i32), I64(i64), F32(f32), F64(f64));
+
+impl BoltWireFormat for BoltVector {
+    fn can_parse(_version: Version, input: &[u8]) -> bool {
+        input.len() > 1 && input[..2] == SIGNATURE
+    }
+
+    fn parse(version: Version, input: &mut Bytes) -> Result<Self> {
+        input.advance(SIGNATURE.len());
+        let type_marker = BoltBytes::parse(version, input)?;
+        let data = BoltBytes::parse(version, input)?;
+        Self::from_be_bytes(&type_marker.value, &data.value)
+    }
+
+    fn write_into(&self, encoding: Encoding, _bytes: &mut BytesMut) -> Result<()> {
+        // Vectors are sent since Bolt 6.0, which is not negotiated yet.
+        Err(Error::UnsupportedType("Vector", encoding.version))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_encode_vector_elements() {
+        let vector = BoltVector::from(vec![1.0_f32, -2.5]);
+
+        assert_eq!(vector.type_marker(), 0xC6);
+        assert_eq!(
+            vector.to_be_bytes(),
+            Bytes::from_static(&[0x3F, 0x80, 0x00, 0x00, 0xC0, 0x20, 0x00, 0x00])
+        );
+    }
+
+    #[test]
+    fn should_deserialize_vector() {
+        let mut input = Bytes::from_static(&[
+            0xB2, 0x56, 0xCC, 0x01, 0xC9, 0xCC, 0x04, 0x00, 0x2A, 0xFF, 0xFE,
+        ]);
+
+        let vector = BoltVector::parse(Version::V5_7, &mut input).unwrap();
+
+        assert_eq!(vector, BoltVector::I16(vec![42, -2]));
+    }
+
+    #[test]
+    fn should_roundtrip_all_element_types() {
+        let vectors = [
+            BoltVector::I8(vec![i8::MIN, 0, i8::MAX]),
+            BoltVector::I16(vec![i16::MIN, 0, i16::MAX]),
+            BoltVector::I32(vec![i32::MIN, 0, i32::MAX]),
+            BoltVector::I64(vec![i64::MIN, 0, i64::MAX]),
+            BoltVector::F32(vec![f32::MIN, 0.5, f32::MAX]),
+            BoltVector::F64(vec![f64::MIN, 0.5, f64::MAX]),
+        ];
+
+        for vector in vectors {
+            let data = vector.to_be_bytes();
+            assert_eq!(
+                BoltVector::from_be_bytes(&[vector.type_marker()], &data).unwrap(),
+                vector
+            );
+        }
+    }
+
+    #[test]
+    fn should_not_serialize_vector_without_bolt_6() {
+        let vector = BoltVector::from(vec![1_i8]);
+
+        let error = vector.into_bytes(Version::V5_7).unwrap_err();
+
+        assert!(matches!(
+            error,
+            Error::UnsupportedType("Vector", Version::V5_7)
+        ));
+    }
+
+    #[test]
+    fn should_reject_truncated_data() {
+        let error = BoltVector::from_be_bytes(&[0xCA], &[0, 0, 1]).unwrap_err();
+
+        assert!(matches!(error, Error::InvalidTypeMarker(_)));
+    }
+}
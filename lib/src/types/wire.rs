@@ -1,6 +1,6 @@
 use bytes::{Bytes, BytesMut};
 
-use crate::{errors::Result, Version};
+use crate::{errors::Result, version::Encoding, Version};
 
 pub(crate) trait BoltWireFormat: Sized {
     // TODO: remove
@@ -8,11 +8,11 @@ pub(crate) trait BoltWireFormat: Sized {
 
     fn parse(version: Version, input: &mut Bytes) -> Result<Self>;
 
-    fn write_into(&self, version: Version, bytes: &mut BytesMut) -> Result<()>;
+    fn write_into(&self, encoding: Encoding, bytes: &mut BytesMut) -> Result<()>;
 
-    fn into_bytes(self, version: Version) -> Result<Bytes> {
+    fn into_bytes(self, encoding: impl Into<Encoding>) -> Result<Bytes> {
         let mut bytes = BytesMut::new();
-        self.write_into(version, &mut bytes)?;
+        self.write_into(encoding.into(), &mut bytes)?;
         Ok(bytes.freeze())
     }
 }
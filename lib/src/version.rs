@@ -1,47 +1,237 @@
 use crate::errors::{Error, Result};
 use bytes::{BufMut, BytesMut};
-use std::cmp::PartialEq;
+use std::cmp::{Ordering, PartialEq};
 use std::fmt::{Debug, Display, Formatter};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord)]
 #[non_exhaustive]
 pub enum Version {
+    /// Bolt 3.0, as spoken by Neo4j 3.5.
+    /// There is only one database and results are always pulled in full.
+    V3,
     V4,
     V4_1,
     V4_3,
     V4_4,
+    V5_0,
+    V5_1,
+    V5_2,
+    V5_3,
+    V5_4,
+    V5_5,
+    V5_6,
+    V5_7,
 }
 
+/// How values are written for the server.
+/// Besides the version, this depends on whether the server accepted the `utc` patch
+/// for Bolt 4.4 in HELLO, which sends date times like Bolt 5.0.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) struct Encoding {
+    pub(crate) version: Version,
+    pub(crate) utc: bool,
+}
+
+impl Encoding {
+    pub(crate) fn new(version: Version, utc_patch: bool) -> Self {
+        let utc = version >= Version::V5_0 || (version == Version::V4_4 && utc_patch);
+        Self { version, utc }
+    }
+}
+
+impl From<Version> for Encoding {
+    fn from(version: Version) -> Self {
+        Self::new(version, false)
+    }
+}
+
+/// Announces support for the manifest style handshake, introduced with Bolt 5.7.
+/// Servers that understand it reply with all the versions they support
+/// instead of picking one of the offered versions.
+pub(crate) const MANIFEST_V1: u32 = 0x0000_01FF;
+
+/// Identifies the driver to the server in HELLO, required since Bolt 5.3.
+pub(crate) const BOLT_AGENT: &str = concat!("neo4rs/", env!("CARGO_PKG_VERSION"));
+
+/// The feature capabilities the driver can agree on in the manifest handshake.
+/// None of the optional protocol features are implemented yet.
+pub(crate) const CAPABILITIES: u64 = 0;
+
+/// The version ranges offered in the handshake, next to the manifest marker.
+/// Ranges are understood by servers since 4.3, Neo4j 4.1 and 4.2 pick 4.1 as the newest version of its range.
+const OFFERED: [VersionRange; 3] = [
+    VersionRange::new(5, 7, 7),
+    VersionRange::new(4, 4, 1),
+    VersionRange::new(4, 1, 1),
+];
+
+/// The oldest version offered by default.
+/// Neo4j 3.5 needs [`Version::V3`] as minimum version.
+pub(crate) const DEFAULT_MIN_VERSION: Version = Version::V4;
+
 impl Version {
-    pub fn add_supported_versions(bytes: &mut BytesMut) {
+    /// All versions supported by the driver, from newest to oldest.
+    const SUPPORTED: [Version; 13] = [
+        Version::V5_7,
+        Version::V5_6,
+        Version::V5_5,
+        Version::V5_4,
+        Version::V5_3,
+        Version::V5_2,
+        Version::V5_1,
+        Version::V5_0,
+        Version::V4_4,
+        Version::V4_3,
+        Version::V4_1,
+        Version::V4,
+        Version::V3,
+    ];
+
+    /// Offers the versions down to `min_version` in the four slots of the handshake.
+    ///
+    /// The first slot announces the manifest and the others are taken by the ranges of Bolt 5, 4.4 and 4.1.
+    /// Bolt 3 takes the place of the manifest if it is the minimum version,
+    /// Bolt 5 is still agreed on with the range then.
+    pub fn add_supported_versions(bytes: &mut BytesMut, min_version: Version) {
         bytes.reserve(16);
-        bytes.put_u32(0x0404); // V4_4
-        bytes.put_u32(0x0304); // V4_3
-        bytes.put_u32(0x0104); // V4_1
-        bytes.put_u32(0x0004); // V4
+        let manifest = (min_version > Version::V3).then_some(MANIFEST_V1);
+        let bolt_3 = (min_version == Version::V3).then(|| VersionRange::new(3, 0, 0).to_u32());
+        let offered = manifest
+            .into_iter()
+            .chain(
+                OFFERED
+                    .into_iter()
+                    .filter_map(|range| range.down_to(min_version))
+                    .map(VersionRange::to_u32),
+            )
+            .chain(bolt_3);
+        let mut slots = 0;
+        for version in offered {
+            bytes.put_u32(version);
+            slots += 1;
+        }
+        for _ in slots..4 {
+            bytes.put_u32(0);
+        }
     }
 
     pub fn parse(version_bytes: [u8; 4]) -> Result<Version> {
         match version_bytes {
-            [0, 0, 4, 4] => Ok(Version::V4_4),
-            [0, 0, 3, 4] => Ok(Version::V4_3),
-            [0, 0, 1, 4] => Ok(Version::V4_1),
-            [0, 0, 0, 4] => Ok(Version::V4),
-            [0, 0, minor, major] => Err(Error::UnsupportedVersion(major, minor)),
+            [0, 0, 0, 0] => Err(Error::UnsupportedVersion(0, 0)),
+            [0, 0, minor, major] => Self::from_parts(major, minor)
+                .ok_or_else(|| Error::ProtocolMismatch(u32::from_be_bytes(version_bytes))),
             otherwise => Err(Error::ProtocolMismatch(u32::from_be_bytes(otherwise))),
         }
     }
+
+    /// Picks the highest version supported by both the server and the driver,
+    /// and not older than `min_version`, from the ranges that the server sent in the manifest handshake.
+    pub(crate) fn negotiate(
+        server_ranges: &[VersionRange],
+        min_version: Version,
+    ) -> Result<Version> {
+        Self::SUPPORTED
+            .into_iter()
+            .take_while(|v| *v >= min_version)
+            .find(|v| server_ranges.iter().any(|r| r.contains(*v)))
+            .ok_or_else(|| {
+                let (major, minor) = server_ranges
+                    .iter()
+                    .map(|r| (r.major, r.minor))
+                    .max()
+                    .unwrap_or_default();
+                Error::UnsupportedVersion(major, minor)
+            })
+    }
+
+    /// Encodes the version as sent by the client to confirm the manifest handshake.
+    pub(crate) fn to_bytes(self) -> [u8; 4] {
+        [0, 0, self.minor(), self.major()]
+    }
+
+    fn from_parts(major: u8, minor: u8) -> Option<Version> {
+        Self::SUPPORTED
+            .into_iter()
+            .find(|v| v.major() == major && v.minor() == minor)
+    }
+
+    fn major(self) -> u8 {
+        match self {
+            Version::V3 => 3,
+            Version::V4 | Version::V4_1 | Version::V4_3 | Version::V4_4 => 4,
+            Version::V5_0
+            | Version::V5_1
+            | Version::V5_2
+            | Version::V5_3
+            | Version::V5_4
+            | Version::V5_5
+            | Version::V5_6
+            | Version::V5_7 => 5,
+        }
+    }
+
+    fn minor(self) -> u8 {
+        match self {
+            Version::V3 | Version::V4 | Version::V5_0 => 0,
+            Version::V4_1 | Version::V5_1 => 1,
+            Version::V5_2 => 2,
+            Version::V4_3 | Version::V5_3 => 3,
+            Version::V4_4 | Version::V5_4 => 4,
+            Version::V5_5 => 5,
+            Version::V5_6 => 6,
+            Version::V5_7 => 7,
+        }
+    }
 }
 
 impl Display for Version {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Version::V4 => write!(f, "4.0"),
-            Version::V4_1 => write!(f, "4.1"),
-            Version::V4_3 => write!(f, "4.3"),
-            Version::V4_4 => write!(f, "4.4"),
+        write!(f, "{}.{}", self.major(), self.minor())
+    }
+}
+
+/// A range of minor versions, going down from `minor` to `minor - range`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) struct VersionRange {
+    major: u8,
+    minor: u8,
+    range: u8,
+}
+
+impl VersionRange {
+    const fn new(major: u8, minor: u8, range: u8) -> Self {
+        Self {
+            major,
+            minor,
+            range,
         }
     }
+
+    pub(crate) fn from_bytes([_, range, minor, major]: [u8; 4]) -> Self {
+        Self::new(major, minor, range)
+    }
+
+    fn to_u32(self) -> u32 {
+        u32::from_be_bytes([0, self.range, self.minor, self.major])
+    }
+
+    /// The part of the range that is not older than `min_version`, if any.
+    fn down_to(self, min_version: Version) -> Option<Self> {
+        match self.major.cmp(&min_version.major()) {
+            Ordering::Less => None,
+            Ordering::Equal => {
+                let range = self.minor.checked_sub(min_version.minor())?;
+                Some(Self::new(self.major, self.minor, self.range.min(range)))
+            }
+            Ordering::Greater => Some(self),
+        }
+    }
+
+    fn contains(self, version: Version) -> bool {
+        version.major() == self.major
+            && version.minor() <= self.minor
+            && version.minor() >= self.minor.saturating_sub(self.range)
+    }
 }
 
 #[cfg(test)]
@@ -50,9 +240,172 @@ mod tests {
 
     #[tokio::test]
     async fn should_parse_version() {
+        assert_eq!(Version::parse([0, 0, 7, 5]).unwrap(), Version::V5_7);
+        assert_eq!(Version::parse([0, 0, 6, 5]).unwrap(), Version::V5_6);
+        assert_eq!(Version::parse([0, 0, 5, 5]).unwrap(), Version::V5_5);
+        assert_eq!(Version::parse([0, 0, 4, 5]).unwrap(), Version::V5_4);
+        assert_eq!(Version::parse([0, 0, 3, 5]).unwrap(), Version::V5_3);
+        assert_eq!(Version::parse([0, 0, 2, 5]).unwrap(), Version::V5_2);
+        assert_eq!(Version::parse([0, 0, 1, 5]).unwrap(), Version::V5_1);
+        assert_eq!(Version::parse([0, 0, 0, 5]).unwrap(), Version::V5_0);
         assert_eq!(Version::parse([0, 0, 4, 4]).unwrap(), Version::V4_4);
         assert_eq!(Version::parse([0, 0, 3, 4]).unwrap(), Version::V4_3);
         assert_eq!(Version::parse([0, 0, 1, 4]).unwrap(), Version::V4_1);
         assert_eq!(Version::parse([0, 0, 0, 4]).unwrap(), Version::V4);
+        assert_eq!(Version::parse([0, 0, 0, 3]).unwrap(), Version::V3);
+    }
+
+    #[test]
+    fn should_only_report_unsupported_version_without_overlap() {
+        assert!(matches!(
+            Version::parse([0, 0, 0, 0]),
+            Err(Error::UnsupportedVersion(0, 0))
+        ));
+        assert!(matches!(
+            Version::parse([0, 0, 2, 4]),
+            Err(Error::ProtocolMismatch(0x0204))
+        ));
+        assert!(matches!(
+            Version::parse(*b"HTTP"),
+            Err(Error::ProtocolMismatch(0x48545450))
+        ));
+    }
+
+    #[test]
+    fn should_offer_supported_versions() {
+        let mut bytes = BytesMut::new();
+        Version::add_supported_versions(&mut bytes, DEFAULT_MIN_VERSION);
+        assert_eq!(
+            &bytes[..],
+            &[0, 0, 1, 0xFF, 0, 7, 7, 5, 0, 1, 4, 4, 0, 1, 1, 4]
+        );
+    }
+
+    #[test]
+    fn should_offer_versions_down_to_the_minimum() {
+        let offer = |min_version| {
+            let mut bytes = BytesMut::new();
+            Version::add_supported_versions(&mut bytes, min_version);
+            bytes
+        };
+        assert_eq!(
+            &offer(Version::V3)[..],
+            &[0, 7, 7, 5, 0, 1, 4, 4, 0, 1, 1, 4, 0, 0, 0, 3]
+        );
+        assert_eq!(
+            &offer(Version::V4_1)[..],
+            &[0, 0, 1, 0xFF, 0, 7, 7, 5, 0, 1, 4, 4, 0, 0, 1, 4]
+        );
+        assert_eq!(
+            &offer(Version::V4_4)[..],
+            &[0, 0, 1, 0xFF, 0, 7, 7, 5, 0, 0, 4, 4, 0, 0, 0, 0]
+        );
+        assert_eq!(
+            &offer(Version::V5_2)[..],
+            &[0, 0, 1, 0xFF, 0, 5, 7, 5, 0, 0, 0, 0, 0, 0, 0, 0]
+        );
+    }
+
+    /// Picks the first offered version that a server without support for ranges speaks,
+    /// like Neo4j 3.5 to 4.2 do.
+    fn pick_without_ranges(offer: &[u8], server: &[(u8, u8)]) -> Option<(u8, u8)> {
+        offer
+            .chunks(4)
+            .map(|slot| (slot[3], slot[2]))
+            .find(|version| server.contains(version))
+    }
+
+    #[test]
+    fn should_offer_bolt_4_1_to_neo4j_4_1_and_4_2() {
+        let neo4j_4_2 = [(4, 2), (4, 1), (4, 0)];
+        let neo4j_4_1 = [(4, 1), (4, 0)];
+        for min_version in [Version::V3, DEFAULT_MIN_VERSION, Version::V4_1] {
+            let mut bytes = BytesMut::new();
+            Version::add_supported_versions(&mut bytes, min_version);
+            assert_eq!(pick_without_ranges(&bytes, &neo4j_4_2), Some((4, 1)));
+            assert_eq!(pick_without_ranges(&bytes, &neo4j_4_1), Some((4, 1)));
+        }
+    }
+
+    #[test]
+    fn should_offer_bolt_3_to_neo4j_3_5_with_a_v3_minimum() {
+        let mut bytes = BytesMut::new();
+        Version::add_supported_versions(&mut bytes, Version::V3);
+        assert_eq!(pick_without_ranges(&bytes, &[(3, 0)]), Some((3, 0)));
+
+        let mut bytes = BytesMut::new();
+        Version::add_supported_versions(&mut bytes, DEFAULT_MIN_VERSION);
+        assert_eq!(pick_without_ranges(&bytes, &[(3, 0)]), None);
+    }
+
+    #[test]
+    fn should_only_offer_supported_versions() {
+        for range in OFFERED {
+            for minor in range.minor - range.range..=range.minor {
+                assert!(Version::from_parts(range.major, minor).is_some());
+            }
+        }
+    }
+
+    #[test]
+    fn should_negotiate_highest_common_version() {
+        let ranges = [
+            VersionRange::from_bytes([0, 0, 0, 6]),
+            VersionRange::from_bytes([0, 8, 8, 5]),
+            VersionRange::from_bytes([0, 4, 4, 4]),
+        ];
+        assert_eq!(
+            Version::negotiate(&ranges, DEFAULT_MIN_VERSION).unwrap(),
+            Version::V5_7
+        );
+
+        let ranges = [VersionRange::from_bytes([0, 0, 4, 4])];
+        assert_eq!(
+            Version::negotiate(&ranges, DEFAULT_MIN_VERSION).unwrap(),
+            Version::V4_4
+        );
+
+        let ranges = [VersionRange::from_bytes([0, 2, 3, 4])];
+        assert_eq!(
+            Version::negotiate(&ranges, DEFAULT_MIN_VERSION).unwrap(),
+            Version::V4_3
+        );
+    }
+
+    #[test]
+    fn should_fail_negotiation_without_overlap() {
+        let ranges = [
+            VersionRange::from_bytes([0, 0, 0, 6]),
+            VersionRange::from_bytes([0, 1, 9, 5]),
+        ];
+        assert!(matches!(
+            Version::negotiate(&ranges, DEFAULT_MIN_VERSION),
+            Err(Error::UnsupportedVersion(6, 0))
+        ));
+        assert!(matches!(
+            Version::negotiate(&[], DEFAULT_MIN_VERSION),
+            Err(Error::UnsupportedVersion(0, 0))
+        ));
+
+        let ranges = [VersionRange::from_bytes([0, 1, 4, 4])];
+        assert!(matches!(
+            Version::negotiate(&ranges, Version::V5_0),
+            Err(Error::UnsupportedVersion(4, 4))
+        ));
+    }
+
+    #[test]
+    fn should_only_apply_utc_patch_on_4_4() {
+        assert!(Encoding::new(Version::V4_4, true).utc);
+        assert!(!Encoding::new(Version::V4_4, false).utc);
+        assert!(!Encoding::new(Version::V4_3, true).utc);
+        assert!(Encoding::new(Version::V5_0, false).utc);
+    }
+
+    #[test]
+    fn should_display_version() {
+        assert_eq!(Version::V3.to_string(), "3.0");
+        assert_eq!(Version::V4.to_string(), "4.0");
+        assert_eq!(Version::V5_1.to_string(), "5.1");
     }
 }
@@ -93,7 +93,7 @@ fn derive_impl(ast: DeriveInput) -> Result<TokenStream, syn::Error> {
     let serialize_fields = fields.iter().flat_map(|o| o.iter()).map(|f| {
         let name = &f.ident;
         quote! {
-            self.#name.write_into(version, bytes)?
+            self.#name.write_into(encoding, bytes)?
         }
     });
 
@@ -114,7 +114,7 @@ fn derive_impl(ast: DeriveInput) -> Result<TokenStream, syn::Error> {
                 })
             }
 
-            fn write_into(&self, version: crate::Version, bytes: &mut ::bytes::BytesMut) -> crate::errors::Result<()> {
+            fn write_into(&self, encoding: crate::version::Encoding, bytes: &mut ::bytes::BytesMut) -> crate::errors::Result<()> {
                 use ::bytes::BufMut;
                 #write_signature
                 #(#serialize_fields;)*